@@ -26,6 +26,7 @@ mod tests {
         Testnet3,
     };
 
+    use aleo_std::StorageMode;
     use tracing_test::traced_test;
 
     type CurrentNetwork = Testnet3;
@@ -38,12 +39,19 @@ mod tests {
         // Initialize the genesis block.
         let genesis = Block::<CurrentNetwork>::read_le(CurrentNetwork::genesis_bytes()).unwrap();
         // Initialize the ledger.
-        let ledger = Ledger::<_, ConsensusMemory<_>>::load(genesis, None).unwrap();
+        let ledger = Ledger::<_, ConsensusMemory<_>>::load(genesis, StorageMode::Production).unwrap();
         // Perform the sync.
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            let completed_height =
-                sync_ledger_with_cdn(TEST_BASE_URL, ledger.clone(), Default::default()).await.unwrap();
+            let completed_height = sync_ledger_with_cdn(
+                &[TEST_BASE_URL.to_string()],
+                &Default::default(),
+                ledger.clone(),
+                None,
+                Default::default(),
+            )
+            .await
+            .unwrap();
             assert_eq!(completed_height, ledger.latest_height());
         });
     }
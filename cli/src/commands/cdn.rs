@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ledger::get_block, storage_mode};
+use snarkos_node::cdn::{BundleEntry, LatestState, Manifest, ManifestSource, BLOCKS_PER_FILE};
+use snarkvm::prelude::{
+    block::Block,
+    store::{helpers::rocksdb::ConsensusDB, ConsensusStore},
+    Network,
+    Testnet3,
+};
+
+use aleo_std::StorageMode;
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Commands to host a CDN of block bundles.
+#[derive(Debug, Parser)]
+pub enum Cdn {
+    /// Builds the block bundles, 'latest.json' and 'manifest.json' of a CDN from the ledger.
+    ///
+    /// The directory can be served over HTTP, or used directly with `snarkos start --cdn file://<directory>`.
+    /// Existing bundles in the directory are kept, so the command can be run again to extend the CDN.
+    Build {
+        /// Specify the network of the ledger.
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Enables development mode, specify the unique ID of the local node.
+        #[clap(long)]
+        dev: Option<u16>,
+        /// Specify the directory of the node storage (overrides the default location).
+        #[clap(long = "storage-dir", conflicts_with = "dev")]
+        storage_dir: Option<PathBuf>,
+        /// The directory to write the block bundles to.
+        #[clap(long)]
+        to: PathBuf,
+        /// The height of the last block to include (defaults to the latest height).
+        #[clap(long)]
+        height: Option<u32>,
+    },
+}
+
+impl Cdn {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Build { network, dev, storage_dir, to, height } => match network {
+                3 => Self::build::<Testnet3>(storage_mode(dev, storage_dir), &to, height),
+                _ => bail!("Unsupported network ID {network}"),
+            },
+        }
+    }
+
+    /// Writes the block bundles from storage into the given directory.
+    fn build<N: Network>(storage_mode: StorageMode, directory: &Path, height: Option<u32>) -> Result<String> {
+        // Open the consensus storage.
+        let store = ConsensusStore::<N, ConsensusDB<N>>::open(storage_mode)?;
+        let block_store = store.block_store();
+        // Retrieve the latest height in storage.
+        let Some(latest_height) = block_store.heights().max().map(|height| *height) else {
+            bail!("The ledger is empty");
+        };
+        // Determine the height of the last block to include.
+        let height = height.unwrap_or(latest_height);
+        ensure!(height <= latest_height, "The given height ({height}) exceeds the latest height ({latest_height})");
+        // Determine the (exclusive) height of the last complete bundle, as the CDN only serves complete bundles.
+        let cdn_height = (height + 1) - (height + 1) % BLOCKS_PER_FILE;
+        ensure!(cdn_height > 0, "The ledger must contain at least {BLOCKS_PER_FILE} blocks");
+
+        std::fs::create_dir_all(directory)?;
+        // Load the existing manifest, if there is one.
+        let manifest_path = directory.join("manifest.json");
+        let mut manifest = match manifest_path.exists() {
+            true => ManifestSource::File(manifest_path.clone()).decode(&std::fs::read(&manifest_path)?)?,
+            false => Manifest::default(),
+        };
+
+        // Write each missing bundle, and add it to the manifest.
+        for start in (0..cdn_height).step_by(BLOCKS_PER_FILE as usize) {
+            let end = start + BLOCKS_PER_FILE;
+            let path = directory.join(format!("{start}.{end}.blocks"));
+            let is_written = path.exists();
+            if is_written && manifest.bundles.contains_key(&start) {
+                continue;
+            }
+            // Retrieve the blocks of the bundle.
+            let (bytes, blocks) = match is_written {
+                true => {
+                    let bytes = std::fs::read(&path)?;
+                    let blocks: Vec<Block<N>> = bincode::deserialize(&bytes)?;
+                    (bytes, blocks)
+                }
+                false => {
+                    let blocks =
+                        (start..end).map(|height| get_block(block_store, height)).collect::<Result<Vec<_>>>()?;
+                    (bincode::serialize(&blocks)?, blocks)
+                }
+            };
+            manifest.bundles.insert(start, BundleEntry::new(end, &bytes, &blocks)?);
+            // Write the bundle.
+            if !is_written {
+                write_atomic(&path, &bytes)?;
+                println!("Wrote blocks {start} to {end}");
+            }
+        }
+        // Write the manifest.
+        write_atomic(&manifest_path, &bincode::serialize(&serde_json::to_string(&manifest)?)?)?;
+
+        // Write the 'latest.json' file, which is bincode-encoded as on the CDN.
+        let latest = LatestState {
+            exclusive_height: cdn_height,
+            inclusive_height: cdn_height - 1,
+            hash: get_block(block_store, cdn_height - 1)?.hash().to_string(),
+        };
+        write_atomic(&directory.join("latest.json"), &bincode::serialize(&serde_json::to_string(&latest)?)?)?;
+
+        Ok(format!(
+            "✅ Built the CDN for blocks 0 to {} {}",
+            cdn_height - 1,
+            format!("(in \"{}\")", directory.display()).dimmed()
+        ))
+    }
+}
+
+/// Writes the bytes to a temporary file, and then renames it to the given path.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, bytes)?;
+    std::fs::rename(temp_path, path)?;
+    Ok(())
+}
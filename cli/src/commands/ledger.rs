@@ -0,0 +1,368 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::storage_mode;
+use snarkos_node::{cdn::BLOCKS_PER_FILE, load_ledger};
+use snarkvm::prelude::{
+    block::Block,
+    store::{helpers::rocksdb::ConsensusDB, BlockStorage, BlockStore, ConsensusStore},
+    FromBytes,
+    Network,
+    Testnet3,
+};
+
+use aleo_std::StorageMode;
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use rand::thread_rng;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+
+/// The magic bytes at the start of a ledger archive.
+const ARCHIVE_MAGIC: [u8; 8] = *b"SNARKLDG";
+/// The version of the ledger archive format.
+const ARCHIVE_VERSION: u16 = 1;
+/// The maximum size of a chunk in a ledger archive.
+const MAX_CHUNK_SIZE: u64 = 1 << 30; // 1 GiB
+
+/// Commands to export and import a snapshot of the ledger.
+///
+/// A ledger archive contains the blocks from genesis up to a given height, in checksummed chunks of
+/// the same 50-block bundles that the CDN serves. The node must be stopped while running these commands.
+#[derive(Debug, Parser)]
+pub enum Ledger {
+    /// Exports the ledger to an archive file.
+    Export {
+        /// Specify the network of the ledger.
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Enables development mode, specify the unique ID of the local node.
+        #[clap(long)]
+        dev: Option<u16>,
+        /// Specify the directory of the node storage (overrides the default location).
+        #[clap(long = "storage-dir", conflicts_with = "dev")]
+        storage_dir: Option<PathBuf>,
+        /// The path to write the archive to.
+        #[clap(long)]
+        to: PathBuf,
+        /// The height of the last block to export (defaults to the latest height).
+        #[clap(long)]
+        height: Option<u32>,
+    },
+    /// Imports the ledger from an archive file.
+    Import {
+        /// Specify the network of the ledger.
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Enables development mode, specify the unique ID of the local node.
+        #[clap(long)]
+        dev: Option<u16>,
+        /// Specify the directory of the node storage (overrides the default location).
+        #[clap(long = "storage-dir", conflicts_with = "dev")]
+        storage_dir: Option<PathBuf>,
+        /// The path to read the archive from.
+        #[clap(long)]
+        from: PathBuf,
+    },
+}
+
+impl Ledger {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Export { network, dev, storage_dir, to, height } => match network {
+                3 => Self::export::<Testnet3>(storage_mode(dev, storage_dir), to, height),
+                _ => bail!("Unsupported network ID {network}"),
+            },
+            Self::Import { network, dev, storage_dir, from } => match network {
+                3 => Self::import::<Testnet3>(storage_mode(dev, storage_dir), from),
+                _ => bail!("Unsupported network ID {network}"),
+            },
+        }
+    }
+
+    /// Exports the blocks from storage to an archive at the given path.
+    fn export<N: Network>(storage_mode: StorageMode, path: PathBuf, height: Option<u32>) -> Result<String> {
+        // Open the consensus storage.
+        let store = ConsensusStore::<N, ConsensusDB<N>>::open(storage_mode)?;
+        let block_store = store.block_store();
+        // Retrieve the latest height in storage.
+        let Some(latest_height) = block_store.heights().max().map(|height| *height) else {
+            bail!("The ledger is empty");
+        };
+        // Determine the height of the last block to export.
+        let height = height.unwrap_or(latest_height);
+        ensure!(height <= latest_height, "The given height ({height}) exceeds the latest height ({latest_height})");
+
+        // Write the archive to the path.
+        let mut writer = BufWriter::new(File::create(&path)?);
+        write_archive::<N>(&mut writer, height, |height| get_block(block_store, height))?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+
+        Ok(format!("✅ Exported blocks 0 to {height} {}", format!("(to \"{}\")", path.display()).dimmed()))
+    }
+
+    /// Imports the blocks from the archive at the given path into storage.
+    fn import<N: Network>(storage_mode: StorageMode, path: PathBuf) -> Result<String> {
+        // Read the header of the archive.
+        let mut reader = BufReader::new(File::open(&path)?);
+        let header = ArchiveHeader::read::<N>(&mut reader)?;
+        // Read the genesis block from the first chunk of the archive.
+        let first_chunk = read_chunk::<N>(&mut reader, 0, header.height)?;
+        let genesis = first_chunk[0].clone();
+        // Ensure the genesis block is the canonical one, if the node is not in development mode.
+        if storage_mode.dev().is_none() {
+            ensure!(
+                genesis == Block::from_bytes_le(N::genesis_bytes())?,
+                "The archive does not start from the genesis block of network {}",
+                N::ID
+            );
+        }
+
+        // Load the ledger.
+        let ledger = load_ledger::<N, ConsensusDB<N>>(genesis, &storage_mode)?;
+        let start_height = ledger.latest_height();
+        ensure!(start_height < header.height, "The ledger is already at height {start_height}");
+
+        // Check and insert each new block into the ledger.
+        let mut rng = thread_rng();
+        let mut process = |block: Block<N>| -> Result<()> {
+            if block.height() > ledger.latest_height() {
+                ledger.check_next_block(&block, &mut rng)?;
+                ledger.advance_to_next_block(&block)?;
+                // Log the progress at the end of each chunk.
+                if (block.height() + 1) % BLOCKS_PER_FILE == 0 {
+                    println!("Imported up to block {} of {}", block.height(), header.height);
+                }
+            }
+            Ok(())
+        };
+        first_chunk.into_iter().try_for_each(&mut process)?;
+        for start in (BLOCKS_PER_FILE..=header.height).step_by(BLOCKS_PER_FILE as usize) {
+            read_chunk::<N>(&mut reader, start, header.height)?.into_iter().try_for_each(&mut process)?;
+        }
+        // Ensure the archive has no trailing bytes.
+        ensure!(reader.read(&mut [0u8])? == 0, "The archive has trailing bytes");
+
+        Ok(format!(
+            "✅ Imported blocks {} to {} {}",
+            start_height + 1,
+            header.height,
+            format!("(from \"{}\")", path.display()).dimmed()
+        ))
+    }
+}
+
+/// The header of a ledger archive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct ArchiveHeader {
+    /// The height of the last block in the archive.
+    height: u32,
+}
+
+impl ArchiveHeader {
+    /// Writes the header for the given network.
+    fn write<N: Network>(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(&ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_le_bytes())?;
+        writer.write_all(&N::ID.to_le_bytes())?;
+        writer.write_all(&BLOCKS_PER_FILE.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        Ok(())
+    }
+
+    /// Reads the header, ensuring it matches the given network.
+    fn read<N: Network>(reader: &mut impl Read) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        ensure!(magic == ARCHIVE_MAGIC, "The file is not a ledger archive");
+        let version = u16::from_le_bytes(read_array(reader)?);
+        ensure!(version == ARCHIVE_VERSION, "Unsupported ledger archive version {version}");
+        let network = u16::from_le_bytes(read_array(reader)?);
+        ensure!(network == N::ID, "The archive is for network {network}, expected network {}", N::ID);
+        let blocks_per_chunk = u32::from_le_bytes(read_array(reader)?);
+        ensure!(blocks_per_chunk == BLOCKS_PER_FILE, "Unsupported number of blocks per chunk ({blocks_per_chunk})");
+        let height = u32::from_le_bytes(read_array(reader)?);
+        Ok(Self { height })
+    }
+}
+
+/// Writes an archive of the blocks from genesis up to the given height.
+///
+/// Each chunk is written as `[start][end][length][sha256 checksum][blocks]`, where the blocks
+/// are serialized exactly as in the CDN bundle `{start}.{end}.blocks`.
+fn write_archive<N: Network>(
+    writer: &mut impl Write,
+    height: u32,
+    mut get_block: impl FnMut(u32) -> Result<Block<N>>,
+) -> Result<()> {
+    // Write the header.
+    ArchiveHeader { height }.write::<N>(writer)?;
+    // Write each chunk.
+    for start in (0..=height).step_by(BLOCKS_PER_FILE as usize) {
+        // Retrieve the blocks of the chunk.
+        let end = start + BLOCKS_PER_FILE;
+        let blocks = (start..end.min(height + 1)).map(&mut get_block).collect::<Result<Vec<_>>>()?;
+        // Serialize the blocks, and compute the checksum.
+        let bytes = bincode::serialize(&blocks)?;
+        let checksum = Sha256::digest(&bytes);
+        // Write the chunk.
+        writer.write_all(&start.to_le_bytes())?;
+        writer.write_all(&end.to_le_bytes())?;
+        writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        writer.write_all(&checksum)?;
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+/// Reads the chunk starting at the given height, ensuring it is intact and contains the expected blocks.
+fn read_chunk<N: Network>(reader: &mut impl Read, start: u32, height: u32) -> Result<Vec<Block<N>>> {
+    // Read the chunk range.
+    let chunk_start = u32::from_le_bytes(read_array(reader)?);
+    let chunk_end = u32::from_le_bytes(read_array(reader)?);
+    ensure!(chunk_start == start && chunk_end == start + BLOCKS_PER_FILE, "Unexpected chunk {chunk_start}.{chunk_end}");
+    // Read the chunk bytes.
+    let length = u64::from_le_bytes(read_array(reader)?);
+    ensure!(length <= MAX_CHUNK_SIZE, "Chunk {chunk_start}.{chunk_end} is too large ({length} bytes)");
+    let checksum: [u8; 32] = read_array(reader)?;
+    let mut bytes = vec![0u8; length as usize];
+    reader.read_exact(&mut bytes)?;
+    // Ensure the checksum matches.
+    ensure!(Sha256::digest(&bytes)[..] == checksum, "Chunk {chunk_start}.{chunk_end} has an invalid checksum");
+
+    // Deserialize the blocks.
+    let blocks: Vec<Block<N>> = bincode::deserialize(&bytes)?;
+    // Ensure the blocks are in sequence.
+    let expected_heights = start..chunk_end.min(height + 1);
+    ensure!(
+        blocks.iter().map(|block| block.height()).eq(expected_heights),
+        "Chunk {chunk_start}.{chunk_end} does not contain the expected blocks"
+    );
+    Ok(blocks)
+}
+
+/// Returns the block at the given height from storage.
+pub(crate) fn get_block<N: Network, B: BlockStorage<N>>(
+    block_store: &BlockStore<N, B>,
+    height: u32,
+) -> Result<Block<N>> {
+    match block_store.get_block_hash(height)? {
+        Some(hash) => match block_store.get_block(&hash)? {
+            Some(block) => Ok(block),
+            None => bail!("Missing block {height} in storage"),
+        },
+        None => bail!("Missing block hash {height} in storage"),
+    }
+}
+
+/// Reads a fixed-size array from the reader.
+fn read_array<const SIZE: usize>(reader: &mut impl Read) -> Result<[u8; SIZE]> {
+    let mut bytes = [0u8; SIZE];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns an archive of the genesis block.
+    fn sample_archive() -> (Block<CurrentNetwork>, Vec<u8>) {
+        let genesis = Block::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let mut archive = Vec::new();
+        write_archive::<CurrentNetwork>(&mut archive, 0, |height| {
+            assert_eq!(height, 0);
+            Ok(genesis.clone())
+        })
+        .unwrap();
+        (genesis, archive)
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let (genesis, archive) = sample_archive();
+
+        // Read the archive.
+        let mut reader = archive.as_slice();
+        let header = ArchiveHeader::read::<CurrentNetwork>(&mut reader).unwrap();
+        assert_eq!(header, ArchiveHeader { height: 0 });
+        assert_eq!(read_chunk::<CurrentNetwork>(&mut reader, 0, header.height).unwrap(), vec![genesis]);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_archive_chunk_matches_cdn_bundle() {
+        let (genesis, archive) = sample_archive();
+
+        // Ensure the chunk payload is the CDN bundle of blocks 0 to 50.
+        let bundle = bincode::serialize(&vec![genesis]).unwrap();
+        assert!(archive.ends_with(&bundle));
+        let mut reader = &archive[archive.len() - bundle.len() - 32 - 8 - 8..];
+        assert_eq!(u32::from_le_bytes(read_array(&mut reader).unwrap()), 0);
+        assert_eq!(u32::from_le_bytes(read_array(&mut reader).unwrap()), BLOCKS_PER_FILE);
+    }
+
+    #[test]
+    fn test_archive_corruption() {
+        let (_, archive) = sample_archive();
+
+        // Ensure a corrupted chunk is rejected.
+        let mut corrupted = archive.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        let mut reader = corrupted.as_slice();
+        ArchiveHeader::read::<CurrentNetwork>(&mut reader).unwrap();
+        assert!(read_chunk::<CurrentNetwork>(&mut reader, 0, 0).is_err());
+
+        // Ensure a truncated chunk is rejected.
+        let mut reader = &archive[..archive.len() - 1];
+        ArchiveHeader::read::<CurrentNetwork>(&mut reader).unwrap();
+        assert!(read_chunk::<CurrentNetwork>(&mut reader, 0, 0).is_err());
+
+        // Ensure an archive for another network is rejected.
+        let mut corrupted = archive.clone();
+        corrupted[10] ^= 1;
+        assert!(ArchiveHeader::read::<CurrentNetwork>(&mut corrupted.as_slice()).is_err());
+
+        // Ensure a file that is not an archive is rejected.
+        assert!(ArchiveHeader::read::<CurrentNetwork>(&mut &archive[1..]).is_err());
+    }
+
+    #[test]
+    fn test_import_and_export_with_storage_dir() {
+        let (_, archive) = sample_archive();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage_dir = temp_dir.path().join("storage");
+        let storage_mode = storage_mode(None, Some(storage_dir.clone()));
+
+        // Ensure the import loads the ledger from the custom directory, which starts from the genesis block.
+        let archive_path = temp_dir.path().join("genesis.archive");
+        std::fs::write(&archive_path, &archive).unwrap();
+        let error = Ledger::import::<CurrentNetwork>(storage_mode.clone(), archive_path).unwrap_err();
+        assert!(error.to_string().contains("already at height 0"));
+        assert!(storage_dir.exists());
+
+        // Ensure the export reads the ledger back from the custom directory.
+        let export_path = temp_dir.path().join("export.archive");
+        Ledger::export::<CurrentNetwork>(storage_mode, export_path.clone(), None).unwrap();
+        assert_eq!(std::fs::read(export_path).unwrap(), archive);
+    }
+}
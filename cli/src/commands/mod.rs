@@ -15,21 +15,35 @@
 mod account;
 pub use account::*;
 
+mod cdn;
+pub use cdn::*;
+
 mod clean;
 pub use clean::*;
 
 mod developer;
 pub use developer::*;
 
+mod ledger;
+pub use ledger::*;
+
+mod signing_history;
+pub use signing_history::*;
+
 mod start;
 pub use start::*;
 
+mod token;
+pub use token::*;
+
 mod update;
 pub use update::*;
 
+use aleo_std::StorageMode;
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
 use clap::{builder::Styles, Parser};
+use std::path::PathBuf;
 
 const HEADER_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Yellow));
 const LITERAL_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Green));
@@ -53,12 +67,20 @@ pub struct CLI {
 pub enum Command {
     #[clap(subcommand)]
     Account(Account),
+    #[clap(subcommand)]
+    Cdn(Cdn),
     #[clap(name = "clean")]
     Clean(Clean),
     #[clap(subcommand)]
     Developer(Developer),
+    #[clap(subcommand)]
+    Ledger(Ledger),
+    #[clap(subcommand)]
+    SigningHistory(SigningHistory),
     #[clap(name = "start")]
     Start(Box<Start>),
+    #[clap(name = "token")]
+    Token(Token),
     #[clap(name = "update")]
     Update(Update),
 }
@@ -68,14 +90,26 @@ impl Command {
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Account(command) => command.parse(),
+            Self::Cdn(command) => command.parse(),
             Self::Clean(command) => command.parse(),
             Self::Developer(command) => command.parse(),
+            Self::Ledger(command) => command.parse(),
+            Self::SigningHistory(command) => command.parse(),
             Self::Start(command) => command.parse(),
+            Self::Token(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
     }
 }
 
+/// Returns the storage mode for the given development ID and storage directory.
+pub(crate) fn storage_mode(dev: Option<u16>, storage_dir: Option<PathBuf>) -> StorageMode {
+    match storage_dir {
+        Some(path) => StorageMode::Custom(path),
+        None => StorageMode::from(dev),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,4 +120,15 @@ mod tests {
         use clap::CommandFactory;
         CLI::command().debug_assert()
     }
+
+    #[test]
+    fn test_storage_dir_conflicts_with_dev() {
+        let storage_dir = ["--dev", "0", "--storage-dir", "/tmp/storage"];
+        for command in [&["snarkos", "clean"][..], &["snarkos", "ledger", "import", "--from", "ledger.archive"]] {
+            // Ensure the storage directory is parsed on its own.
+            assert!(CLI::try_parse_from(command.iter().chain(&storage_dir[2..])).is_ok());
+            // Ensure a custom storage directory cannot be combined with development mode.
+            assert!(CLI::try_parse_from(command.iter().chain(&storage_dir)).is_err());
+        }
+    }
 }
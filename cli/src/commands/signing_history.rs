@@ -0,0 +1,179 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::storage_mode;
+use snarkos_node::bft::{
+    helpers,
+    storage_service::{BFTFileStorage, BFTPersistentStorage, StorageBackend, StorageService},
+};
+use snarkvm::prelude::{Network, Testnet3};
+
+use aleo_std::StorageMode;
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Commands to export and import the signing history of a validator.
+///
+/// The signing history protects a validator from signing two different batches for the same round and author,
+/// and should be migrated along with the validator. The node must be stopped while running these commands.
+#[derive(Debug, Parser)]
+pub enum SigningHistory {
+    /// Exports the signing history to a JSON file.
+    Export {
+        /// Specify the network of the signing history.
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Enables development mode, specify the unique ID of the local node.
+        #[clap(long)]
+        dev: Option<u16>,
+        /// Specify the directory of the node storage (overrides the default location).
+        #[clap(long = "storage-dir", conflicts_with = "dev")]
+        storage_dir: Option<PathBuf>,
+        /// Specify the storage backend for the BFT of the validator [options: rocksdb, file]
+        #[clap(default_value_t = StorageBackend::RocksDB, long = "bft-storage")]
+        bft_storage: StorageBackend,
+        /// The path to write the signing history to.
+        #[clap(long)]
+        path: PathBuf,
+    },
+    /// Imports the signing history from a JSON file.
+    Import {
+        /// Specify the network of the signing history.
+        #[clap(default_value = "3", long = "network")]
+        network: u16,
+        /// Enables development mode, specify the unique ID of the local node.
+        #[clap(long)]
+        dev: Option<u16>,
+        /// Specify the directory of the node storage (overrides the default location).
+        #[clap(long = "storage-dir", conflicts_with = "dev")]
+        storage_dir: Option<PathBuf>,
+        /// Specify the storage backend for the BFT of the validator [options: rocksdb, file]
+        #[clap(default_value_t = StorageBackend::RocksDB, long = "bft-storage")]
+        bft_storage: StorageBackend,
+        /// The path to read the signing history from.
+        #[clap(long)]
+        path: PathBuf,
+    },
+}
+
+impl SigningHistory {
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Export { network, dev, storage_dir, bft_storage, path } => match network {
+                3 => Self::export::<Testnet3>(bft_storage, storage_mode(dev, storage_dir), path),
+                _ => bail!("Unsupported network ID {network}"),
+            },
+            Self::Import { network, dev, storage_dir, bft_storage, path } => match network {
+                3 => Self::import::<Testnet3>(bft_storage, storage_mode(dev, storage_dir), path),
+                _ => bail!("Unsupported network ID {network}"),
+            },
+        }
+    }
+
+    /// Opens the BFT storage of the given backend, which must persist the signing history.
+    fn open_storage<N: Network>(
+        backend: StorageBackend,
+        storage_mode: StorageMode,
+    ) -> Result<Box<dyn StorageService<N>>> {
+        match backend {
+            StorageBackend::Memory => bail!("The '{backend}' BFT storage backend does not persist a signing history"),
+            StorageBackend::RocksDB => Ok(Box::new(BFTPersistentStorage::open(storage_mode)?)),
+            // Note: The segments are stored within the ledger directory, as in the validator.
+            StorageBackend::File => {
+                Ok(Box::new(BFTFileStorage::open(aleo_std::aleo_ledger_dir(N::ID, storage_mode).join("bft"))?))
+            }
+        }
+    }
+
+    /// Exports the signing history from storage to the given path.
+    fn export<N: Network>(backend: StorageBackend, storage_mode: StorageMode, path: PathBuf) -> Result<String> {
+        // Open the BFT storage.
+        let storage = Self::open_storage::<N>(backend, storage_mode)?;
+        // Retrieve the signing history.
+        let history = helpers::SigningHistory::export(storage.as_ref());
+        // Write the signing history to the path.
+        std::fs::write(&path, serde_json::to_string_pretty(&history)?)?;
+        Ok(format!(
+            "✅ Exported {} signed batches {}",
+            history.signed_batches().len(),
+            format!("(to \"{}\")", path.display()).dimmed()
+        ))
+    }
+
+    /// Imports the signing history from the given path into storage.
+    fn import<N: Network>(backend: StorageBackend, storage_mode: StorageMode, path: PathBuf) -> Result<String> {
+        // Read the signing history from the path.
+        let history: helpers::SigningHistory<N> = serde_json::from_slice(&std::fs::read(&path)?)?;
+        // Open the BFT storage.
+        let storage = Self::open_storage::<N>(backend, storage_mode)?;
+        // Import the signing history.
+        let num_signed_batches = history.import(storage.as_ref())?;
+        Ok(format!(
+            "✅ Imported {num_signed_batches} signed batches {}",
+            format!("(from \"{}\")", path.display()).dimmed()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_backend_is_rejected() {
+        let path = std::env::temp_dir().join("snarkos-signing-history-memory.json");
+        let command = SigningHistory::Export {
+            network: 3,
+            dev: None,
+            storage_dir: None,
+            bft_storage: StorageBackend::Memory,
+            path: path.clone(),
+        };
+        assert!(command.parse().is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_file_backend_round_trip() {
+        let directory = std::env::temp_dir().join(format!("snarkos-signing-history-{}", std::process::id()));
+        let path = directory.join("history.json");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        // Export the signing history from the file storage in the custom directory.
+        let export = SigningHistory::Export {
+            network: 3,
+            dev: None,
+            storage_dir: Some(directory.clone()),
+            bft_storage: StorageBackend::File,
+            path: path.clone(),
+        };
+        assert!(export.parse().is_ok());
+        // Ensure the segments were created in the ledger directory of the custom storage.
+        assert!(directory.join("bft").exists());
+
+        // Import the signing history back into the file storage.
+        let import = SigningHistory::Import {
+            network: 3,
+            dev: None,
+            storage_dir: Some(directory.clone()),
+            bft_storage: StorageBackend::File,
+            path,
+        };
+        assert!(import.parse().is_ok());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -12,9 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::{storage_mode, Token};
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::{storage_service::StorageBackend, BFTConfig, MEMORY_POOL_PORT},
+    cdn::{CdnConfig, ManifestSource},
+    consensus::DEFAULT_TRANSMISSION_TTL_IN_SECS,
+    rest::{set_jwt_secret, IndexConfig, RateLimitConfig, Scope},
+    router::messages::NodeType,
+    Node,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -31,13 +39,17 @@ use snarkvm::{
     utilities::to_bytes_le,
 };
 
+use aleo_std::StorageMode;
 use anyhow::{bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
 use core::str::FromStr;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
 use tokio::runtime::{self, Runtime};
 
 /// The recommended minimum number of 'open files' limit for a validator.
@@ -93,6 +105,27 @@ pub struct Start {
     /// Specify the requests per second (RPS) rate limit per IP for the REST server
     #[clap(default_value = "10", long = "rest-rps")]
     pub rest_rps: u32,
+    /// Specify the requests per second (RPS) rate limit per JWT for the REST server
+    #[clap(default_value = "100", long = "rest-token-rps")]
+    pub rest_token_rps: u32,
+    /// Specify the IP addresses that are exempt from the REST rate limits (e.g. 127.0.0.1,10.0.0.1)
+    #[clap(long = "rest-allowlist", value_delimiter = ',')]
+    pub rest_allowlist: Vec<IpAddr>,
+    /// If the flag is set, the REST server will index the transactions by program, function and fee payer
+    #[clap(long = "transaction-index")]
+    pub transaction_index: bool,
+    /// If the flag is set, the REST server will index the public `credits.aleo` transitions of each address
+    #[clap(long = "address-index")]
+    pub address_index: bool,
+    /// Specify the scopes of the REST routes that require a JWT, besides 'admin' [options: read, broadcast]
+    #[clap(long = "rest-auth", value_delimiter = ',')]
+    pub rest_auth: Vec<Scope>,
+    /// Specify the secret that signs the JWTs of the REST server
+    #[clap(long = "jwt-secret")]
+    pub jwt_secret: Option<String>,
+    /// Specify the path to a file containing the secret that signs the JWTs of the REST server (created if missing)
+    #[clap(long = "jwt-secret-file")]
+    pub jwt_secret_file: Option<PathBuf>,
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
@@ -110,12 +143,31 @@ pub struct Start {
     #[clap(default_value = "false", long = "metrics")]
     pub metrics: bool,
 
-    /// Enables the node to prefetch initial blocks from a CDN
+    /// Enables the node to prefetch initial blocks from a CDN, given as a comma-separated list of mirrors
+    /// (each a URL, or a `file://` path to a local directory); a client syncs from the CDN and its peers
+    /// concurrently, while a validator syncs from the CDN before it starts
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
     pub cdn: String,
     /// If the flag is set, the node will not prefetch from a CDN
     #[clap(long)]
     pub nocdn: bool,
+    /// Specify the trusted manifest of the CDN, as the path to a local file or as `sha256:<checksum>` of the
+    /// manifest served by the mirrors (by default, the manifest of the mirrors is only checked for corruption)
+    #[clap(long = "cdn-manifest")]
+    pub cdn_manifest: Option<ManifestSource>,
+
+    /// Specify the time-to-live, in seconds, of the unconfirmed transmissions in the memory pool of a validator
+    #[clap(default_value_t = DEFAULT_TRANSMISSION_TTL_IN_SECS, long = "transmission-ttl")]
+    pub transmission_ttl: u64,
+    /// Specify the path to a JSON file with the BFT parameters of a validator (defaults to the network parameters)
+    #[clap(long = "bft-config")]
+    pub bft_config: Option<PathBuf>,
+    /// Specify the storage backend for the BFT of a validator [options: memory, rocksdb, file]
+    #[clap(default_value_t = StorageBackend::RocksDB, long = "bft-storage")]
+    pub bft_storage: StorageBackend,
+    /// Specify the directory of the node storage (overrides the default location)
+    #[clap(long = "storage-dir", conflicts_with = "dev")]
+    pub storage_dir: Option<PathBuf>,
 
     /// Enables development mode, specify a unique ID for this node
     #[clap(long)]
@@ -194,21 +246,53 @@ impl Start {
     }
 
     /// Returns the CDN to prefetch initial blocks from, from the given configurations.
-    fn parse_cdn(&self) -> Option<String> {
+    fn parse_cdn(&self) -> Option<Vec<String>> {
         // Determine if the node type is not declared.
         let is_no_node_type = !(self.validator || self.prover || self.client);
 
+        // Parse the mirrors of the CDN.
+        let mirrors =
+            self.cdn.split(',').map(str::trim).filter(|url| !url.is_empty()).map(String::from).collect::<Vec<_>>();
+        // Determine if the CDN is a local directory.
+        let is_local_cdn = !mirrors.is_empty() && mirrors.iter().all(|url| url.starts_with("file://"));
+
         // Disable CDN if:
-        //  1. The node is in development mode.
+        //  1. The node is in development mode (unless the CDN is a local directory).
         //  2. The user has explicitly disabled CDN.
         //  3. The node is a prover (no need to sync).
         //  4. The node type is not declared (defaults to client) (no need to sync).
-        if self.dev.is_some() || self.cdn.is_empty() || self.nocdn || self.prover || is_no_node_type {
+        if (self.dev.is_some() && !is_local_cdn) || mirrors.is_empty() || self.nocdn || self.prover || is_no_node_type {
             None
         }
         // Enable the CDN otherwise.
         else {
-            Some(self.cdn.clone())
+            Some(mirrors)
+        }
+    }
+
+    /// Returns the storage mode of the node, from the given configurations.
+    fn parse_storage_mode(&self) -> StorageMode {
+        storage_mode(self.dev, self.storage_dir.clone())
+    }
+
+    /// Returns the BFT parameters of the node, from the given configurations.
+    fn parse_bft_config<N: Network>(&self) -> Result<BFTConfig> {
+        // Load the BFT parameters from the file, if one is provided.
+        let bft_config = match &self.bft_config {
+            Some(path) => BFTConfig::load(path)?,
+            None => BFTConfig::default(),
+        };
+        // Ensure the BFT parameters are safe to run with.
+        bft_config.validate::<N>()?;
+        Ok(bft_config)
+    }
+
+    /// Sets the JWT secret of the REST server, if it is provided to the CLI.
+    fn parse_jwt_secret(&self) -> Result<()> {
+        // If no secret is provided, the REST server signs the JWTs with a random secret for this run.
+        match Token::read_secret(&self.jwt_secret, &self.jwt_secret_file)? {
+            Some(secret) => set_jwt_secret(&secret),
+            None => Ok(()),
         }
     }
 
@@ -389,7 +473,9 @@ impl Start {
         self.parse_development(&mut trusted_peers, &mut trusted_validators)?;
 
         // Parse the CDN.
-        let cdn = self.parse_cdn();
+        let cdn = self
+            .parse_cdn()
+            .map(|mirrors| CdnConfig { mirrors, manifest: self.cdn_manifest.clone().unwrap_or_default() });
 
         // Parse the genesis block.
         let genesis = self.parse_genesis::<N>()?;
@@ -403,6 +489,8 @@ impl Start {
             true => None,
             false => Some(self.rest),
         };
+        // Parse the JWT secret.
+        self.parse_jwt_secret()?;
 
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
@@ -444,10 +532,20 @@ impl Start {
 
         // Initialize the node.
         let bft_ip = if self.dev.is_some() { self.bft } else { None };
+        let storage_mode = self.parse_storage_mode();
+        let bft_config = self.parse_bft_config::<N>()?;
+        // Initialize the REST rate limits.
+        let rate_limits = RateLimitConfig {
+            ip_rps: self.rest_rps,
+            token_rps: self.rest_token_rps,
+            allowlist: self.rest_allowlist.clone(),
+        };
+        // Initialize the REST indexes.
+        let indexes = IndexConfig { transactions: self.transaction_index, addresses: self.address_index };
         match node_type {
-            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev).await,
-            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, self.dev).await,
+            NodeType::Validator => Node::new_validator(self.node, bft_ip, rest_ip, rate_limits, indexes, &self.rest_auth, account, &trusted_peers, &trusted_validators, genesis, cdn, self.transmission_ttl, bft_config, self.bft_storage, storage_mode).await,
+            NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, storage_mode).await,
+            NodeType::Client => Node::new_client(self.node, rest_ip, rate_limits, indexes, &self.rest_auth, account, &trusted_peers, genesis, cdn, storage_mode).await,
         }
     }
 
@@ -619,6 +717,78 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_rest_auth() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.rest_auth.is_empty());
+
+        let config = Start::try_parse_from(["snarkos", "--rest-auth", "read,broadcast"].iter()).unwrap();
+        assert_eq!(config.rest_auth, vec![Scope::Read, Scope::Broadcast]);
+
+        assert!(Start::try_parse_from(["snarkos", "--rest-auth", "write"].iter()).is_err());
+    }
+
+    #[test]
+    fn test_parse_rest_rate_limits() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.rest_token_rps, 100);
+        assert!(config.rest_allowlist.is_empty());
+
+        let config =
+            Start::try_parse_from(["snarkos", "--rest-token-rps", "50", "--rest-allowlist", "127.0.0.1,::1"].iter())
+                .unwrap();
+        assert_eq!(config.rest_token_rps, 50);
+        assert_eq!(config.rest_allowlist, vec![IpAddr::from([127, 0, 0, 1]), IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])]);
+    }
+
+    #[test]
+    fn test_parse_storage_dir() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.parse_storage_mode(), StorageMode::Production);
+
+        let config = Start::try_parse_from(["snarkos", "--storage-dir", "/tmp/storage"].iter()).unwrap();
+        assert_eq!(config.parse_storage_mode(), StorageMode::Custom(PathBuf::from("/tmp/storage")));
+
+        // Ensure a custom storage directory cannot be combined with development mode.
+        assert!(Start::try_parse_from(["snarkos", "--dev", "0", "--storage-dir", "/tmp/storage"].iter()).is_err());
+    }
+
+    #[test]
+    fn test_parse_bft_config() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.parse_bft_config::<CurrentNetwork>().unwrap(), BFTConfig::default());
+
+        let path = std::env::temp_dir().join(format!("snarkos-start-bft-config-{}.json", std::process::id()));
+        let config = Start::try_parse_from(["snarkos", "--bft-config", path.to_str().unwrap()].iter()).unwrap();
+        // Ensure a missing file is rejected.
+        assert!(config.parse_bft_config::<CurrentNetwork>().is_err());
+        // Ensure the parameters are loaded from the file.
+        std::fs::write(&path, r#"{ "max_batch_delay_in_ms": 1000, "max_gc_rounds": 20 }"#).unwrap();
+        let bft_config = config.parse_bft_config::<CurrentNetwork>().unwrap();
+        assert_eq!(bft_config.max_batch_delay_in_ms, 1000);
+        assert_eq!(bft_config.max_gc_rounds, 20);
+        // Ensure unsafe parameters are rejected.
+        std::fs::write(&path, r#"{ "max_gc_rounds": 1 }"#).unwrap();
+        assert!(config.parse_bft_config::<CurrentNetwork>().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_cdn_manifest() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.cdn_manifest, None);
+
+        let config = Start::try_parse_from(["snarkos", "--cdn-manifest", "/cdn/manifest.json"].iter()).unwrap();
+        assert_eq!(config.cdn_manifest, Some(ManifestSource::File(PathBuf::from("/cdn/manifest.json"))));
+
+        let checksum = "ab".repeat(32);
+        let config =
+            Start::try_parse_from(["snarkos", "--cdn-manifest", &format!("sha256:{checksum}")].iter()).unwrap();
+        assert_eq!(config.cdn_manifest, Some(ManifestSource::Checksum(checksum)));
+
+        assert!(Start::try_parse_from(["snarkos", "--cdn-manifest", "sha256:ab"].iter()).is_err());
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -631,6 +801,11 @@ mod tests {
         let config =
             Start::try_parse_from(["snarkos", "--validator", "--private-key", "aleo1xx", "--cdn", ""].iter()).unwrap();
         assert!(config.parse_cdn().is_none());
+        let config = Start::try_parse_from(
+            ["snarkos", "--validator", "--private-key", "aleo1xx", "--cdn", "url1, url2,"].iter(),
+        )
+        .unwrap();
+        assert_eq!(config.parse_cdn(), Some(vec!["url1".to_string(), "url2".to_string()]));
 
         // Validator (Dev)
         let config =
@@ -646,6 +821,11 @@ mod tests {
         )
         .unwrap();
         assert!(config.parse_cdn().is_none());
+        let config = Start::try_parse_from(
+            ["snarkos", "--dev", "0", "--validator", "--private-key", "aleo1xx", "--cdn", "file:///cdn"].iter(),
+        )
+        .unwrap();
+        assert_eq!(config.parse_cdn(), Some(vec!["file:///cdn".to_string()]));
 
         // Prover (Prod)
         let config = Start::try_parse_from(["snarkos", "--prover", "--private-key", "aleo1xx"].iter()).unwrap();
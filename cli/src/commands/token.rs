@@ -0,0 +1,86 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node::rest::{load_or_create_jwt_secret, set_jwt_secret, Claims, Scope};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Issues a JSON web token for the REST server of a node.
+#[derive(Debug, Parser)]
+pub struct Token {
+    /// Specify the secret that signs the JWTs of the REST server
+    #[clap(long = "jwt-secret")]
+    pub jwt_secret: Option<String>,
+    /// Specify the path to a file containing the secret that signs the JWTs of the REST server (created if missing)
+    #[clap(long = "jwt-secret-file")]
+    pub jwt_secret_file: Option<PathBuf>,
+    /// Specify the scopes of the token, as a comma-separated list [options: read, broadcast, admin]
+    #[clap(long = "scopes", value_delimiter = ',', required = true)]
+    pub scopes: Vec<Scope>,
+    /// Specify the subject of the token, such as the name of its holder
+    #[clap(default_value = "operator", long = "subject")]
+    pub subject: String,
+    /// Specify the number of days the token is valid for
+    #[clap(default_value = "30", long = "days")]
+    pub days: u32,
+}
+
+impl Token {
+    /// Issues the token, signed with the secret of the REST server.
+    pub fn parse(self) -> Result<String> {
+        // Set the secret that the REST server uses to verify the token.
+        match Self::read_secret(&self.jwt_secret, &self.jwt_secret_file)? {
+            Some(secret) => set_jwt_secret(&secret)?,
+            None => bail!("Missing the '--jwt-secret' or '--jwt-secret-file' argument"),
+        }
+
+        // Issue the token.
+        let duration = i64::from(self.days) * 24 * 60 * 60;
+        Claims::new_with_scopes(self.subject, self.scopes, duration).to_jwt_string()
+    }
+
+    /// Returns the JWT secret from the given secret or secret file, if either is provided.
+    pub(crate) fn read_secret(secret: &Option<String>, secret_file: &Option<PathBuf>) -> Result<Option<Vec<u8>>> {
+        match (secret, secret_file) {
+            // Parse the secret directly.
+            (Some(secret), None) => Ok(Some(secret.trim().as_bytes().to_vec())),
+            // Parse the secret from a file, which is created with a random secret if it does not exist.
+            (None, Some(path)) => Ok(Some(load_or_create_jwt_secret(path)?)),
+            (None, None) => Ok(None),
+            // Ensure only one secret flag is provided to the CLI.
+            (Some(_), Some(_)) => {
+                bail!("Cannot use '--jwt-secret' and '--jwt-secret-file' simultaneously, please use only one")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token() {
+        // Ensure a secret and the scopes are required.
+        assert!(Token::try_parse_from(["snarkos", "--jwt-secret", "0123456789abcdef"].iter()).is_err());
+        let token = Token::try_parse_from(["snarkos", "--scopes", "read"].iter()).unwrap();
+        assert!(token.parse().is_err());
+
+        let token = Token::try_parse_from(["snarkos", "--scopes", "read,broadcast", "--days", "7"].iter()).unwrap();
+        assert_eq!(token.scopes, vec![Scope::Read, Scope::Broadcast]);
+        assert_eq!(token.days, 7);
+    }
+}
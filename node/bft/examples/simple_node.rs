@@ -18,6 +18,7 @@ extern crate tracing;
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{init_consensus_channels, init_primary_channels, ConsensusReceiver, PrimarySender, Storage},
+    BFTConfig,
     Primary,
     BFT,
     MAX_GC_ROUNDS,
@@ -129,7 +130,8 @@ pub async fn start_bft(
     // Initialize the consensus receiver handler.
     consensus_handler(consensus_receiver);
     // Initialize the BFT instance.
-    let mut bft = BFT::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut bft =
+        BFT::<CurrentNetwork>::new(BFTConfig::default(), account, storage, ledger, ip, &trusted_validators, dev)?;
     // Run the BFT instance.
     bft.run(Some(consensus_sender), sender.clone(), receiver).await?;
     // Retrieve the BFT's primary.
@@ -162,7 +164,8 @@ pub async fn start_primary(
     // Initialize the trusted validators.
     let trusted_validators = trusted_validators(node_id, num_nodes, peers);
     // Initialize the primary instance.
-    let mut primary = Primary::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut primary =
+        Primary::<CurrentNetwork>::new(BFTConfig::default(), account, storage, ledger, ip, &trusted_validators, dev)?;
     // Run the primary instance.
     primary.run(None, sender.clone(), receiver).await?;
     // Handle OS signals.
@@ -200,7 +203,7 @@ fn initialize_components(node_id: u16, num_nodes: u16) -> Result<(Committee<Curr
 
 /// Handles the consensus receiver.
 fn consensus_handler(receiver: ConsensusReceiver<CurrentNetwork>) {
-    let ConsensusReceiver { mut rx_consensus_subdag } = receiver;
+    let ConsensusReceiver { mut rx_consensus_subdag, .. } = receiver;
 
     tokio::task::spawn(async move {
         while let Some((subdag, transmissions, callback)) = rx_consensus_subdag.recv().await {
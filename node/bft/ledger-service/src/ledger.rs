@@ -39,6 +39,9 @@ use std::{
 pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
     coinbase_verifying_key: Arc<CoinbaseVerifyingKey<N>>,
+    /// The callbacks that are invoked with each block that is added to the ledger.
+    #[cfg(feature = "ledger-write")]
+    callbacks: parking_lot::RwLock<Vec<crate::AdvanceCallback<N>>>,
     shutdown: Arc<AtomicBool>,
 }
 
@@ -46,7 +49,13 @@ impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
     /// Initializes a new core ledger service.
     pub fn new(ledger: Ledger<N, C>, shutdown: Arc<AtomicBool>) -> Self {
         let coinbase_verifying_key = Arc::new(ledger.coinbase_puzzle().coinbase_verifying_key().clone());
-        Self { ledger, coinbase_verifying_key, shutdown }
+        Self {
+            ledger,
+            coinbase_verifying_key,
+            #[cfg(feature = "ledger-write")]
+            callbacks: Default::default(),
+            shutdown,
+        }
     }
 }
 
@@ -298,6 +307,14 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         // Advance to the next block.
         self.ledger.advance_to_next_block(block)?;
         tracing::info!("\n\nAdvanced to block {} at round {} - {}\n", block.height(), block.round(), block.hash());
+        // Notify the callbacks of the new block.
+        self.callbacks.read().iter().for_each(|callback| callback(block));
         Ok(())
     }
+
+    /// Registers the given callback, which is invoked with each block that is added to the ledger.
+    #[cfg(feature = "ledger-write")]
+    fn on_advance(&self, callback: crate::AdvanceCallback<N>) {
+        self.callbacks.write().push(callback);
+    }
 }
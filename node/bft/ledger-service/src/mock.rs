@@ -49,6 +49,15 @@ impl<N: Network> MockLedgerService<N> {
         }
         Self { committee, height_to_hash: Mutex::new(height_to_hash) }
     }
+
+    /// Advances the mock ledger to the given height, as if the blocks were added by another source.
+    pub fn advance_to_height(&self, height: u32) {
+        let mut height_to_hash = self.height_to_hash.lock();
+        let start = height_to_hash.last_key_value().map(|(height, _)| height + 1).unwrap_or(0);
+        for i in start..=height {
+            height_to_hash.insert(i, (Field::<N>::from_u32(i)).into());
+        }
+    }
 }
 
 #[async_trait]
@@ -200,4 +209,8 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
         self.height_to_hash.lock().insert(block.height(), block.hash());
         Ok(())
     }
+
+    /// Ignores the callback, as the mock ledger does not store the blocks.
+    #[cfg(feature = "ledger-write")]
+    fn on_advance(&self, _callback: crate::AdvanceCallback<N>) {}
 }
@@ -171,4 +171,8 @@ impl<N: Network> LedgerService<N> for ProverLedgerService<N> {
     fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
         bail!("Cannot advance to next block in prover - {block}")
     }
+
+    /// Ignores the callback, as the prover does not advance the ledger.
+    #[cfg(feature = "ledger-write")]
+    fn on_advance(&self, _callback: crate::AdvanceCallback<N>) {}
 }
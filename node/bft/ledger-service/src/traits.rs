@@ -25,6 +25,9 @@ use snarkvm::{
 use indexmap::IndexMap;
 use std::{fmt::Debug, ops::Range};
 
+/// A callback that is invoked with each block that is added to the ledger.
+pub type AdvanceCallback<N> = Box<dyn Fn(&Block<N>) + Send + Sync>;
+
 #[async_trait]
 pub trait LedgerService<N: Network>: Debug + Send + Sync {
     /// Returns the latest round in the ledger.
@@ -113,4 +116,9 @@ pub trait LedgerService<N: Network>: Debug + Send + Sync {
     /// Adds the given block as the next block in the ledger.
     #[cfg(feature = "ledger-write")]
     fn advance_to_next_block(&self, block: &Block<N>) -> Result<()>;
+
+    /// Registers the given callback, which is invoked with each block that is added to the ledger,
+    /// whether it was committed by the BFT or received through sync.
+    #[cfg(feature = "ledger-write")]
+    fn on_advance(&self, callback: AdvanceCallback<N>);
 }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{CoreLedgerService, LedgerService};
+use crate::{AdvanceCallback, CoreLedgerService, LedgerService};
 use async_trait::async_trait;
 use indexmap::IndexMap;
 use snarkvm::{
@@ -178,4 +178,9 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for TranslucentLedgerS
     fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
         self.inner.advance_to_next_block(block)
     }
+
+    /// Registers the given callback, which is invoked with each block that is added to the ledger.
+    fn on_advance(&self, callback: AdvanceCallback<N>) {
+        self.inner.on_advance(callback)
+    }
 }
@@ -21,11 +21,12 @@ use crate::{
         ConsensusSender,
         PrimaryReceiver,
         PrimarySender,
+        QueueState,
         Storage,
         DAG,
     },
+    BFTConfig,
     Primary,
-    MAX_LEADER_CERTIFICATE_DELAY_IN_SECS,
 };
 use snarkos_account::Account;
 use snarkos_node_bft_ledger_service::LedgerService;
@@ -80,6 +81,7 @@ pub struct BFT<N: Network> {
 impl<N: Network> BFT<N> {
     /// Initializes a new instance of the BFT.
     pub fn new(
+        config: BFTConfig,
         account: Account<N>,
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
@@ -87,8 +89,10 @@ impl<N: Network> BFT<N> {
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
     ) -> Result<Self> {
+        // Ensure the BFT parameters are valid.
+        config.validate::<N>()?;
         Ok(Self {
-            primary: Primary::new(account, storage, ledger, ip, trusted_validators, dev)?,
+            primary: Primary::new(config, account, storage, ledger, ip, trusted_validators, dev)?,
             dag: Default::default(),
             leader_certificate: Default::default(),
             leader_certificate_timer: Default::default(),
@@ -121,6 +125,11 @@ impl<N: Network> BFT<N> {
         Ok(())
     }
 
+    /// Returns the BFT parameters.
+    pub const fn config(&self) -> &BFTConfig {
+        self.primary.config()
+    }
+
     /// Returns the primary.
     pub const fn primary(&self) -> &Primary<N> {
         &self.primary
@@ -172,6 +181,11 @@ impl<N: Network> BFT<N> {
     pub fn num_unconfirmed_transactions(&self) -> usize {
         self.primary.num_unconfirmed_transactions()
     }
+
+    /// Returns the combined state of the ready queues of the workers.
+    pub fn ready_queue_state(&self) -> QueueState {
+        self.primary.ready_queue_state()
+    }
 }
 
 impl<N: Network> BFT<N> {
@@ -344,7 +358,8 @@ impl<N: Network> BFT<N> {
 
     /// Returns `true` if the timer for the leader certificate has expired.
     fn is_timer_expired(&self) -> bool {
-        self.leader_certificate_timer.load(Ordering::SeqCst) + MAX_LEADER_CERTIFICATE_DELAY_IN_SECS <= now()
+        self.leader_certificate_timer.load(Ordering::SeqCst) + self.config().max_leader_certificate_delay_in_secs()
+            <= now()
     }
 
     /// Returns 'true' if any of the following conditions hold:
@@ -704,6 +719,10 @@ impl<N: Network> BFT<N> {
             while let Some((certificate, callback)) = rx_primary_certificate.recv().await {
                 // Update the DAG with the certificate.
                 let result = self_.update_dag::<true>(certificate).await;
+                // If the certificate was stored, request consensus to refill the workers.
+                if result.is_ok() {
+                    self_.request_refill();
+                }
                 // Send the callback **after** updating the DAG.
                 // Note: We must await the DAG update before proceeding.
                 callback.send(result).ok();
@@ -715,6 +734,9 @@ impl<N: Network> BFT<N> {
         self.spawn(async move {
             while let Some((leader_certificates, certificates)) = rx_sync_bft_dag_at_bootup.recv().await {
                 self_.sync_bft_dag_at_bootup(leader_certificates, certificates).await;
+                // Replay the certificates that were restored from storage into the DAG.
+                // Note: This must happen **after** the DAG is synced with the ledger, to skip the committed certificates.
+                self_.restore_dag_from_storage().await;
             }
         });
 
@@ -741,6 +763,10 @@ impl<N: Network> BFT<N> {
         leader_certificates: Vec<(BatchCertificate<N>, IndexSet<Field<N>>)>,
         certificates: Vec<BatchCertificate<N>>,
     ) {
+        // If there are no leader certificates, return early.
+        if leader_certificates.is_empty() {
+            return;
+        }
         // Split the leader certificates into past leader certificates, the latest leader certificate, and the election certificate IDs.
         let (past_leader_certificates, leader_certificate, election_certificate_ids) = {
             // Compute the penultimate index.
@@ -790,6 +816,58 @@ impl<N: Network> BFT<N> {
         }
     }
 
+    /// Inserts the certificates in storage that are not yet committed into the DAG.
+    ///
+    /// On restart, storage restores the certificates of the previous run, which must be
+    /// in the DAG for the leaders that reference them to be ordered and committed.
+    async fn restore_dag_from_storage(&self) {
+        // Acquire the BFT lock.
+        let _lock = self.lock.lock().await;
+        // Retrieve the certificates in storage.
+        let certificates = self.storage().get_certificates_above_gc_round();
+
+        // Acquire the DAG write lock.
+        let mut dag = self.dag.write();
+        // Retrieve the last committed round.
+        let last_committed_round = dag.last_committed_round();
+        // Initialize a counter for the restored certificates.
+        let mut num_restored = 0;
+        // Iterate over the certificates, in order of their rounds.
+        for certificate in certificates {
+            // Retrieve the certificate round.
+            let round = certificate.round();
+            // Skip the certificate if it is below the GC round of the DAG.
+            if round + self.storage().max_gc_rounds() <= last_committed_round {
+                continue;
+            }
+            // Skip the certificate if it is already in the DAG, or was already committed.
+            if dag.contains_certificate_in_round(round, certificate.id())
+                || dag.is_recently_committed(round, certificate.id())
+            {
+                continue;
+            }
+            // Skip the certificate if it was already committed to the ledger.
+            // Note: On failure to read from the ledger, we skip the certificate, out of safety.
+            if self.ledger().contains_certificate(&certificate.id()).unwrap_or(true) {
+                continue;
+            }
+            // Insert the certificate into the DAG.
+            dag.insert(certificate);
+            num_restored += 1;
+        }
+        if num_restored > 0 {
+            info!("Restored {num_restored} certificates from storage into the DAG");
+        }
+    }
+
+    /// Requests consensus to refill the workers from its unconfirmed queues, as their capacity may have freed up.
+    fn request_refill(&self) {
+        if let Some(consensus_sender) = self.consensus_sender.get() {
+            // Note: If a refill request is already pending, this request is covered by it.
+            let _ = consensus_sender.tx_consensus_refill.try_send(());
+        }
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
         self.handles.lock().push(tokio::spawn(future));
@@ -811,6 +889,7 @@ impl<N: Network> BFT<N> {
 mod tests {
     use crate::{
         helpers::{now, Storage},
+        BFTConfig,
         BFT,
     };
     use snarkos_account::Account;
@@ -863,7 +942,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Ensure this call succeeds on an odd round.
@@ -901,7 +980,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Store is at round 1, and we are checking for round 2.
@@ -923,7 +1002,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Ensure this call fails on an even round.
@@ -944,7 +1023,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
 
         let result = bft.is_even_round_ready_for_next_round(IndexSet::new(), committee.clone(), 2);
         assert!(!result);
@@ -969,7 +1048,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
 
         // Ensure this call fails on an odd round.
         let result = bft.update_leader_certificate_to_even_round(1);
@@ -987,7 +1066,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
 
         // Ensure this call succeeds on an even round.
         let result = bft.update_leader_certificate_to_even_round(6);
@@ -1039,7 +1118,7 @@ mod tests {
 
         // Initialize the BFT.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage.clone(), ledger, None, &[], None)?;
 
         // Set the leader certificate.
         *bft.leader_certificate.write() = Some(leader_certificate);
@@ -1077,7 +1156,7 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account.clone(), storage, ledger.clone(), None, &[], None)?;
+            let bft = BFT::new(BFTConfig::default(), account.clone(), storage, ledger.clone(), None, &[], None)?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(3);
@@ -1107,7 +1186,7 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+            let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(2);
@@ -1165,7 +1244,7 @@ mod tests {
         /* Test missing previous certificate. */
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
 
         // The expected error message.
         let error_msg = format!(
@@ -1179,4 +1258,41 @@ mod tests {
         assert_eq!(result.unwrap_err().to_string(), error_msg);
         Ok(())
     }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_restore_dag_from_storage() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the test instance.
+        let (_, account, ledger, storage) = sample_test_instance(Some(1), 10, rng);
+
+        // Sample the current certificate and previous certificates.
+        let (certificate, previous_certificates) = snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_with_previous_certificates(
+            3,
+            rng,
+        );
+        // Insert the certificates into storage (i.e. as restored from a previous run).
+        for certificate in previous_certificates.iter().chain([&certificate]) {
+            storage.testing_only_insert_certificate_testing_only(certificate.clone());
+        }
+
+        // Initialize the BFT.
+        let bft = BFT::new(BFTConfig::default(), account, storage, ledger, None, &[], None)?;
+        // Commit one of the previous certificates (i.e. as synced from the ledger).
+        bft.dag.write().commit(&previous_certificates[0], 10);
+
+        // Restore the DAG from storage.
+        bft.restore_dag_from_storage().await;
+
+        // Ensure the uncommitted certificates were inserted into the DAG.
+        let dag = bft.dag.read();
+        assert!(dag.contains_certificate_in_round(3, certificate.id()));
+        for previous_certificate in &previous_certificates[1..] {
+            assert!(dag.contains_certificate_in_round(2, previous_certificate.id()));
+        }
+        // Ensure the committed certificate was not inserted into the DAG.
+        assert!(!dag.contains_certificate_in_round(2, previous_certificates[0].id()));
+        Ok(())
+    }
 }
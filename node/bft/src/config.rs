@@ -0,0 +1,187 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    MAX_BATCH_DELAY_IN_MS,
+    MAX_GC_ROUNDS,
+    MAX_TRANSMISSIONS_PER_BATCH,
+    MAX_WORKERS,
+    PRIMARY_PING_IN_MS,
+    WORKER_PING_IN_MS,
+};
+use snarkvm::{console::prelude::*, ledger::narwhal::BatchHeader};
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The timing and sizing parameters of the BFT.
+/// Note: Every validator in the committee is expected to use the same parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BFTConfig {
+    /// The maximum number of milliseconds to wait before proposing a batch.
+    pub max_batch_delay_in_ms: u64,
+    /// The maximum number of rounds to store before garbage collecting.
+    pub max_gc_rounds: u64,
+    /// The maximum number of transmissions allowed in a batch.
+    pub max_transmissions_per_batch: usize,
+    /// The maximum number of workers that can be spawned.
+    pub max_workers: u8,
+    /// The frequency (in milliseconds) at which each primary broadcasts a ping to every other node.
+    pub primary_ping_in_ms: u64,
+    /// The frequency (in milliseconds) at which each worker broadcasts a ping to every other node.
+    pub worker_ping_in_ms: u64,
+}
+
+impl Default for BFTConfig {
+    /// Initializes the default BFT parameters.
+    fn default() -> Self {
+        Self {
+            max_batch_delay_in_ms: MAX_BATCH_DELAY_IN_MS,
+            max_gc_rounds: MAX_GC_ROUNDS,
+            max_transmissions_per_batch: MAX_TRANSMISSIONS_PER_BATCH,
+            max_workers: MAX_WORKERS,
+            primary_ping_in_ms: PRIMARY_PING_IN_MS,
+            worker_ping_in_ms: WORKER_PING_IN_MS,
+        }
+    }
+}
+
+impl BFTConfig {
+    /// Loads the BFT parameters from the given JSON file, where any omitted parameter takes its default value.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        // Read the config file.
+        let contents = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read the BFT config file '{}' - {e}", path.display()))?;
+        // Parse the config file.
+        serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Failed to parse the BFT config file '{}' - {e}", path.display()))
+    }
+
+    /// Ensures the BFT parameters are safe to run with.
+    pub fn validate<N: Network>(&self) -> Result<()> {
+        // Ensure the batch delay spans at least one second, as the batch timestamps are in seconds.
+        ensure!(self.max_batch_delay_in_ms >= 1000, "The maximum batch delay must be at least 1000 ms");
+        // Ensure the DAG retains the rounds required to elect and commit a leader.
+        ensure!(self.max_gc_rounds >= 2, "The maximum GC rounds must be at least 2");
+        // Ensure the batches are within the limit of the batch header.
+        ensure!(
+            (1..=BatchHeader::<N>::MAX_TRANSMISSIONS).contains(&self.max_transmissions_per_batch),
+            "The maximum transmissions per batch must be between 1 and {}",
+            BatchHeader::<N>::MAX_TRANSMISSIONS
+        );
+        // Ensure each worker is able to contribute at least one transmission to a batch.
+        ensure!(
+            (1..=self.max_transmissions_per_batch).contains(&(self.max_workers as usize)),
+            "The maximum workers must be between 1 and the maximum transmissions per batch"
+        );
+        // Ensure the pings do not flood the network, by sending more than once per batch.
+        ensure!(
+            self.primary_ping_in_ms >= self.max_batch_delay_in_ms,
+            "The primary ping interval must be at least the maximum batch delay"
+        );
+        ensure!(
+            self.worker_ping_in_ms >= self.max_batch_delay_in_ms,
+            "The worker ping interval must be at least the maximum batch delay"
+        );
+        // Ensure the messages of a peer fit in the message queues of the gateway, which are sized for the defaults.
+        ensure!(self.max_gc_rounds <= MAX_GC_ROUNDS, "The maximum GC rounds must be at most {MAX_GC_ROUNDS}");
+        ensure!(
+            self.max_transmissions_per_batch <= MAX_TRANSMISSIONS_PER_BATCH,
+            "The maximum transmissions per batch must be at most {MAX_TRANSMISSIONS_PER_BATCH}"
+        );
+        Ok(())
+    }
+
+    /// Returns the maximum number of seconds allowed for the leader to send their certificate.
+    pub const fn max_leader_certificate_delay_in_secs(&self) -> i64 {
+        2 * self.max_batch_delay_in_ms as i64 / 1000
+    }
+
+    /// Returns the maximum number of transmissions allowed in a worker ping.
+    pub fn max_transmissions_per_worker_ping(&self) -> usize {
+        (self.max_transmissions_per_batch / 10).max(1)
+    }
+
+    /// Returns the maximum number of transmissions allowed in the ready queue of each worker.
+    pub fn max_transmissions_per_worker(&self) -> usize {
+        self.max_transmissions_per_batch / self.max_workers.max(1) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_validate() {
+        // Ensure the defaults are valid.
+        assert!(BFTConfig::default().validate::<CurrentNetwork>().is_ok());
+
+        // Ensure the unsafe parameters are rejected.
+        let default = BFTConfig::default();
+        let invalid = [
+            BFTConfig { max_batch_delay_in_ms: 500, ..default.clone() },
+            BFTConfig { max_gc_rounds: 1, ..default.clone() },
+            BFTConfig { max_transmissions_per_batch: 0, ..default.clone() },
+            BFTConfig { max_transmissions_per_batch: 1000, ..default.clone() },
+            BFTConfig { max_workers: 0, ..default.clone() },
+            BFTConfig { max_transmissions_per_batch: 2, max_workers: 3, ..default.clone() },
+            BFTConfig { primary_ping_in_ms: 1000, ..default.clone() },
+            BFTConfig { worker_ping_in_ms: 1000, ..default.clone() },
+            BFTConfig { max_gc_rounds: MAX_GC_ROUNDS + 1, max_transmissions_per_batch: 100, ..default.clone() },
+            BFTConfig { max_transmissions_per_batch: MAX_TRANSMISSIONS_PER_BATCH + 1, ..default.clone() },
+        ];
+        for config in invalid {
+            assert!(config.validate::<CurrentNetwork>().is_err(), "{config:?}");
+        }
+
+        // Ensure a faster network with fewer rounds is accepted.
+        let config = BFTConfig {
+            max_batch_delay_in_ms: 1000,
+            max_gc_rounds: 20,
+            max_transmissions_per_batch: 100,
+            max_workers: 2,
+            primary_ping_in_ms: 1000,
+            worker_ping_in_ms: 2000,
+        };
+        assert!(config.validate::<CurrentNetwork>().is_ok());
+        assert_eq!(config.max_leader_certificate_delay_in_secs(), 2);
+        assert_eq!(config.max_transmissions_per_worker(), 50);
+
+        // Ensure the defaults match the documented default constants.
+        assert_eq!(default.max_leader_certificate_delay_in_secs(), crate::MAX_LEADER_CERTIFICATE_DELAY_IN_SECS);
+        assert_eq!(default.max_transmissions_per_worker_ping(), crate::MAX_TRANSMISSIONS_PER_WORKER_PING);
+    }
+
+    #[test]
+    fn test_load() {
+        let path = std::env::temp_dir().join(format!("snarkos-bft-config-{}.json", std::process::id()));
+
+        // Ensure the omitted parameters take their default values.
+        fs::write(&path, r#"{ "max_batch_delay_in_ms": 1000, "max_gc_rounds": 20 }"#).unwrap();
+        let config = BFTConfig::load(&path).unwrap();
+        assert_eq!(config, BFTConfig { max_batch_delay_in_ms: 1000, max_gc_rounds: 20, ..Default::default() });
+
+        // Ensure the unknown parameters are rejected.
+        fs::write(&path, r#"{ "max_batch_delay": 1000 }"#).unwrap();
+        assert!(BFTConfig::load(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+        assert!(BFTConfig::load(&path).is_err());
+    }
+}
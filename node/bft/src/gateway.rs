@@ -16,11 +16,10 @@ use crate::{
     events::{EventCodec, PrimaryPing},
     helpers::{assign_to_worker, Cache, PrimarySender, Resolver, SyncSender, WorkerSender},
     spawn_blocking,
+    BFTConfig,
     CONTEXT,
-    MAX_BATCH_DELAY_IN_MS,
     MAX_GC_ROUNDS,
     MAX_TRANSMISSIONS_PER_BATCH,
-    MAX_TRANSMISSIONS_PER_WORKER_PING,
     MEMORY_POOL_PORT,
 };
 use snarkos_account::Account;
@@ -63,7 +62,14 @@ use futures::SinkExt;
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
 use rand::seq::{IteratorRandom, SliceRandom};
-use std::{collections::HashSet, future::Future, io, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    future::Future,
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::{
     net::TcpStream,
     sync::{oneshot, OnceCell},
@@ -72,15 +78,8 @@ use tokio::{
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
 
-/// The maximum interval of events to cache.
-const CACHE_EVENTS_INTERVAL: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
-/// The maximum interval of requests to cache.
-const CACHE_REQUESTS_INTERVAL: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
-
 /// The maximum number of connection attempts in an interval.
 const MAX_CONNECTION_ATTEMPTS: usize = 10;
-/// The maximum interval to restrict a peer.
-const RESTRICTED_INTERVAL: i64 = (MAX_CONNECTION_ATTEMPTS as u64 * MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
 
 /// The minimum number of validators to maintain a connection to.
 const MIN_CONNECTED_VALIDATORS: usize = 175;
@@ -97,6 +96,8 @@ pub trait Transport<N: Network>: Send + Sync {
 
 #[derive(Clone)]
 pub struct Gateway<N: Network> {
+    /// The BFT parameters.
+    config: BFTConfig,
     /// The account of the node.
     account: Account<N>,
     /// The ledger service.
@@ -108,7 +109,9 @@ pub struct Gateway<N: Network> {
     /// The resolver.
     resolver: Arc<Resolver<N>>,
     /// The set of trusted validators.
-    trusted_validators: IndexSet<SocketAddr>,
+    trusted_validators: Arc<RwLock<IndexSet<SocketAddr>>>,
+    /// The set of banned IPs.
+    banned_ips: Arc<RwLock<IndexSet<IpAddr>>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: Arc<RwLock<IndexSet<SocketAddr>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -129,6 +132,7 @@ pub struct Gateway<N: Network> {
 impl<N: Network> Gateway<N> {
     /// Initializes a new gateway.
     pub fn new(
+        config: BFTConfig,
         account: Account<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
@@ -145,12 +149,14 @@ impl<N: Network> Gateway<N> {
         let tcp = Tcp::new(Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE));
         // Return the gateway.
         Ok(Self {
+            config,
             account,
             ledger,
             tcp,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_validators: trusted_validators.iter().copied().collect(),
+            trusted_validators: Arc::new(RwLock::new(trusted_validators.iter().copied().collect())),
+            banned_ips: Default::default(),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             primary_sender: Default::default(),
@@ -212,12 +218,27 @@ impl<N: Network> Gateway<N> {
 
     /// The maximum number of certificate requests to cache.
     fn max_cache_certificates(&self) -> usize {
-        2 * MAX_GC_ROUNDS as usize * self.max_committee_size()
+        2 * self.config.max_gc_rounds as usize * self.max_committee_size()
     }
 
     /// The maximum number of transmission requests to cache.
     fn max_cache_transmissions(&self) -> usize {
-        self.max_cache_certificates() * MAX_TRANSMISSIONS_PER_BATCH
+        self.max_cache_certificates() * self.config.max_transmissions_per_batch
+    }
+
+    /// The maximum interval of events to cache, in seconds.
+    fn cache_events_interval(&self) -> i64 {
+        (self.config.max_batch_delay_in_ms / 1000) as i64
+    }
+
+    /// The maximum interval of requests to cache, in seconds.
+    fn cache_requests_interval(&self) -> i64 {
+        (self.config.max_batch_delay_in_ms / 1000) as i64
+    }
+
+    /// The maximum interval to restrict a peer, in seconds.
+    fn restricted_interval(&self) -> i64 {
+        (MAX_CONNECTION_ATTEMPTS as u64 * self.config.max_batch_delay_in_ms / 1000) as i64
     }
 
     /// The maximum number of duplicates for any particular request.
@@ -248,6 +269,11 @@ impl<N: Network> CommunicationService for Gateway<N> {
 }
 
 impl<N: Network> Gateway<N> {
+    /// Returns the BFT parameters.
+    pub const fn config(&self) -> &BFTConfig {
+        &self.config
+    }
+
     /// Returns the account of the node.
     pub const fn account(&self) -> &Account<N> {
         &self.account
@@ -310,10 +336,25 @@ impl<N: Network> Gateway<N> {
         self.connecting_peers.lock().contains(&ip)
     }
 
+    /// Returns `true` if the given IP is banned.
+    pub fn is_banned(&self, ip: IpAddr) -> bool {
+        self.banned_ips.read().contains(&ip)
+    }
+
+    /// Returns the list of banned IPs.
+    pub fn banned_ips(&self) -> Vec<IpAddr> {
+        self.banned_ips.read().iter().copied().collect()
+    }
+
+    /// Returns the list of trusted validators.
+    pub fn trusted_validators(&self) -> Vec<SocketAddr> {
+        self.trusted_validators.read().iter().copied().collect()
+    }
+
     /// Returns `true` if the given peer IP is an authorized validator.
     pub fn is_authorized_validator_ip(&self, ip: SocketAddr) -> bool {
         // If the peer IP is in the trusted validators, return early.
-        if self.trusted_validators.contains(&ip) {
+        if self.trusted_validators.read().contains(&ip) {
             return true;
         }
         // Retrieve the Aleo address of the peer IP.
@@ -397,6 +438,10 @@ impl<N: Network> Gateway<N> {
         if self.is_connecting_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (already connecting)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(peer_ip.ip()) {
+            bail!("{CONTEXT} Dropping connection attempt to '{peer_ip}' (banned)")
+        }
         Ok(())
     }
 
@@ -406,6 +451,10 @@ impl<N: Network> Gateway<N> {
         if self.is_local_ip(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (attempted to self-connect)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(peer_ip.ip()) {
+            bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (banned)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("{CONTEXT} Dropping connection request from '{peer_ip}' (already shaking hands as the initiator)")
@@ -417,7 +466,7 @@ impl<N: Network> Gateway<N> {
         // Ensure the peer is not spamming connection attempts.
         if !peer_ip.ip().is_loopback() {
             // Add this connection attempt and retrieve the number of attempts.
-            let num_attempts = self.cache.insert_inbound_connection(peer_ip.ip(), RESTRICTED_INTERVAL);
+            let num_attempts = self.cache.insert_inbound_connection(peer_ip.ip(), self.restricted_interval());
             // Ensure the connecting peer has not surpassed the connection attempt limit.
             if num_attempts > MAX_CONNECTION_ATTEMPTS {
                 bail!("Dropping connection request from '{peer_ip}' (tried {num_attempts} times)")
@@ -508,7 +557,7 @@ impl<N: Network> Gateway<N> {
             bail!("{CONTEXT} Dropping '{}' from '{peer_ip}' (not authorized)", event.name())
         }
         // Drop the peer, if they have exceeded the rate limit (i.e. they are requesting too much from us).
-        let num_events = self.cache.insert_inbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+        let num_events = self.cache.insert_inbound_event(peer_ip, self.cache_events_interval());
         if num_events >= self.max_cache_events() {
             bail!("Dropping '{peer_ip}' for spamming events (num_events = {num_events})")
         }
@@ -521,7 +570,7 @@ impl<N: Network> Gateway<N> {
                 _ => unreachable!(),
             };
             // Skip processing this certificate if the rate limit was exceed (i.e. someone is spamming a specific certificate).
-            let num_events = self.cache.insert_inbound_certificate(certificate_id, CACHE_REQUESTS_INTERVAL);
+            let num_events = self.cache.insert_inbound_certificate(certificate_id, self.cache_requests_interval());
             if num_events >= self.max_cache_duplicates() {
                 return Ok(());
             }
@@ -533,7 +582,7 @@ impl<N: Network> Gateway<N> {
                 _ => unreachable!(),
             };
             // Skip processing this certificate if the rate limit was exceeded (i.e. someone is spamming a specific certificate).
-            let num_events = self.cache.insert_inbound_transmission(transmission_id, CACHE_REQUESTS_INTERVAL);
+            let num_events = self.cache.insert_inbound_transmission(transmission_id, self.cache_requests_interval());
             if num_events >= self.max_cache_duplicates() {
                 return Ok(());
             }
@@ -753,7 +802,7 @@ impl<N: Network> Gateway<N> {
             Event::WorkerPing(ping) => {
                 // Ensure the number of transmissions is not too large.
                 ensure!(
-                    ping.transmission_ids.len() <= MAX_TRANSMISSIONS_PER_WORKER_PING,
+                    ping.transmission_ids.len() <= self.config.max_transmissions_per_worker_ping(),
                     "{CONTEXT} Received too many transmissions"
                 );
                 // Retrieve the number of workers.
@@ -776,6 +825,34 @@ impl<N: Network> Gateway<N> {
         }
     }
 
+    /// Inserts the given peer IP into the trusted validators.
+    pub fn insert_trusted_validator(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_validators.write().insert(peer_ip)
+    }
+
+    /// Removes the given peer IP from the trusted validators.
+    pub fn remove_trusted_validator(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_validators.write().shift_remove(&peer_ip)
+    }
+
+    /// Bans the given IP, and disconnects from any connected peers with this IP.
+    pub fn ban(&self, ip: IpAddr) -> bool {
+        // Add the IP to the banned IPs.
+        let is_new = self.banned_ips.write().insert(ip);
+        // Disconnect from any connected peers with this IP.
+        let peer_ips =
+            self.connected_peers.read().iter().filter(|peer_ip| peer_ip.ip() == ip).copied().collect::<Vec<_>>();
+        for peer_ip in peer_ips {
+            self.disconnect(peer_ip);
+        }
+        is_new
+    }
+
+    /// Unbans the given IP.
+    pub fn unban(&self, ip: IpAddr) -> bool {
+        self.banned_ips.write().shift_remove(&ip)
+    }
+
     /// Disconnects from the given peer IP, if the peer is connected.
     pub fn disconnect(&self, peer_ip: SocketAddr) -> JoinHandle<()> {
         let gateway = self.clone();
@@ -856,7 +933,7 @@ impl<N: Network> Gateway<N> {
     /// This function attempts to connect to any disconnected trusted validators.
     fn handle_trusted_validators(&self) {
         // Ensure that the trusted nodes are connected.
-        for validator_ip in &self.trusted_validators {
+        for validator_ip in &self.trusted_validators() {
             // If the trusted_validator is not connected, attempt to connect to it.
             if !self.is_local_ip(*validator_ip)
                 && !self.is_connecting_ip(*validator_ip)
@@ -937,21 +1014,21 @@ impl<N: Network> Transport<N> for Gateway<N> {
         // If the event type is a certificate request, increment the cache.
         if matches!(event, Event::CertificateRequest(_)) | matches!(event, Event::CertificateResponse(_)) {
             // Update the outbound event cache. This is necessary to ensure we don't under count the outbound events.
-            self.cache.insert_outbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+            self.cache.insert_outbound_event(peer_ip, self.cache_events_interval());
             // Send the event to the peer.
-            send!(self, insert_outbound_certificate, CACHE_REQUESTS_INTERVAL, max_cache_certificates)
+            send!(self, insert_outbound_certificate, self.cache_requests_interval(), max_cache_certificates)
         }
         // If the event type is a transmission request, increment the cache.
         else if matches!(event, Event::TransmissionRequest(_)) | matches!(event, Event::TransmissionResponse(_)) {
             // Update the outbound event cache. This is necessary to ensure we don't under count the outbound events.
-            self.cache.insert_outbound_event(peer_ip, CACHE_EVENTS_INTERVAL);
+            self.cache.insert_outbound_event(peer_ip, self.cache_events_interval());
             // Send the event to the peer.
-            send!(self, insert_outbound_transmission, CACHE_REQUESTS_INTERVAL, max_cache_transmissions)
+            send!(self, insert_outbound_transmission, self.cache_requests_interval(), max_cache_transmissions)
         }
         // Otherwise, employ a general rate limit.
         else {
             // Send the event to the peer.
-            send!(self, insert_outbound_event, CACHE_EVENTS_INTERVAL, max_cache_events)
+            send!(self, insert_outbound_event, self.cache_events_interval(), max_cache_events)
         }
     }
 
@@ -987,6 +1064,7 @@ impl<N: Network> Reading for Gateway<N> {
     type Message = Event<N>;
 
     /// The maximum queue depth of incoming messages for a single peer.
+    /// Note: The depth is sized for the default parameters, which bound the configured parameters (see `BFTConfig::validate`).
     const MESSAGE_QUEUE_DEPTH: usize =
         2 * MAX_GC_ROUNDS as usize * Committee::<N>::MAX_COMMITTEE_SIZE as usize * MAX_TRANSMISSIONS_PER_BATCH;
 
@@ -1020,6 +1098,7 @@ impl<N: Network> Writing for Gateway<N> {
     type Message = Event<N>;
 
     /// The maximum queue depth of outgoing messages for a single peer.
+    /// Note: The depth is sized for the default parameters, which bound the configured parameters (see `BFTConfig::validate`).
     const MESSAGE_QUEUE_DEPTH: usize =
         2 * MAX_GC_ROUNDS as usize * Committee::<N>::MAX_COMMITTEE_SIZE as usize * MAX_TRANSMISSIONS_PER_BATCH;
 
@@ -1310,6 +1389,7 @@ mod prop_tests {
     use crate::{
         gateway::prop_tests::GatewayAddress::{Dev, Prod},
         helpers::{init_primary_channels, init_worker_channels, Storage},
+        BFTConfig,
         Gateway,
         Worker,
         MAX_WORKERS,
@@ -1377,6 +1457,7 @@ mod prop_tests {
             any_valid_dev_gateway()
                 .prop_map(|(storage, _, private_key, address)| {
                     Gateway::new(
+                        BFTConfig::default(),
                         Account::try_from(private_key).unwrap(),
                         storage.ledger().clone(),
                         address.ip(),
@@ -1426,7 +1507,9 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway =
+            Gateway::new(BFTConfig::default(), account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port())
+                .unwrap();
         let tcp_config = gateway.tcp().config();
         assert_eq!(tcp_config.listener_ip, Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
         assert_eq!(tcp_config.desired_listening_port, Some(MEMORY_POOL_PORT + dev.port().unwrap()));
@@ -1441,7 +1524,9 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway =
+            Gateway::new(BFTConfig::default(), account.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port())
+                .unwrap();
         let tcp_config = gateway.tcp().config();
         if let Some(socket_addr) = dev.ip() {
             assert_eq!(tcp_config.listener_ip, Some(socket_addr.ip()));
@@ -1466,7 +1551,8 @@ mod prop_tests {
         let worker_storage = storage.clone();
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway = Gateway::new(account, storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway =
+            Gateway::new(BFTConfig::default(), account, storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
 
         let (primary_sender, _) = init_primary_channels();
 
@@ -1481,9 +1567,15 @@ mod prop_tests {
                 let (tx_worker, rx_worker) = init_worker_channels();
                 // Construct the worker instance.
                 let ledger = Arc::new(MockLedgerService::new(committee.clone()));
-                let worker =
-                    Worker::new(id, Arc::new(gateway.clone()), worker_storage.clone(), ledger, Default::default())
-                        .unwrap();
+                let worker = Worker::new(
+                    BFTConfig::default(),
+                    id,
+                    Arc::new(gateway.clone()),
+                    worker_storage.clone(),
+                    ledger,
+                    Default::default(),
+                )
+                .unwrap();
                 // Run the worker instance.
                 worker.run(rx_worker);
 
@@ -41,20 +41,24 @@ const MAX_CHANNEL_SIZE: usize = 8192;
 pub struct ConsensusSender<N: Network> {
     pub tx_consensus_subdag:
         mpsc::Sender<(Subdag<N>, IndexMap<TransmissionID<N>, Transmission<N>>, oneshot::Sender<Result<()>>)>,
+    pub tx_consensus_refill: mpsc::Sender<()>,
 }
 
 #[derive(Debug)]
 pub struct ConsensusReceiver<N: Network> {
     pub rx_consensus_subdag:
         mpsc::Receiver<(Subdag<N>, IndexMap<TransmissionID<N>, Transmission<N>>, oneshot::Sender<Result<()>>)>,
+    pub rx_consensus_refill: mpsc::Receiver<()>,
 }
 
 /// Initializes the consensus channels.
 pub fn init_consensus_channels<N: Network>() -> (ConsensusSender<N>, ConsensusReceiver<N>) {
     let (tx_consensus_subdag, rx_consensus_subdag) = mpsc::channel(MAX_CHANNEL_SIZE);
+    // Note: A single pending refill request suffices, as it covers every request that follows it.
+    let (tx_consensus_refill, rx_consensus_refill) = mpsc::channel(1);
 
-    let sender = ConsensusSender { tx_consensus_subdag };
-    let receiver = ConsensusReceiver { rx_consensus_subdag };
+    let sender = ConsensusSender { tx_consensus_subdag, tx_consensus_refill };
+    let receiver = ConsensusReceiver { rx_consensus_subdag, rx_consensus_refill };
 
     (sender, receiver)
 }
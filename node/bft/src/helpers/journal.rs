@@ -0,0 +1,167 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::Proposal;
+use snarkvm::{
+    console::network::Network,
+    prelude::{error, FromBytes, IoResult, Read, ToBytes, Write},
+};
+
+/// The write-ahead journal of the primary's in-flight proposal state.
+///
+/// The journal is written to storage **before** the primary broadcasts a batch proposal,
+/// and is reloaded on boot, to guarantee the primary never equivocates across restarts.
+///
+/// Note: The signatures received for the proposal are recorded incrementally in storage, next to the journal,
+/// and the batches signed for peers are recorded in the signing history in storage.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProposalJournal<N: Network> {
+    /// The latest round for which the primary proposed a batch.
+    latest_round: u64,
+    /// The batch proposal, if one is in-flight.
+    proposal: Option<Proposal<N>>,
+}
+
+impl<N: Network> ProposalJournal<N> {
+    /// The version of the journal encoding.
+    const VERSION: u8 = 1;
+
+    /// Initializes a new instance of the proposal journal.
+    pub const fn new(latest_round: u64, proposal: Option<Proposal<N>>) -> Self {
+        Self { latest_round, proposal }
+    }
+
+    /// Returns the latest round and the batch proposal.
+    pub fn into_parts(self) -> (u64, Option<Proposal<N>>) {
+        (self.latest_round, self.proposal)
+    }
+}
+
+impl<N: Network> ToBytes for ProposalJournal<N> {
+    /// Writes the proposal journal to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        Self::VERSION.write_le(&mut writer)?;
+        // Write the latest round.
+        self.latest_round.write_le(&mut writer)?;
+        // Write the proposal.
+        match &self.proposal {
+            Some(proposal) => {
+                1u8.write_le(&mut writer)?;
+                proposal.write_le(&mut writer)?;
+            }
+            None => 0u8.write_le(&mut writer)?,
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for ProposalJournal<N> {
+    /// Reads the proposal journal from the buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != Self::VERSION {
+            return Err(error(format!("Invalid proposal journal version ({version})")));
+        }
+        // Read the latest round.
+        let latest_round = u64::read_le(&mut reader)?;
+        // Read the proposal.
+        let proposal = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(Proposal::read_le(&mut reader)?),
+            variant => return Err(error(format!("Invalid proposal variant ({variant})"))),
+        };
+        Ok(Self { latest_round, proposal })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::now, MAX_TRANSMISSIONS_PER_BATCH};
+    use snarkvm::{
+        ledger::{
+            committee::test_helpers::sample_committee_for_round_and_members,
+            narwhal::{BatchHeader, Data, Transmission, TransmissionID},
+        },
+        prelude::{Address, Field, PrivateKey, TestRng, Uniform},
+    };
+
+    use ::bytes::Bytes;
+    use indexmap::IndexMap;
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Samples a proposal journal, with a proposal of the given number of transmissions.
+    fn sample_journal(num_transmissions: usize, rng: &mut TestRng) -> ProposalJournal<CurrentNetwork> {
+        let round = 5;
+        // Sample the transmissions.
+        let transmissions = (0..num_transmissions)
+            .map(|_| {
+                let transmission_id = TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into());
+                (transmission_id, Transmission::Transaction(Data::Buffer(Bytes::from(vec![1u8; 8]))))
+            })
+            .collect::<IndexMap<_, _>>();
+        // Sample a batch header for the transmissions.
+        let author = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let previous_certificate_ids = (0..3).map(|_| Field::<CurrentNetwork>::rand(rng)).collect();
+        let batch_header = BatchHeader::new(
+            &author,
+            round,
+            now(),
+            transmissions.keys().copied().collect(),
+            previous_certificate_ids,
+            Default::default(),
+            rng,
+        )
+        .unwrap();
+        // Sample a committee that includes the batch author.
+        let mut members = vec![batch_header.author()];
+        for _ in 0..3 {
+            members.push(Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap());
+        }
+        let committee = sample_committee_for_round_and_members(round, members, rng);
+        // Construct the proposal.
+        let proposal = Proposal::new(committee, batch_header, transmissions).unwrap();
+
+        ProposalJournal::new(round, Some(proposal))
+    }
+
+    #[test]
+    fn test_bytes() {
+        let rng = &mut TestRng::default();
+
+        for journal in [sample_journal(10, rng), ProposalJournal::new(3, None)] {
+            // Serialize and deserialize the journal.
+            let bytes = journal.to_bytes_le().unwrap();
+            let candidate = ProposalJournal::<CurrentNetwork>::from_bytes_le(&bytes).unwrap();
+            assert!(journal == candidate);
+            // Ensure a truncated journal is rejected.
+            assert!(ProposalJournal::<CurrentNetwork>::from_bytes_le(&bytes[..bytes.len() - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_bytes_full_batch() {
+        let rng = &mut TestRng::default();
+
+        // Ensure a proposal with the default maximum number of transmissions round-trips.
+        let journal = sample_journal(MAX_TRANSMISSIONS_PER_BATCH, rng);
+        let bytes = journal.to_bytes_le().unwrap();
+        let candidate = ProposalJournal::<CurrentNetwork>::from_bytes_le(&bytes).unwrap();
+        assert!(journal == candidate);
+    }
+}
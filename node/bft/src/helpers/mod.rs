@@ -21,12 +21,18 @@ pub use channels::*;
 pub mod dag;
 pub use dag::*;
 
+pub mod journal;
+pub use journal::*;
+
 pub mod partition;
 pub use partition::*;
 
 pub mod pending;
 pub use pending::*;
 
+pub mod priority;
+pub use priority::*;
+
 pub mod proposal;
 pub use proposal::*;
 
@@ -36,6 +42,9 @@ pub use ready::*;
 pub mod resolver;
 pub use resolver::*;
 
+pub mod signing;
+pub use signing::*;
+
 pub mod storage;
 pub use storage::*;
 
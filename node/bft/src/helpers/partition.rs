@@ -53,7 +53,7 @@ pub fn assign_to_worker<N: Network>(transmission_id: impl Into<TransmissionID<N>
 pub fn assign_to_workers<N: Network>(
     workers: &[Worker<N>],
     transmissions: impl Iterator<Item = (TransmissionID<N>, Transmission<N>)>,
-    op: impl Fn(&Worker<N>, TransmissionID<N>, Transmission<N>),
+    mut op: impl FnMut(&Worker<N>, TransmissionID<N>, Transmission<N>),
 ) -> Result<()> {
     // Set the number of workers.
     let num_workers = u8::try_from(workers.len()).expect("Too many workers");
@@ -0,0 +1,563 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::now;
+use snarkvm::{
+    console::prelude::*,
+    ledger::{
+        block::Transaction,
+        coinbase::ProverSolution,
+        narwhal::{Data, Transmission, TransmissionID},
+    },
+    prelude::Address,
+};
+
+use serde::Serialize;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    hash::Hash,
+};
+
+/// The priority of an entry, which is its fee per byte.
+#[derive(Copy, Clone, Debug)]
+pub struct Priority {
+    /// The fee, in microcredits, or `None` if the entry is exempt from fees.
+    fee: Option<u64>,
+    /// The size, in bytes.
+    size: u64,
+}
+
+impl Priority {
+    /// Initializes a new priority from the given fee (in microcredits) and size (in bytes).
+    pub const fn new(fee: u64, size: u64) -> Self {
+        Self { fee: Some(fee), size }
+    }
+
+    /// Initializes a new priority for an entry of the given size (in bytes) that is exempt from fees,
+    /// which precedes every entry that pays a fee.
+    pub const fn exempt(size: u64) -> Self {
+        Self { fee: None, size }
+    }
+
+    /// Returns the priority of the given transaction.
+    pub fn from_transaction<N: Network>(transaction: &Transaction<N>, size: usize) -> Self {
+        Self::new(transaction.fee_amount().map_or(0, |fee| *fee), size as u64)
+    }
+
+    /// Returns the size, in bytes.
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.fee, other.fee) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            // Compare the fees per byte, without the rounding of a division.
+            (Some(fee), Some(other_fee)) => {
+                let lhs = u128::from(fee) * u128::from(other.size.max(1));
+                let rhs = u128::from(other_fee) * u128::from(self.size.max(1));
+                lhs.cmp(&rhs)
+            }
+        }
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
+
+/// The sender of an entry, between which a priority queue is fair.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Sender<N: Network> {
+    /// The address that pays the fee of a transaction, or that found a solution.
+    Address(Address<N>),
+    /// The transmission itself, if its sender is unknown, such as a transaction that pays its fee privately.
+    Transmission(TransmissionID<N>),
+}
+
+/// Returns the sender of the given transaction, which is the payer of its fee.
+/// Note: A transaction with a private fee has no known payer, so it is its own sender.
+fn transaction_sender<N: Network>(transaction: &Transaction<N>) -> Sender<N> {
+    match transaction.fee_transition().and_then(|fee| fee.payer()) {
+        Some(payer) => Sender::Address(payer),
+        None => Sender::Transmission(TransmissionID::Transaction(transaction.id())),
+    }
+}
+
+/// Returns the sender and the priority of the given transaction.
+pub fn transaction_priority<N: Network>(transaction: &Transaction<N>) -> (Sender<N>, Priority) {
+    let size = transaction.to_bytes_le().map_or(0, |bytes| bytes.len());
+    (transaction_sender(transaction), Priority::from_transaction(transaction, size))
+}
+
+/// Returns the sender and the priority of the given transmission.
+/// Note: The solutions and ratifications precede every transaction, as they are exempt from fees.
+pub fn transmission_priority<N: Network>(
+    transmission_id: TransmissionID<N>,
+    transmission: &Transmission<N>,
+) -> (Sender<N>, Priority) {
+    match transmission {
+        Transmission::Ratification => (Sender::Transmission(transmission_id), Priority::exempt(0)),
+        Transmission::Solution(Data::Object(solution)) => {
+            let size = solution.to_bytes_le().map_or(0, |bytes| bytes.len());
+            (Sender::Address(solution.address()), Priority::exempt(size as u64))
+        }
+        Transmission::Solution(Data::Buffer(bytes)) => {
+            let solution = Data::<ProverSolution<N>>::Buffer(bytes.clone());
+            let sender = match solution.deserialize_blocking() {
+                Ok(solution) => Sender::Address(solution.address()),
+                Err(_) => Sender::Transmission(transmission_id),
+            };
+            (sender, Priority::exempt(bytes.len() as u64))
+        }
+        Transmission::Transaction(Data::Object(transaction)) => transaction_priority(transaction),
+        Transmission::Transaction(Data::Buffer(bytes)) => {
+            match Data::<Transaction<N>>::Buffer(bytes.clone()).deserialize_blocking() {
+                Ok(transaction) => {
+                    (transaction_sender(&transaction), Priority::from_transaction(&transaction, bytes.len()))
+                }
+                Err(_) => (Sender::Transmission(transmission_id), Priority::new(0, bytes.len() as u64)),
+            }
+        }
+    }
+}
+
+/// The outcome of an insertion into a priority queue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Insertion<K, V> {
+    /// The entry was inserted.
+    Inserted,
+    /// The entry was inserted, and the given lowest-priority entries were evicted to make room for it.
+    Evicted(Vec<(K, V)>),
+    /// The entry was not inserted, as its key already exists.
+    Duplicate,
+    /// The entry was not inserted, as it has the lowest priority in the full queue.
+    Rejected,
+}
+
+impl<K, V> Insertion<K, V> {
+    /// Returns `true` if the entry was inserted.
+    pub const fn is_inserted(&self) -> bool {
+        matches!(self, Self::Inserted | Self::Evicted(..))
+    }
+
+    /// Returns the number of entries that were dropped by the insertion.
+    pub fn num_dropped(&self) -> usize {
+        match self {
+            Self::Inserted | Self::Duplicate => 0,
+            Self::Evicted(evicted) => evicted.len(),
+            Self::Rejected => 1,
+        }
+    }
+}
+
+/// The state of a priority queue.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct QueueState {
+    /// The number of entries.
+    pub num_entries: usize,
+    /// The maximum number of entries.
+    pub max_entries: usize,
+    /// The total size of the entries, in bytes.
+    pub num_bytes: u64,
+    /// The maximum total size of the entries, in bytes.
+    pub max_bytes: u64,
+    /// The number of entries that were evicted or rejected, as the queue was full.
+    pub num_dropped: u64,
+}
+
+/// The rank of an entry among the entries of its sender, which is its priority, and then its arrival.
+type Rank = (Reverse<Priority>, u64);
+
+#[derive(Clone, Debug)]
+struct Entry<V, S> {
+    /// The value.
+    value: V,
+    /// The sender.
+    sender: S,
+    /// The priority.
+    priority: Priority,
+    /// The timestamp (in seconds) at which the entry was inserted.
+    timestamp: i64,
+    /// The sequence number, which is the order of arrival.
+    seq: u64,
+}
+
+impl<V, S> Entry<V, S> {
+    /// Returns the rank of the entry among the entries of its sender.
+    const fn rank(&self) -> Rank {
+        (Reverse(self.priority), self.seq)
+    }
+}
+
+/// A queue, bounded by its number of entries and their total size, that is drained in order of priority,
+/// with fairness between the senders.
+///
+/// The entries are drained in rounds, where each round takes at most one entry of each sender,
+/// ordered by their fee per byte, and then by their arrival. As such, a sender that floods the queue
+/// only delays its own entries, and its lowest-priority entries are the first to be evicted.
+///
+/// The first entry of each sender is indexed for draining, and the last entry of each sender is indexed
+/// by its round for eviction, so that the insertions, evictions and drains take logarithmic time.
+#[derive(Clone, Debug)]
+pub struct PriorityQueue<K, V, S> {
+    /// The entries.
+    entries: HashMap<K, Entry<V, S>>,
+    /// The keys of the entries, by their sequence number.
+    arrivals: BTreeMap<u64, K>,
+    /// The ranks of the entries of each sender.
+    senders: HashMap<S, BTreeSet<Rank>>,
+    /// The rank of the first entry of each sender, in the order in which they are drained.
+    heads: BTreeSet<Rank>,
+    /// The round and rank of the last entry of each sender, in the order in which they are drained.
+    tails: BTreeSet<(usize, Rank)>,
+    /// The sequence number of the next entry.
+    next_seq: u64,
+    /// The maximum number of entries.
+    max_entries: usize,
+    /// The maximum total size of the entries, in bytes.
+    max_bytes: u64,
+    /// The total size of the entries, in bytes.
+    num_bytes: u64,
+    /// The number of entries that were evicted or rejected, as the queue was full.
+    num_dropped: u64,
+}
+
+impl<K: Copy + Eq + Hash, V, S: Clone + Eq + Hash> PriorityQueue<K, V, S> {
+    /// Initializes a new priority queue with the given maximum number of entries and total size (in bytes).
+    pub fn new(max_entries: usize, max_bytes: u64) -> Self {
+        Self {
+            entries: Default::default(),
+            arrivals: Default::default(),
+            senders: Default::default(),
+            heads: Default::default(),
+            tails: Default::default(),
+            next_seq: 0,
+            max_entries,
+            max_bytes,
+            num_bytes: 0,
+            num_dropped: 0,
+        }
+    }
+
+    /// Returns `true` if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of entries in the queue.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the total size of the entries in the queue, in bytes.
+    pub const fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+
+    /// Returns the state of the queue.
+    pub fn state(&self) -> QueueState {
+        QueueState {
+            num_entries: self.entries.len(),
+            max_entries: self.max_entries,
+            num_bytes: self.num_bytes,
+            max_bytes: self.max_bytes,
+            num_dropped: self.num_dropped,
+        }
+    }
+
+    /// Returns `true` if the queue contains the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the value for the given key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    /// Returns the keys, in order of arrival.
+    pub fn keys(&self) -> impl '_ + Iterator<Item = &K> {
+        self.arrivals.values()
+    }
+
+    /// Returns the entries, in order of arrival.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (&K, &V)> {
+        self.arrivals.values().map(|key| (key, &self.entries[key].value))
+    }
+
+    /// Inserts the given entry into the queue, evicting the lowest-priority entries if the queue is full.
+    pub fn insert(&mut self, key: K, value: V, sender: S, priority: Priority) -> Insertion<K, V> {
+        // Ensure the key is new.
+        if self.entries.contains_key(&key) {
+            return Insertion::Duplicate;
+        }
+        // Insert the entry.
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.insert_entry(key, Entry { value, sender, priority, timestamp: now(), seq });
+        // If the queue is within its limits, return early.
+        if self.entries.len() <= self.max_entries && self.num_bytes <= self.max_bytes {
+            return Insertion::Inserted;
+        }
+        // Evict the entries that would be drained last, until the queue is within its limits.
+        let mut evicted = Vec::new();
+        while self.entries.len() > self.max_entries || self.num_bytes > self.max_bytes {
+            let (_, (_, last)) = *self.tails.last().expect("The queue is not empty");
+            // If the new entry would be evicted, reject it instead, and restore the evicted entries.
+            if last == seq {
+                self.remove_entry(&key);
+                for (key, entry) in evicted {
+                    self.insert_entry(key, entry);
+                }
+                self.num_dropped += 1;
+                return Insertion::Rejected;
+            }
+            let last_key = self.arrivals[&last];
+            if let Some(entry) = self.remove_entry(&last_key) {
+                evicted.push((last_key, entry));
+            }
+        }
+        self.num_dropped += evicted.len() as u64;
+        Insertion::Evicted(evicted.into_iter().map(|(key, entry)| (key, entry.value)).collect())
+    }
+
+    /// Removes the entry for the given key, and returns its value.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|entry| entry.value)
+    }
+
+    /// Retains only the entries for which the given function returns `true`,
+    /// and returns the keys of the removed entries.
+    pub fn retain(&mut self, mut keep: impl FnMut(&K, &V) -> bool) -> Vec<K> {
+        self.retain_entries(|key, entry| keep(key, &entry.value))
+    }
+
+    /// Removes the entries that were inserted before the given timestamp (in seconds), and returns their keys.
+    pub fn expire(&mut self, cutoff: i64) -> Vec<K> {
+        self.retain_entries(|_, entry| entry.timestamp >= cutoff)
+    }
+
+    /// Removes up to the given number of entries in order of priority, and returns them.
+    pub fn drain(&mut self, num_entries: usize) -> Vec<(K, V)> {
+        let mut drained = Vec::with_capacity(num_entries.min(self.entries.len()));
+        // The first entries of the senders that were drained in this round, which are deferred to the next round.
+        let mut deferred = Vec::new();
+        while drained.len() < num_entries {
+            // Take the highest-priority entry of this round, or start the next round once every sender had its turn.
+            let Some((_, seq)) = self.heads.pop_first() else {
+                if deferred.is_empty() {
+                    break;
+                }
+                self.heads.extend(deferred.drain(..));
+                continue;
+            };
+            let key = self.arrivals[&seq];
+            let Some(entry) = self.remove_entry(&key) else {
+                continue;
+            };
+            if let Some(head) = self.senders.get(&entry.sender).and_then(|ranks| ranks.first()) {
+                self.heads.remove(head);
+                deferred.push(*head);
+            }
+            drained.push((key, entry.value));
+        }
+        self.heads.extend(deferred);
+        drained
+    }
+
+    /// Retains only the entries for which the given function returns `true`,
+    /// and returns the keys of the removed entries.
+    fn retain_entries(&mut self, mut keep: impl FnMut(&K, &Entry<V, S>) -> bool) -> Vec<K> {
+        let removed =
+            self.arrivals.values().filter(|key| !keep(*key, &self.entries[*key])).copied().collect::<Vec<_>>();
+        for key in &removed {
+            self.remove_entry(key);
+        }
+        removed
+    }
+
+    /// Inserts the given entry, and indexes it by its sender.
+    fn insert_entry(&mut self, key: K, entry: Entry<V, S>) {
+        self.unlink(&entry.sender);
+        self.senders.entry(entry.sender.clone()).or_default().insert(entry.rank());
+        self.link(&entry.sender);
+        self.arrivals.insert(entry.seq, key);
+        self.num_bytes = self.num_bytes.saturating_add(entry.priority.size());
+        self.entries.insert(key, entry);
+    }
+
+    /// Removes the entry for the given key, and its index by its sender.
+    fn remove_entry(&mut self, key: &K) -> Option<Entry<V, S>> {
+        let entry = self.entries.remove(key)?;
+        self.arrivals.remove(&entry.seq);
+        self.num_bytes = self.num_bytes.saturating_sub(entry.priority.size());
+        self.unlink(&entry.sender);
+        if let Some(ranks) = self.senders.get_mut(&entry.sender) {
+            ranks.remove(&entry.rank());
+            if ranks.is_empty() {
+                self.senders.remove(&entry.sender);
+            }
+        }
+        self.link(&entry.sender);
+        Some(entry)
+    }
+
+    /// Removes the first and last entries of the given sender from the indices.
+    fn unlink(&mut self, sender: &S) {
+        if let Some(ranks) = self.senders.get(sender) {
+            if let (Some(first), Some(last)) = (ranks.first(), ranks.last()) {
+                self.heads.remove(first);
+                self.tails.remove(&(ranks.len() - 1, *last));
+            }
+        }
+    }
+
+    /// Adds the first and last entries of the given sender to the indices.
+    fn link(&mut self, sender: &S) {
+        if let Some(ranks) = self.senders.get(sender) {
+            if let (Some(first), Some(last)) = (ranks.first(), ranks.last()) {
+                self.heads.insert(*first);
+                self.tails.insert((ranks.len() - 1, *last));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority() {
+        // Ensure the priorities are compared by their fee per byte.
+        assert!(Priority::new(200, 100) > Priority::new(300, 200));
+        assert_eq!(Priority::new(100, 100), Priority::new(200, 200));
+        assert!(Priority::exempt(1000) > Priority::new(u64::MAX, 1));
+        assert_eq!(Priority::exempt(1000), Priority::exempt(1));
+        assert!(Priority::new(0, 0) < Priority::new(1, 1000));
+    }
+
+    #[test]
+    fn test_priority_queue_order() {
+        let mut queue = PriorityQueue::new(10, u64::MAX);
+        // Insert the entries of a sender that floods the queue, followed by the entries of another sender.
+        assert!(queue.insert(1, "a1", 'a', Priority::new(10, 1)).is_inserted());
+        assert!(queue.insert(2, "a2", 'a', Priority::new(30, 1)).is_inserted());
+        assert!(queue.insert(3, "a3", 'a', Priority::new(20, 1)).is_inserted());
+        assert!(queue.insert(4, "b1", 'b', Priority::new(5, 1)).is_inserted());
+        assert!(queue.insert(5, "b2", 'b', Priority::new(5, 1)).is_inserted());
+        assert_eq!(queue.insert(5, "b2", 'b', Priority::new(5, 1)), Insertion::Duplicate);
+
+        // Ensure each round takes one entry of each sender, in order of priority and arrival.
+        let drained = queue.drain(3).into_iter().map(|(_, value)| value).collect::<Vec<_>>();
+        assert_eq!(drained, vec!["a2", "b1", "a3"]);
+        assert_eq!(queue.keys().copied().collect::<Vec<_>>(), vec![1, 5]);
+
+        let drained = queue.drain(10).into_iter().map(|(_, value)| value).collect::<Vec<_>>();
+        assert_eq!(drained, vec!["a1", "b2"]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_priority_queue_eviction() {
+        let mut queue = PriorityQueue::new(3, u64::MAX);
+        assert!(queue.insert(1, "a1", 'a', Priority::new(10, 1)).is_inserted());
+        assert!(queue.insert(2, "a2", 'a', Priority::new(20, 1)).is_inserted());
+        assert!(queue.insert(3, "b1", 'b', Priority::new(1, 1)).is_inserted());
+
+        // Ensure the lowest-priority entry of the sender with the most entries is evicted.
+        assert_eq!(queue.insert(4, "c1", 'c', Priority::new(1, 1)), Insertion::Evicted(vec![(1, "a1")]));
+        assert_eq!(queue.len(), 3);
+
+        // Ensure a new entry that would be drained last is rejected.
+        assert_eq!(queue.insert(5, "a3", 'a', Priority::new(5, 1)), Insertion::Rejected);
+        assert_eq!(queue.keys().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(queue.state().num_dropped, 2);
+    }
+
+    #[test]
+    fn test_priority_queue_removal() {
+        let mut queue = PriorityQueue::new(3, u64::MAX);
+        assert!(queue.insert(1, "a1", 'a', Priority::new(10, 1)).is_inserted());
+        assert!(queue.insert(2, "a2", 'a', Priority::new(20, 1)).is_inserted());
+        assert!(queue.insert(3, "b1", 'b', Priority::new(1, 1)).is_inserted());
+
+        // Ensure a removed entry no longer counts towards the rounds of its sender.
+        assert_eq!(queue.remove(&2), Some("a2"));
+        assert!(queue.insert(4, "c1", 'c', Priority::new(1, 1)).is_inserted());
+        assert_eq!(queue.insert(5, "d1", 'd', Priority::new(1, 1)), Insertion::Rejected);
+        assert_eq!(queue.insert(6, "a3", 'a', Priority::new(50, 1)), Insertion::Evicted(vec![(1, "a1")]));
+
+        let drained = queue.drain(10).into_iter().map(|(_, value)| value).collect::<Vec<_>>();
+        assert_eq!(drained, vec!["a3", "b1", "c1"]);
+    }
+
+    #[test]
+    fn test_priority_queue_bytes() {
+        let mut queue = PriorityQueue::new(10, 100);
+        assert!(queue.insert(1, "a1", 'a', Priority::new(10, 40)).is_inserted());
+        assert!(queue.insert(2, "b1", 'b', Priority::new(10, 40)).is_inserted());
+        assert_eq!(queue.num_bytes(), 80);
+
+        // Ensure the lowest-priority entries are evicted until the queue is within its size.
+        assert_eq!(queue.insert(3, "c1", 'c', Priority::new(1000, 90)), Insertion::Evicted(vec![(2, "b1"), (1, "a1")]));
+        assert_eq!(queue.num_bytes(), 90);
+
+        // Ensure a new entry that would be drained last is rejected, and the size is unchanged.
+        assert_eq!(queue.insert(4, "d1", 'd', Priority::new(1, 20)), Insertion::Rejected);
+        assert_eq!(queue.num_bytes(), 90);
+
+        // Ensure the size is updated when the entries are removed.
+        assert_eq!(queue.drain(1), vec![(3, "c1")]);
+        assert_eq!(queue.num_bytes(), 0);
+        assert_eq!(
+            queue.state(),
+            QueueState { num_entries: 0, max_entries: 10, num_bytes: 0, max_bytes: 100, num_dropped: 3 }
+        );
+    }
+
+    #[test]
+    fn test_priority_queue_retain() {
+        let mut queue = PriorityQueue::new(10, u64::MAX);
+        assert!(queue.insert(1, "a1", 'a', Priority::new(10, 10)).is_inserted());
+        assert!(queue.insert(2, "a2", 'a', Priority::new(10, 20)).is_inserted());
+        assert!(queue.insert(3, "b1", 'b', Priority::new(10, 30)).is_inserted());
+
+        // Ensure the removed entries are returned, and the size is updated.
+        assert_eq!(queue.retain(|_, value| !value.starts_with('a')), vec![1, 2]);
+        assert_eq!(queue.num_bytes(), 30);
+
+        // Ensure the entries are only expired if they were inserted before the cutoff.
+        assert!(queue.expire(now() - 60).is_empty());
+        assert_eq!(queue.expire(now() + 1), vec![3]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.num_bytes(), 0);
+    }
+}
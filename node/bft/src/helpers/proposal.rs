@@ -22,12 +22,13 @@ use snarkvm::{
         committee::Committee,
         narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
     },
-    prelude::{bail, ensure, Itertools, Result},
+    prelude::{bail, ensure, error, FromBytes, IoResult, Itertools, Read, Result, ToBytes, Write},
 };
 
 use indexmap::{IndexMap, IndexSet};
 use std::collections::HashSet;
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct Proposal<N: Network> {
     /// The proposed batch header.
     batch_header: BatchHeader<N>,
@@ -169,6 +170,59 @@ impl<N: Network> Proposal<N> {
     }
 }
 
+impl<N: Network> ToBytes for Proposal<N> {
+    /// Writes the proposal to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the batch header.
+        self.batch_header.write_le(&mut writer)?;
+        // Write the number of transmissions.
+        u32::try_from(self.transmissions.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the transmissions.
+        for (transmission_id, transmission) in &self.transmissions {
+            transmission_id.write_le(&mut writer)?;
+            transmission.write_le(&mut writer)?;
+        }
+        // Write the number of signatures.
+        u32::try_from(self.signatures.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the signatures.
+        for signature in &self.signatures {
+            signature.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> FromBytes for Proposal<N> {
+    /// Reads the proposal from the buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the batch header.
+        let batch_header = FromBytes::read_le(&mut reader)?;
+        // Read the number of transmissions.
+        let num_transmissions = u32::read_le(&mut reader)?;
+        // Ensure the number of transmissions is within bounds.
+        if num_transmissions as usize > BatchHeader::<N>::MAX_TRANSMISSIONS {
+            return Err(error(format!("Number of transmissions ({num_transmissions}) exceeds the maximum")));
+        }
+        // Read the transmissions.
+        let mut transmissions = IndexMap::with_capacity(num_transmissions as usize);
+        for _ in 0..num_transmissions {
+            transmissions.insert(FromBytes::read_le(&mut reader)?, FromBytes::read_le(&mut reader)?);
+        }
+        // Read the number of signatures.
+        let num_signatures = u32::read_le(&mut reader)?;
+        // Ensure the number of signatures is within bounds.
+        if num_signatures as usize > Committee::<N>::MAX_COMMITTEE_SIZE as usize {
+            return Err(error(format!("Number of signatures ({num_signatures}) exceeds the maximum")));
+        }
+        // Read the signatures.
+        let mut signatures = IndexSet::with_capacity(num_signatures as usize);
+        for _ in 0..num_signatures {
+            signatures.insert(FromBytes::read_le(&mut reader)?);
+        }
+        Ok(Self { batch_header, transmissions, signatures })
+    }
+}
+
 #[cfg(test)]
 mod prop_tests {
     use crate::helpers::{
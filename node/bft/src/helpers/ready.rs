@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{
+    helpers::{fmt_id, transmission_priority, Insertion, PriorityQueue, QueueState, Sender},
+    spawn_blocking,
+    MAX_TRANSMISSIONS_PER_BATCH,
+};
 use snarkvm::{
     console::prelude::*,
     ledger::{
@@ -25,23 +30,28 @@ use indexmap::{IndexMap, IndexSet};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+/// The maximum number of transmissions in the ready queue, as a multiple of the maximum transmissions per batch.
+/// Note: The ready queue is only bounded by its number of transmissions, as each transmission is bounded in size.
+const MAX_READY_BATCHES: usize = 4;
+
 #[derive(Clone, Debug)]
 pub struct Ready<N: Network> {
-    /// The current map of `(transmission ID, transmission)` entries.
-    transmissions: Arc<RwLock<IndexMap<TransmissionID<N>, Transmission<N>>>>,
+    /// The current queue of `(transmission ID, transmission)` entries, by their sender and fee per byte.
+    transmissions: Arc<RwLock<PriorityQueue<TransmissionID<N>, Transmission<N>, Sender<N>>>>,
 }
 
 impl<N: Network> Default for Ready<N> {
     /// Initializes a new instance of the ready queue.
     fn default() -> Self {
-        Self::new()
+        Self::new(MAX_TRANSMISSIONS_PER_BATCH)
     }
 }
 
 impl<N: Network> Ready<N> {
-    /// Initializes a new instance of the ready queue.
-    pub fn new() -> Self {
-        Self { transmissions: Default::default() }
+    /// Initializes a new instance of the ready queue, for the given maximum number of transmissions per batch.
+    pub fn new(max_transmissions_per_batch: usize) -> Self {
+        let max_transmissions = MAX_READY_BATCHES * max_transmissions_per_batch;
+        Self { transmissions: Arc::new(RwLock::new(PriorityQueue::new(max_transmissions, u64::MAX))) }
     }
 
     /// Returns `true` if the ready queue is empty.
@@ -54,6 +64,11 @@ impl<N: Network> Ready<N> {
         self.transmissions.read().len()
     }
 
+    /// Returns the state of the ready queue.
+    pub fn state(&self) -> QueueState {
+        self.transmissions.read().state()
+    }
+
     /// Returns the number of ratifications in the ready queue.
     pub fn num_ratifications(&self) -> usize {
         self.transmissions.read().keys().filter(|id| matches!(id, TransmissionID::Ratification)).count()
@@ -76,12 +91,12 @@ impl<N: Network> Ready<N> {
 
     /// Returns the transmissions in the ready queue.
     pub fn transmissions(&self) -> IndexMap<TransmissionID<N>, Transmission<N>> {
-        self.transmissions.read().clone()
+        self.transmissions.read().iter().map(|(id, transmission)| (*id, transmission.clone())).collect()
     }
 
     /// Returns the solutions in the ready queue.
     pub fn solutions(&self) -> impl '_ + Iterator<Item = (PuzzleCommitment<N>, Data<ProverSolution<N>>)> {
-        self.transmissions.read().clone().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
+        self.transmissions().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
             (TransmissionID::Solution(id), Transmission::Solution(solution)) => Some((id, solution)),
             _ => None,
         })
@@ -89,7 +104,7 @@ impl<N: Network> Ready<N> {
 
     /// Returns the transactions in the ready queue.
     pub fn transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
-        self.transmissions.read().clone().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
+        self.transmissions().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
             (TransmissionID::Transaction(id), Transmission::Transaction(tx)) => Some((id, tx)),
             _ => None,
         })
@@ -109,22 +124,58 @@ impl<N: Network> Ready<N> {
 
     /// Inserts the specified (`transmission ID`, `transmission`) to the ready queue.
     /// Returns `true` if the transmission is new, and was added to the ready queue.
-    pub fn insert(&self, transmission_id: impl Into<TransmissionID<N>>, transmission: Transmission<N>) -> bool {
+    /// If the ready queue is full, the lowest-priority transmission is evicted to make room.
+    pub async fn insert(&self, transmission_id: impl Into<TransmissionID<N>>, transmission: Transmission<N>) -> bool {
         let transmission_id = transmission_id.into();
-        // Insert the transmission ID.
-        let is_new = self.transmissions.write().insert(transmission_id, transmission).is_none();
+        // Skip a known transmission, before determining its priority.
+        if self.contains(transmission_id) {
+            return false;
+        }
+        // Determine the sender and priority of the transmission.
+        // Note: This is done in a blocking task, as it (de)serializes the transmission to read its size and fee.
+        let Ok((transmission, (sender, priority))) = spawn_blocking!({
+            let rank = transmission_priority(transmission_id, &transmission);
+            Ok((transmission, rank))
+        }) else {
+            return false;
+        };
+        // Insert the transmission, if it is new.
+        // Note: The queue checks the transmission ID under the write lock, as it may have been inserted meanwhile.
+        let insertion = self.transmissions.write().insert(transmission_id, transmission, sender, priority);
+        // Report the transmissions that were dropped from the full ready queue.
+        match &insertion {
+            Insertion::Evicted(evicted) => {
+                for (evicted_id, _) in evicted {
+                    trace!("Evicted transmission '{}' from the full ready queue", fmt_id(evicted_id));
+                }
+            }
+            Insertion::Rejected => {
+                trace!("Transmission '{}' does not fit in the full ready queue", fmt_id(transmission_id))
+            }
+            Insertion::Inserted | Insertion::Duplicate => (),
+        }
+        #[cfg(feature = "metrics")]
+        metrics::counter(metrics::bft::DROPPED_TRANSMISSIONS, insertion.num_dropped() as u64);
         // Return whether the transmission is new.
-        is_new
+        insertion.is_inserted()
     }
 
-    /// Removes up to the specified number of transmissions and returns them.
+    /// Removes up to the specified number of transmissions in order of priority, and returns them.
     pub fn drain(&self, num_transmissions: usize) -> IndexMap<TransmissionID<N>, Transmission<N>> {
-        // Acquire the write lock.
-        let mut transmissions = self.transmissions.write();
-        // Determine the number of transmissions to drain.
-        let range = 0..transmissions.len().min(num_transmissions);
-        // Drain the transmission IDs.
-        transmissions.drain(range).collect::<IndexMap<_, _>>()
+        // Drain the transmissions.
+        self.transmissions.write().drain(num_transmissions).into_iter().collect::<IndexMap<_, _>>()
+    }
+
+    /// Removes the transmissions for which the given function returns `false`,
+    /// and returns the number of removed transmissions.
+    pub fn retain(&self, keep: impl FnMut(&TransmissionID<N>, &Transmission<N>) -> bool) -> usize {
+        self.transmissions.write().retain(keep).len()
+    }
+
+    /// Removes the transmissions that were inserted before the given timestamp (in seconds),
+    /// and returns the number of removed transmissions.
+    pub fn expire(&self, cutoff: i64) -> usize {
+        self.transmissions.write().expire(cutoff).len()
     }
 }
 
@@ -137,15 +188,15 @@ mod tests {
 
     type CurrentNetwork = snarkvm::prelude::Testnet3;
 
-    #[test]
-    fn test_ready() {
+    #[tokio::test]
+    async fn test_ready() {
         let rng = &mut TestRng::default();
 
         // Sample random fake bytes.
         let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
 
         // Initialize the ready queue.
-        let ready = Ready::<CurrentNetwork>::new();
+        let ready = Ready::<CurrentNetwork>::default();
 
         // Initialize the commitments.
         let commitment_1 = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
@@ -158,9 +209,9 @@ mod tests {
         let solution_3 = Transmission::Solution(data(rng));
 
         // Insert the commitments.
-        assert!(ready.insert(commitment_1, solution_1.clone()));
-        assert!(ready.insert(commitment_2, solution_2.clone()));
-        assert!(ready.insert(commitment_3, solution_3.clone()));
+        assert!(ready.insert(commitment_1, solution_1.clone()).await);
+        assert!(ready.insert(commitment_2, solution_2.clone()).await);
+        assert!(ready.insert(commitment_3, solution_3.clone()).await);
 
         // Check the number of transmissions.
         assert_eq!(ready.num_transmissions(), 3);
@@ -197,8 +248,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_ready_duplicate() {
+    #[tokio::test]
+    async fn test_ready_duplicate() {
         use rand::RngCore;
         let rng = &mut TestRng::default();
 
@@ -208,7 +259,7 @@ mod tests {
         let data = Data::Buffer(Bytes::from(vec));
 
         // Initialize the ready queue.
-        let ready = Ready::<CurrentNetwork>::new();
+        let ready = Ready::<CurrentNetwork>::default();
 
         // Initialize the commitments.
         let commitment = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
@@ -217,10 +268,33 @@ mod tests {
         let solution = Transmission::Solution(data);
 
         // Insert the commitments.
-        assert!(ready.insert(commitment, solution.clone()));
-        assert!(!ready.insert(commitment, solution));
+        assert!(ready.insert(commitment, solution.clone()).await);
+        assert!(!ready.insert(commitment, solution).await);
 
         // Check the number of transmissions.
         assert_eq!(ready.num_transmissions(), 1);
     }
+
+    #[tokio::test]
+    async fn test_ready_full() {
+        let rng = &mut TestRng::default();
+
+        // Sample random fake bytes.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+
+        // Initialize the ready queue, for one transmission per batch.
+        let ready = Ready::<CurrentNetwork>::new(1);
+
+        // Fill the ready queue, and insert one more solution.
+        for _ in 0..MAX_READY_BATCHES + 1 {
+            let commitment = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
+            ready.insert(commitment, Transmission::Solution(data(rng))).await;
+        }
+
+        // Check that the ready queue reports the dropped solution.
+        let state = ready.state();
+        assert_eq!(state.num_entries, MAX_READY_BATCHES);
+        assert_eq!(state.max_entries, MAX_READY_BATCHES);
+        assert_eq!(state.num_dropped, 1);
+    }
 }
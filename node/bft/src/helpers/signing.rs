@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_bft_storage_service::StorageService;
+use snarkvm::{
+    console::{account::Address, network::Network, types::Field},
+    prelude::{ensure, Result},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A batch signed by this validator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SignedBatch<N: Network> {
+    /// The round of the batch.
+    pub round: u64,
+    /// The author of the batch.
+    pub author: Address<N>,
+    /// The batch ID.
+    pub batch_id: Field<N>,
+}
+
+/// The signing history of a validator, used to protect against signing two different batches
+/// for the same round and author. This is the interchange format used to migrate a validator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SigningHistory<N: Network> {
+    /// The network ID.
+    network: u16,
+    /// The batches signed by this validator, ordered by round.
+    signed_batches: Vec<SignedBatch<N>>,
+}
+
+impl<N: Network> SigningHistory<N> {
+    /// Exports the signing history from the given storage service.
+    ///
+    /// The export holds every batch signed within the DAG GC window, plus the highest signed round
+    /// of each author, which storage keeps regardless of garbage collection.
+    pub fn export(storage: &dyn StorageService<N>) -> Self {
+        let signed_batches = storage
+            .signed_batches()
+            .into_iter()
+            .map(|(round, author, batch_id)| SignedBatch { round, author, batch_id })
+            .collect();
+        Self { network: N::ID, signed_batches }
+    }
+
+    /// Imports the signing history into the given storage service, returning the number of signed batches.
+    ///
+    /// This method returns an error if the history conflicts with the signing history in storage,
+    /// in which case the signed batches preceding the conflict remain in storage.
+    pub fn import(&self, storage: &dyn StorageService<N>) -> Result<usize> {
+        // Ensure the signing history is for this network.
+        ensure!(self.network == N::ID, "Signing history is for network {}, expected network {}", self.network, N::ID);
+        // Insert the signed batches.
+        for SignedBatch { round, author, batch_id } in &self.signed_batches {
+            storage.insert_signed_batch(*round, *author, *batch_id)?;
+        }
+        Ok(self.signed_batches.len())
+    }
+
+    /// Returns the batches signed by this validator, ordered by round.
+    pub fn signed_batches(&self) -> &[SignedBatch<N>] {
+        &self.signed_batches
+    }
+}
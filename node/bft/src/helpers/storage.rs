@@ -20,7 +20,7 @@ use snarkvm::{
         block::Block,
         narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
     },
-    prelude::{anyhow, bail, cfg_iter, ensure, Address, Field, Network, Result},
+    prelude::{anyhow, bail, cfg_iter, ensure, Address, Field, Network, Result, Signature},
 };
 
 use indexmap::{IndexMap, IndexSet};
@@ -85,7 +85,8 @@ pub struct StorageInner<N: Network> {
     certificates: RwLock<IndexMap<Field<N>, BatchCertificate<N>>>,
     /// The map of `batch ID` to `round`.
     batch_ids: RwLock<IndexMap<Field<N>, u64>>,
-    /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
+    /// The storage service, holding the `transmission ID` to `(transmission, certificate IDs)` entries,
+    /// and persisting the `rounds`, `certificates`, and `batch_ids` maps across restarts.
     transmissions: Arc<dyn StorageService<N>>,
 }
 
@@ -113,11 +114,87 @@ impl<N: Network> Storage<N> {
             batch_ids: Default::default(),
             transmissions,
         }));
+        // Restore the certificate DAG from the storage service.
+        let restored_round = storage.restore_certificates();
         // Update the storage to the current round.
-        storage.update_current_round(current_round);
+        storage.update_current_round(current_round.max(restored_round));
         // Return the storage.
         storage
     }
+
+    /// Restores the `rounds`, `certificates`, and `batch_ids` maps from the storage service,
+    /// returning the highest restored round (or `0` if there are no certificates in the storage service).
+    ///
+    /// The certificates at or below the GC round of the ledger, and the certificates that were already
+    /// committed to the ledger, are removed from the storage service instead of being restored.
+    /// Note: The committed certificates within the GC range are synced from the ledger at bootup.
+    fn restore_certificates(&self) -> u64 {
+        // Retrieve the certificates from the storage service.
+        let certificates = self.transmissions.certificates();
+        // If there are no certificates, return early.
+        if certificates.is_empty() {
+            return 0;
+        }
+        // Compute the GC round of the ledger.
+        let ledger_gc_round = self.ledger.latest_round().saturating_sub(self.max_gc_rounds);
+        // Initialize a tracker for the highest restored round.
+        let mut highest_round = 0;
+        // Initialize a counter for the restored certificates.
+        let mut num_restored = 0;
+        // Acquire the write locks.
+        let mut rounds = self.rounds.write();
+        let mut certificates_map = self.certificates.write();
+        let mut batch_ids = self.batch_ids.write();
+        for certificate in &certificates {
+            // Retrieve the round.
+            let round = certificate.round();
+            // Retrieve the certificate ID.
+            let certificate_id = certificate.id();
+            // Retrieve the batch ID.
+            let batch_id = certificate.batch_id();
+
+            // If the certificate is stale or already committed, remove it from the storage service.
+            // Note: On failure to read from the ledger, we restore the certificate, as it is re-checked when committing.
+            if round <= ledger_gc_round || self.ledger.contains_certificate(&certificate_id).unwrap_or(false) {
+                self.transmissions.remove_transmissions(&certificate_id, certificate.transmission_ids());
+                self.transmissions.remove_certificate(certificate);
+                continue;
+            }
+
+            // Insert the round to certificate ID entry.
+            rounds.entry(round).or_default().insert((certificate_id, batch_id, certificate.author()));
+            // Insert the certificate.
+            certificates_map.insert(certificate_id, certificate.clone());
+            // Insert the batch ID.
+            batch_ids.insert(batch_id, round);
+            // Update the highest restored round.
+            highest_round = highest_round.max(round);
+            // Increment the number of restored certificates.
+            num_restored += 1;
+        }
+        info!(
+            "Restored {num_restored} of {} certificates from storage (up to round {highest_round})",
+            certificates.len()
+        );
+        highest_round
+    }
+
+    /// Returns the certificates in storage above the GC round, ordered by round.
+    pub(crate) fn get_certificates_above_gc_round(&self) -> Vec<BatchCertificate<N>> {
+        // Retrieve the GC round.
+        let gc_round = self.gc_round();
+        // Retrieve the certificates above the GC round.
+        let mut certificates = self
+            .certificates
+            .read()
+            .values()
+            .filter(|certificate| certificate.round() > gc_round)
+            .cloned()
+            .collect::<Vec<_>>();
+        // Sort the certificates by round.
+        certificates.sort_by_key(|certificate| certificate.round());
+        certificates
+    }
 }
 
 impl<N: Network> Storage<N> {
@@ -211,6 +288,8 @@ impl<N: Network> Storage<N> {
                     self.remove_certificate(certificate.id());
                 }
             }
+            // Remove the signed batches for the GC round(s) from storage, keeping the highest signed round of each author.
+            self.transmissions.remove_signed_batches(next_gc_round);
             // Update the GC round.
             self.gc_round.store(next_gc_round, Ordering::SeqCst);
         }
@@ -524,6 +603,8 @@ impl<N: Network> Storage<N> {
         self.rounds.write().entry(round).or_default().insert((certificate_id, batch_id, author));
         // Obtain the certificate's transmission ids.
         let transmission_ids = certificate.transmission_ids().clone();
+        // Persist the certificate into the DAG in storage.
+        self.transmissions.insert_certificate(&certificate);
         // Insert the certificate.
         self.certificates.write().insert(certificate_id, certificate);
         // Insert the batch ID.
@@ -568,11 +649,43 @@ impl<N: Network> Storage<N> {
         self.batch_ids.write().remove(&batch_id);
         // Remove the transmission entries in the certificate from storage.
         self.transmissions.remove_transmissions(&certificate_id, certificate.transmission_ids());
+        // Remove the certificate from the DAG in storage.
+        self.transmissions.remove_certificate(&certificate);
         // Return successfully.
         true
     }
 }
 
+impl<N: Network> Storage<N> {
+    /// Returns the proposal journal from the storage service, if one exists.
+    pub(crate) fn get_proposal_journal(&self) -> Option<Vec<u8>> {
+        self.transmissions.get_proposal_journal()
+    }
+
+    /// Stores the given proposal journal in the storage service, replacing the existing journal and its signatures.
+    pub(crate) fn store_proposal_journal(&self, journal: Vec<u8>) -> Result<()> {
+        self.transmissions.store_proposal_journal(journal)
+    }
+
+    /// Returns the signatures recorded for the journaled proposal with the given `batch ID`.
+    pub(crate) fn get_proposal_signatures(&self, batch_id: Field<N>) -> Vec<Signature<N>> {
+        self.transmissions.get_proposal_signatures(batch_id)
+    }
+
+    /// Records the given signature for the journaled proposal with the given `batch ID`.
+    pub(crate) fn insert_proposal_signature(&self, batch_id: Field<N>, signature: Signature<N>) -> Result<()> {
+        self.transmissions.insert_proposal_signature(batch_id, signature)
+    }
+
+    /// Records that this validator signed the given batch header.
+    ///
+    /// This method returns an error if a different batch header was already signed for the same round and author,
+    /// in which case the batch header must not be signed.
+    pub(crate) fn insert_signed_batch(&self, batch_header: &BatchHeader<N>) -> Result<()> {
+        self.transmissions.insert_signed_batch(batch_header.round(), batch_header.author(), batch_header.batch_id())
+    }
+}
+
 impl<N: Network> Storage<N> {
     /// Syncs the current height with the block.
     pub(crate) fn sync_height_with_block(&self, next_height: u32) {
@@ -739,6 +852,7 @@ impl<N: Network> Storage<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::helpers::SigningHistory;
     use snarkos_node_bft_ledger_service::MockLedgerService;
     use snarkos_node_bft_storage_service::BFTMemoryService;
     use snarkvm::{
@@ -878,6 +992,176 @@ mod tests {
         assert_storage(&storage, &[], &[], &[], &Default::default());
     }
 
+    #[test]
+    fn test_certificate_restore() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage service.
+        let service: Arc<dyn StorageService<CurrentNetwork>> = Arc::new(BFTMemoryService::new());
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger.clone(), service.clone(), 1);
+
+        // Create a new certificate.
+        let certificate =
+            snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_for_round(5, rng);
+        // Retrieve the certificate ID.
+        let certificate_id = certificate.id();
+        // Retrieve the round.
+        let round = certificate.round();
+        // Retrieve the batch ID.
+        let batch_id = certificate.batch_id();
+        // Retrieve the author of the batch.
+        let author = certificate.author();
+
+        // Construct the sample 'transmissions'.
+        let (missing_transmissions, transmissions) = sample_transmissions(&certificate, rng);
+        // Insert the certificate.
+        storage.insert_certificate_atomic(certificate.clone(), missing_transmissions);
+        // Ensure the certificate is persisted in the storage service.
+        assert_eq!(service.certificates(), vec![certificate.clone()]);
+
+        // Initialize a new storage from the same storage service (i.e. simulate a restart).
+        let storage = Storage::<CurrentNetwork>::new(ledger, service.clone(), 1);
+        // Ensure the storage resumed at the round of the certificate.
+        assert_eq!(storage.current_round(), round);
+        // Ensure the certificate DAG was restored.
+        {
+            // Construct the expected layout for 'rounds'.
+            let rounds = [(round, indexset! { (certificate_id, batch_id, author) })];
+            // Construct the expected layout for 'certificates'.
+            let certificates = [(certificate_id, certificate.clone())];
+            // Construct the expected layout for 'batch_ids'.
+            let batch_ids = [(batch_id, round)];
+            // Assert the storage is well-formed.
+            assert_storage(&storage, &rounds, &certificates, &batch_ids, &transmissions);
+        }
+
+        // Advance the storage past the GC window of the certificate.
+        storage.update_current_round(round + 2);
+        // Ensure the certificate was garbage collected from the storage and the storage service.
+        assert_storage(&storage, &[], &[], &[], &Default::default());
+        assert!(service.certificates().is_empty());
+    }
+
+    /// Checks that the certificates persisted in a storage service are restored after a restart,
+    /// where `open` reopens the storage service from the same location.
+    fn check_certificate_restart(open: impl Fn() -> Arc<dyn StorageService<CurrentNetwork>>, rng: &mut TestRng) {
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+        // Set the maximum number of GC rounds.
+        let max_gc_rounds = 10;
+
+        // Create a new certificate.
+        let certificate =
+            snarkvm::ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_for_round(5, rng);
+        let (certificate_id, round) = (certificate.id(), certificate.round());
+        // Construct the sample 'transmissions'.
+        let (missing_transmissions, _) = sample_transmissions(&certificate, rng);
+
+        // Insert the certificate, and shut down the storage.
+        {
+            let storage = Storage::<CurrentNetwork>::new(ledger.clone(), open(), max_gc_rounds);
+            storage.insert_certificate_atomic(certificate.clone(), missing_transmissions);
+            assert!(storage.contains_certificate(certificate_id));
+        }
+
+        // Reopen the storage (i.e. simulate a restart).
+        {
+            let storage = Storage::<CurrentNetwork>::new(ledger, open(), max_gc_rounds);
+            // Ensure the storage resumed at the round of the certificate.
+            assert_eq!(storage.current_round(), round);
+            // Ensure the certificate and its transmissions were restored.
+            assert_eq!(storage.get_certificate(certificate_id), Some(certificate.clone()));
+            assert_eq!(storage.get_round_for_batch(certificate.batch_id()), Some(round));
+            assert_eq!(storage.get_certificates_above_gc_round(), vec![certificate.clone()]);
+            for transmission_id in certificate.transmission_ids() {
+                assert!(storage.contains_transmission(*transmission_id));
+            }
+        }
+
+        // Reopen the storage with a ledger that has advanced past the GC window of the certificate.
+        let ledger =
+            Arc::new(MockLedgerService::new_at_height(committee, u32::try_from(round + max_gc_rounds).unwrap()));
+        let service = open();
+        let storage = Storage::<CurrentNetwork>::new(ledger, service.clone(), max_gc_rounds);
+        // Ensure the stale certificate was not restored, and was removed from the storage service.
+        assert!(!storage.contains_certificate(certificate_id));
+        assert!(service.certificates().is_empty());
+        for transmission_id in certificate.transmission_ids() {
+            assert!(!storage.contains_transmission(*transmission_id));
+        }
+    }
+
+    #[test]
+    fn test_certificate_restart_file() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+        check_certificate_restart(
+            || Arc::new(snarkos_node_bft_storage_service::BFTFileStorage::open(temp_dir.path()).unwrap()),
+            rng,
+        );
+    }
+
+    #[test]
+    fn test_certificate_restart_rocksdb() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage_mode = aleo_std::StorageMode::Custom(temp_dir.path().to_owned());
+        check_certificate_restart(
+            || Arc::new(snarkos_node_bft_storage_service::BFTPersistentStorage::open(storage_mode.clone()).unwrap()),
+            rng,
+        );
+    }
+
+    #[test]
+    fn test_signed_batches() {
+        let rng = &mut TestRng::default();
+
+        // Sample a committee.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        // Initialize the ledger.
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        // Initialize the storage service.
+        let service: Arc<dyn StorageService<CurrentNetwork>> = Arc::new(BFTMemoryService::new());
+        // Initialize the storage.
+        let storage = Storage::<CurrentNetwork>::new(ledger, service.clone(), 1);
+
+        // Sample a batch header.
+        let batch_header = snarkvm::ledger::narwhal::batch_header::test_helpers::sample_batch_header_for_round(5, rng);
+        let (round, author, batch_id) = (batch_header.round(), batch_header.author(), batch_header.batch_id());
+
+        // Record the batch header as signed.
+        assert!(storage.insert_signed_batch(&batch_header).is_ok());
+        // Ensure the same batch header can be signed again.
+        assert!(storage.insert_signed_batch(&batch_header).is_ok());
+        // Ensure a different batch header for the same round and author is refused.
+        assert!(service.insert_signed_batch(round, author, Field::from_u64(1)).is_err());
+        assert_eq!(service.signed_batches(), vec![(round, author, batch_id)]);
+
+        // Export the signing history, and import it into a new storage service.
+        let history = SigningHistory::export(&*service);
+        let other_service = BFTMemoryService::<CurrentNetwork>::new();
+        assert_eq!(history.import(&other_service).unwrap(), 1);
+        assert_eq!(other_service.signed_batches(), vec![(round, author, batch_id)]);
+        // Ensure the imported signing history refuses a different batch header.
+        assert!(other_service.insert_signed_batch(round, author, Field::from_u64(1)).is_err());
+
+        // Sign a later batch header from the same author.
+        service.insert_signed_batch(round + 1, author, Field::from_u64(2)).unwrap();
+        // Advance the storage past the GC window of both batch headers.
+        storage.update_current_round(round + 4);
+        // Ensure only the highest signed round of the author outlives garbage collection.
+        assert_eq!(service.signed_batches(), vec![(round + 1, author, Field::from_u64(2))]);
+        // Ensure a different batch header for the highest signed round is still refused.
+        assert!(service.insert_signed_batch(round + 1, author, Field::from_u64(3)).is_err());
+    }
+
     #[test]
     fn test_certificate_duplicate() {
         let rng = &mut TestRng::default();
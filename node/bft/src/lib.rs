@@ -29,6 +29,9 @@ pub mod helpers;
 mod bft;
 pub use bft::*;
 
+mod config;
+pub use config::*;
+
 mod gateway;
 pub use gateway::*;
 
@@ -46,24 +49,24 @@ pub const CONTEXT: &str = "[MemoryPool]";
 /// The port on which the memory pool listens for incoming connections.
 pub const MEMORY_POOL_PORT: u16 = 5000; // port
 
-/// The maximum number of milliseconds to wait before proposing a batch.
+/// The default maximum number of milliseconds to wait before proposing a batch.
 pub const MAX_BATCH_DELAY_IN_MS: u64 = 2500; // ms
-/// The maximum number of rounds to store before garbage collecting.
+/// The default maximum number of rounds to store before garbage collecting.
 pub const MAX_GC_ROUNDS: u64 = 50; // rounds
-/// The maximum number of seconds allowed for the leader to send their certificate.
+/// The default maximum number of seconds allowed for the leader to send their certificate.
 pub const MAX_LEADER_CERTIFICATE_DELAY_IN_SECS: i64 = 2 * MAX_BATCH_DELAY_IN_MS as i64 / 1000; // seconds
 /// The maximum number of seconds before the timestamp is considered expired.
 pub const MAX_TIMESTAMP_DELTA_IN_SECS: i64 = 10; // seconds
-/// The maximum number of transmissions allowed in a batch.
+/// The default maximum number of transmissions allowed in a batch.
 pub const MAX_TRANSMISSIONS_PER_BATCH: usize = 250; // transmissions
-/// The maximum number of transmissions allowed in a worker ping.
+/// The default maximum number of transmissions allowed in a worker ping.
 pub const MAX_TRANSMISSIONS_PER_WORKER_PING: usize = MAX_TRANSMISSIONS_PER_BATCH / 10; // transmissions
-/// The maximum number of workers that can be spawned.
+/// The default maximum number of workers that can be spawned.
 pub const MAX_WORKERS: u8 = 1; // workers
 
-/// The frequency at which each primary broadcasts a ping to every other node.
+/// The default frequency at which each primary broadcasts a ping to every other node.
 pub const PRIMARY_PING_IN_MS: u64 = 4 * MAX_BATCH_DELAY_IN_MS; // ms
-/// The frequency at which each worker broadcasts a ping to every other node.
+/// The default frequency at which each worker broadcasts a ping to every other node.
 pub const WORKER_PING_IN_MS: u64 = 4 * MAX_BATCH_DELAY_IN_MS; // ms
 
 /// A helper macro to spawn a blocking task.
@@ -25,18 +25,16 @@ use crate::{
         PrimaryReceiver,
         PrimarySender,
         Proposal,
+        ProposalJournal,
+        QueueState,
         Storage,
     },
     spawn_blocking,
+    BFTConfig,
     Gateway,
     Sync,
     Transport,
     Worker,
-    MAX_BATCH_DELAY_IN_MS,
-    MAX_TRANSMISSIONS_PER_BATCH,
-    MAX_WORKERS,
-    PRIMARY_PING_IN_MS,
-    WORKER_PING_IN_MS,
 };
 use snarkos_account::Account;
 use snarkos_node_bft_events::PrimaryPing;
@@ -94,13 +92,14 @@ pub struct Primary<N: Network> {
     signed_proposals: Arc<RwLock<HashMap<Address<N>, (u64, Field<N>, Signature<N>)>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
-    /// The lock for propose_batch.
+    /// The lock for propose_batch (this holds the latest round for which the primary proposed a batch).
     propose_lock: Arc<TMutex<u64>>,
 }
 
 impl<N: Network> Primary<N> {
     /// Initializes a new primary instance.
     pub fn new(
+        config: BFTConfig,
         account: Account<N>,
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
@@ -109,9 +108,12 @@ impl<N: Network> Primary<N> {
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the gateway.
-        let gateway = Gateway::new(account, ledger.clone(), ip, trusted_validators, dev)?;
+        let gateway = Gateway::new(config, account, ledger.clone(), ip, trusted_validators, dev)?;
         // Initialize the sync module.
         let sync = Sync::new(gateway.clone(), storage.clone(), ledger.clone());
+        // Restore the latest proposed round and the proposed batch from the proposal journal.
+        let (latest_proposed_round, proposed_batch) =
+            Self::restore_proposal_journal(&storage, &ledger, gateway.account().address())?;
         // Initialize the primary instance.
         Ok(Self {
             sync,
@@ -120,13 +122,69 @@ impl<N: Network> Primary<N> {
             ledger,
             workers: Arc::from(vec![]),
             bft_sender: Default::default(),
-            proposed_batch: Default::default(),
+            proposed_batch: Arc::new(RwLock::new(proposed_batch)),
             signed_proposals: Default::default(),
             handles: Default::default(),
-            propose_lock: Default::default(),
+            propose_lock: Arc::new(TMutex::new(latest_proposed_round)),
         })
     }
 
+    /// Returns the latest proposed round and the proposed batch (with its signatures) from the proposal journal in storage.
+    /// If there is no proposal journal in storage, the default (empty) state is returned.
+    fn restore_proposal_journal(
+        storage: &Storage<N>,
+        ledger: &Arc<dyn LedgerService<N>>,
+        address: Address<N>,
+    ) -> Result<(u64, Option<Proposal<N>>)> {
+        // Retrieve the proposal journal from storage.
+        let Some(journal) = storage.get_proposal_journal() else {
+            return Ok(Default::default());
+        };
+        // Deserialize the proposal journal.
+        // Note: If the journal is corrupt, the primary must not start, as it could otherwise equivocate.
+        let (latest_proposed_round, mut proposed_batch) = match ProposalJournal::from_bytes_le(&journal) {
+            Ok(journal) => journal.into_parts(),
+            Err(e) => bail!("Failed to restore the proposal journal from storage - {e}"),
+        };
+        // Ensure the proposed batch was authored by this primary.
+        if let Some(proposal) = &mut proposed_batch {
+            if proposal.batch_header().author() != address {
+                warn!("Discarding the proposal journal, as it belongs to a different validator");
+                return Ok(Default::default());
+            }
+            // Restore the signatures received for the proposed batch.
+            let signatures = storage.get_proposal_signatures(proposal.batch_id());
+            if !signatures.is_empty() {
+                match ledger.get_previous_committee_for_round(proposal.round()) {
+                    Ok(previous_committee) => {
+                        for signature in signatures {
+                            let signer = signature.to_address();
+                            if let Err(e) = proposal.add_signature(signer, signature, &previous_committee) {
+                                warn!("Discarding a signature from the proposal journal - {e}");
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Discarding the signatures from the proposal journal - {e}"),
+                }
+            }
+            info!("Restored the proposed batch for round {} from the proposal journal", proposal.round());
+        }
+        Ok((latest_proposed_round, proposed_batch))
+    }
+
+    /// Writes the given latest proposed round and proposed batch to the proposal journal in storage.
+    ///
+    /// This method must be called **before** the primary broadcasts a batch proposal,
+    /// to ensure the primary does not equivocate if it restarts.
+    ///
+    /// Note: The caller must hold the `proposed_batch` write lock, to ensure the journal writes are not interleaved.
+    fn write_proposal_journal(&self, latest_proposed_round: u64, proposal: Option<&Proposal<N>>) -> Result<()> {
+        // Construct the proposal journal.
+        let journal = ProposalJournal::new(latest_proposed_round, proposal.cloned());
+        // Store the proposal journal.
+        self.storage.store_proposal_journal(journal.to_bytes_le()?)
+    }
+
     /// Run the primary instance.
     pub async fn run(
         &mut self,
@@ -147,11 +205,12 @@ impl<N: Network> Primary<N> {
         // Construct a map for the workers.
         let mut workers = Vec::new();
         // Initialize the workers.
-        for id in 0..MAX_WORKERS {
+        for id in 0..self.config().max_workers {
             // Construct the worker channels.
             let (tx_worker, rx_worker) = init_worker_channels();
             // Construct the worker instance.
             let worker = Worker::new(
+                self.config().clone(),
                 id,
                 Arc::new(self.gateway.clone()),
                 self.storage.clone(),
@@ -186,6 +245,11 @@ impl<N: Network> Primary<N> {
         self.storage.current_round()
     }
 
+    /// Returns the BFT parameters.
+    pub const fn config(&self) -> &BFTConfig {
+        self.gateway.config()
+    }
+
     /// Returns the gateway.
     pub const fn gateway(&self) -> &Gateway<N> {
         &self.gateway
@@ -237,6 +301,19 @@ impl<N: Network> Primary<N> {
     pub fn num_unconfirmed_transactions(&self) -> usize {
         self.workers.iter().map(|worker| worker.num_transactions()).sum()
     }
+
+    /// Returns the combined state of the ready queues of the workers.
+    pub fn ready_queue_state(&self) -> QueueState {
+        self.workers.iter().map(|worker| worker.ready_queue_state()).fold(QueueState::default(), |total, state| {
+            QueueState {
+                num_entries: total.num_entries + state.num_entries,
+                max_entries: total.max_entries + state.max_entries,
+                num_bytes: total.num_bytes.saturating_add(state.num_bytes),
+                max_bytes: total.max_bytes.saturating_add(state.max_bytes),
+                num_dropped: total.num_dropped + state.num_dropped,
+            }
+        })
+    }
 }
 
 impl<N: Network> Primary<N> {
@@ -381,8 +458,16 @@ impl<N: Network> Primary<N> {
             return Ok(());
         }
 
+        // If the batch had already been proposed, return early, before the transmissions are taken from the workers.
+        // Note: The proposed round is restored from the proposal journal, so this also holds across restarts.
+        ensure!(round > 0, "Round 0 cannot have transaction batches");
+        if round <= *lock_guard {
+            warn!("Primary is safely skipping a batch proposal - round {round} already proposed");
+            return Ok(());
+        }
+
         // Determined the required number of transmissions per worker.
-        let num_transmissions_per_worker = MAX_TRANSMISSIONS_PER_BATCH / self.num_workers() as usize;
+        let num_transmissions_per_worker = self.config().max_transmissions_per_batch / self.num_workers() as usize;
         // Initialize the map of transmissions.
         let mut transmissions: IndexMap<_, _> = Default::default();
         // Initialize a tracker for the number of transactions.
@@ -433,13 +518,6 @@ impl<N: Network> Primary<N> {
             debug!("Primary is safely skipping a batch proposal {}", "(no unconfirmed transactions)".dimmed());
             return Ok(());
         }
-        // Ditto if the batch had already been proposed.
-        ensure!(round > 0, "Round 0 cannot have transaction batches");
-        if *lock_guard == round {
-            warn!("Primary is safely skipping a batch proposal - round {round} already proposed");
-            return Ok(());
-        }
-
         *lock_guard = round;
 
         /* Proceeding to sign & propose the batch. */
@@ -469,10 +547,26 @@ impl<N: Network> Primary<N> {
         // Construct the proposal.
         let proposal =
             Proposal::new(self.ledger.get_previous_committee_for_round(round)?, batch_header.clone(), transmissions)?;
+        let journaled = {
+            // Acquire the write lock.
+            let mut proposed_batch = self.proposed_batch.write();
+            // Write the proposal journal, before broadcasting the batch.
+            match self.write_proposal_journal(round, Some(&proposal)) {
+                Ok(()) => {
+                    // Set the proposed batch.
+                    *proposed_batch = Some(proposal);
+                    Ok(())
+                }
+                Err(e) => Err((proposal, e)),
+            }
+        };
+        if let Err((proposal, e)) = journaled {
+            // Reinsert the transmissions back into the ready queue.
+            self.reinsert_transmissions_into_workers(proposal).await?;
+            bail!("Failed to write the proposal journal for round {round} - {e}");
+        }
         // Broadcast the batch to all validators for signing.
         self.gateway.broadcast(Event::BatchPropose(batch_header.into()));
-        // Set the proposed batch.
-        *self.proposed_batch.write() = Some(proposal);
         Ok(())
     }
 
@@ -561,10 +655,17 @@ impl<N: Network> Primary<N> {
         // Ensure the batch header from the peer is valid.
         let missing_transmissions = self.storage.check_batch_header(&batch_header, transmissions)?;
         // Inserts the missing transmissions into the workers.
-        self.insert_missing_transmissions_into_workers(peer_ip, missing_transmissions.into_iter())?;
+        self.insert_missing_transmissions_into_workers(peer_ip, missing_transmissions.into_iter()).await?;
 
         /* Proceeding to sign the batch. */
 
+        // Record the batch in the signing history, before signing it.
+        // Note: This refuses to sign a different batch for the same round and author, including across restarts.
+        // The signature itself is only cached in memory, as signing the same batch again after a restart is safe.
+        if let Err(e) = self.storage.insert_signed_batch(&batch_header) {
+            bail!("Refusing to sign a batch in round {batch_round} from '{peer_ip}' - {e}");
+        }
+
         // Retrieve the batch ID.
         let batch_id = batch_header.batch_id();
         // Sign the batch ID.
@@ -668,7 +769,11 @@ impl<N: Network> Primary<N> {
                     info!("Received a batch signature for round {} from '{peer_ip}'", proposal.round());
                     // Check if the batch is ready to be certified.
                     if !proposal.is_quorum_threshold_reached(&previous_committee) {
-                        // If the batch is not ready to be certified, return early.
+                        // If the batch is not ready to be certified, record the signature for the proposal journal.
+                        if let Err(e) = self.storage.insert_proposal_signature(batch_id, signature) {
+                            warn!("Failed to write the batch signature to the proposal journal - {e}");
+                        }
+                        // Return early.
                         return Ok(());
                     }
                 }
@@ -676,10 +781,15 @@ impl<N: Network> Primary<N> {
                 None => return Ok(()),
             };
             // Retrieve the batch proposal, clearing the proposed batch.
-            match proposed_batch.take() {
-                Some(proposal) => proposal,
-                None => return Ok(()),
+            let Some(proposal) = proposed_batch.take() else {
+                return Ok(());
+            };
+            // Clear the batch from the proposal journal.
+            // Note: The latest proposed round is kept, so the primary does not propose for this round again.
+            if let Err(e) = self.write_proposal_journal(proposal.round(), None) {
+                warn!("Failed to clear the certified batch from the proposal journal - {e}");
             }
+            proposal
         };
 
         /* Proceeding to certify the batch. */
@@ -692,7 +802,7 @@ impl<N: Network> Primary<N> {
         // If there was an error storing the certificate, reinsert the transmissions back into the ready queue.
         if let Err(e) = self.store_and_broadcast_certificate(&proposal, &previous_committee).await {
             // Reinsert the transmissions back into the ready queue for the next proposal.
-            self.reinsert_transmissions_into_workers(proposal)?;
+            self.reinsert_transmissions_into_workers(proposal).await?;
             return Err(e);
         }
         Ok(())
@@ -820,7 +930,7 @@ impl<N: Network> Primary<N> {
             self.spawn(async move {
                 loop {
                     // Sleep briefly.
-                    tokio::time::sleep(Duration::from_millis(PRIMARY_PING_IN_MS)).await;
+                    tokio::time::sleep(Duration::from_millis(self_.config().primary_ping_in_ms)).await;
 
                     // Retrieve the block locators.
                     let block_locators = match self_.sync.get_block_locators() {
@@ -955,7 +1065,7 @@ impl<N: Network> Primary<N> {
             let self_ = self.clone();
             self.spawn(async move {
                 loop {
-                    tokio::time::sleep(Duration::from_millis(WORKER_PING_IN_MS)).await;
+                    tokio::time::sleep(Duration::from_millis(self_.config().worker_ping_in_ms)).await;
                     // If the primary is not synced, then do not broadcast the worker ping(s).
                     if !self_.sync.is_synced() {
                         trace!("Skipping worker ping(s) {}", "(node is syncing)".dimmed());
@@ -974,7 +1084,7 @@ impl<N: Network> Primary<N> {
         self.spawn(async move {
             loop {
                 // Sleep briefly, but longer than if there were no batch.
-                tokio::time::sleep(Duration::from_millis(MAX_BATCH_DELAY_IN_MS)).await;
+                tokio::time::sleep(Duration::from_millis(self_.config().max_batch_delay_in_ms)).await;
                 // If the primary is not synced, then do not propose a batch.
                 if !self_.sync.is_synced() {
                     debug!("Skipping batch proposal {}", "(node is syncing)".dimmed());
@@ -1108,9 +1218,19 @@ impl<N: Network> Primary<N> {
         // If the batch is expired, clear the proposed batch.
         if is_expired {
             // Reset the proposed batch.
-            let proposal = self.proposed_batch.write().take();
+            let proposal = {
+                let mut proposed_batch = self.proposed_batch.write();
+                let proposal = proposed_batch.take();
+                // Clear the expired batch from the proposal journal, so it is not restored on restart.
+                if let Some(proposal) = &proposal {
+                    if let Err(e) = self.write_proposal_journal(proposal.round(), None) {
+                        warn!("Failed to clear the expired batch from the proposal journal - {e}");
+                    }
+                }
+                proposal
+            };
             if let Some(proposal) = proposal {
-                self.reinsert_transmissions_into_workers(proposal)?;
+                self.reinsert_transmissions_into_workers(proposal).await?;
             }
         }
         Ok(())
@@ -1125,8 +1245,13 @@ impl<N: Network> Primary<N> {
             while fast_forward_round < next_round.saturating_sub(1) {
                 // Update to the next round in storage.
                 fast_forward_round = self.storage.increment_to_next_round(fast_forward_round)?;
-                // Clear the proposed batch.
-                *self.proposed_batch.write() = None;
+                // Clear the proposed batch, along with its entry in the proposal journal.
+                let mut proposed_batch = self.proposed_batch.write();
+                if let Some(proposal) = proposed_batch.take() {
+                    if let Err(e) = self.write_proposal_journal(proposal.round(), None) {
+                        warn!("Failed to clear the stale batch from the proposal journal - {e}");
+                    }
+                }
             }
         }
 
@@ -1220,27 +1345,39 @@ impl<N: Network> Primary<N> {
     }
 
     /// Inserts the missing transmissions from the proposal into the workers.
-    fn insert_missing_transmissions_into_workers(
+    async fn insert_missing_transmissions_into_workers(
         &self,
         peer_ip: SocketAddr,
         transmissions: impl Iterator<Item = (TransmissionID<N>, Transmission<N>)>,
     ) -> Result<()> {
-        // Insert the transmissions into the workers.
+        // Assign the transmissions to the workers.
+        let mut assignments = Vec::new();
         assign_to_workers(&self.workers, transmissions, |worker, transmission_id, transmission| {
-            worker.process_transmission_from_peer(peer_ip, transmission_id, transmission);
-        })
+            assignments.push((worker.clone(), transmission_id, transmission));
+        })?;
+        // Insert the transmissions into the workers.
+        for (worker, transmission_id, transmission) in assignments {
+            worker.process_transmission_from_peer(peer_ip, transmission_id, transmission).await;
+        }
+        Ok(())
     }
 
     /// Re-inserts the transmissions from the proposal into the workers.
-    fn reinsert_transmissions_into_workers(&self, proposal: Proposal<N>) -> Result<()> {
-        // Re-insert the transmissions into the workers.
+    async fn reinsert_transmissions_into_workers(&self, proposal: Proposal<N>) -> Result<()> {
+        // Assign the transmissions to the workers.
+        let mut assignments = Vec::new();
         assign_to_workers(
             &self.workers,
             proposal.into_transmissions().into_iter(),
             |worker, transmission_id, transmission| {
-                worker.reinsert(transmission_id, transmission);
+                assignments.push((worker.clone(), transmission_id, transmission));
             },
-        )
+        )?;
+        // Re-insert the transmissions into the workers.
+        for (worker, transmission_id, transmission) in assignments {
+            worker.reinsert(transmission_id, transmission).await;
+        }
+        Ok(())
     }
 
     /// Recursively stores a given batch certificate, after ensuring:
@@ -1571,10 +1708,11 @@ mod tests {
         let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
 
         // Initialize the primary.
-        let mut primary = Primary::new(account, storage, ledger, None, &[], None).unwrap();
+        let mut primary = Primary::new(BFTConfig::default(), account, storage, ledger, None, &[], None).unwrap();
 
         // Construct a worker instance.
         primary.workers = Arc::from([Worker::new(
+            BFTConfig::default(),
             0, // id
             Arc::new(primary.gateway.clone()),
             primary.storage.clone(),
@@ -1848,7 +1986,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await;
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -1860,6 +1998,158 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_proposal_journal_restart() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Generate a solution and a transaction, and store them on one of the workers.
+        let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Propose a batch.
+        assert!(primary.propose_batch().await.is_ok());
+        let proposal = primary.proposed_batch.read().clone().unwrap();
+
+        // Create a valid proposal with an author that isn't the primary, and sign it.
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let peer_proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            proposal.round(),
+            Default::default(),
+            now(),
+            &mut rng,
+        );
+        for (transmission_id, transmission) in peer_proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await;
+        }
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+        assert!(
+            primary
+                .process_batch_propose_from_peer(peer_ip, (*peer_proposal.batch_header()).clone().into())
+                .await
+                .is_ok()
+        );
+        assert!(primary.signed_proposals.read().contains_key(&peer_account.1.address()));
+
+        // Initialize a new primary from the same storage (i.e. simulate a restart).
+        let account = accounts[0].1.clone();
+        let restarted = Primary::new(
+            BFTConfig::default(),
+            account,
+            primary.storage.clone(),
+            primary.ledger.clone(),
+            None,
+            &[],
+            None,
+        )
+        .unwrap();
+
+        // Ensure the proposed batch and the proposed round were restored.
+        assert!(*restarted.proposed_batch.read() == Some(proposal.clone()));
+        assert_eq!(*restarted.propose_lock.lock().await, proposal.round());
+
+        // Ensure the signing history still refuses a different batch from the peer for the same round,
+        // while the same batch may be signed again.
+        let other_peer_proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            proposal.round(),
+            Default::default(),
+            now(),
+            &mut rng,
+        );
+        assert!(restarted.signed_proposals.read().is_empty());
+        assert!(restarted.storage.insert_signed_batch(peer_proposal.batch_header()).is_ok());
+        assert!(restarted.storage.insert_signed_batch(other_peer_proposal.batch_header()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proposal_journal_signatures_restart() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+        map_account_addresses(&primary, &accounts);
+
+        // Generate a solution and a transaction, and store them on one of the workers.
+        let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Propose a batch.
+        assert!(primary.propose_batch().await.is_ok());
+        let journal = primary.storage.get_proposal_journal().unwrap();
+
+        // Have the primary process only one signature, mimicking a lack of quorum.
+        let signatures = peer_signatures_for_proposal(&primary, &accounts, &mut rng);
+        let (socket_addr, signature) = signatures.first().unwrap();
+        primary.process_batch_signature_from_peer(*socket_addr, *signature).await.unwrap();
+        let proposal = primary.proposed_batch.read().clone().unwrap();
+        assert_eq!(proposal.signers().len(), 1);
+
+        // Ensure the signature was recorded without rewriting the proposal journal.
+        assert_eq!(primary.storage.get_proposal_journal().unwrap(), journal);
+        assert_eq!(primary.storage.get_proposal_signatures(proposal.batch_id()), vec![signature.signature]);
+
+        // Initialize a new primary from the same storage (i.e. simulate a restart).
+        let account = accounts[0].1.clone();
+        let restarted = Primary::new(
+            BFTConfig::default(),
+            account,
+            primary.storage.clone(),
+            primary.ledger.clone(),
+            None,
+            &[],
+            None,
+        )
+        .unwrap();
+
+        // Ensure the proposed batch was restored with its signature.
+        assert!(*restarted.proposed_batch.read() == Some(proposal));
+    }
+
+    #[tokio::test]
+    async fn test_proposal_journal_expired_batch() {
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Generate a solution and a transaction, and store them on one of the workers.
+        let (solution_commitment, solution) = sample_unconfirmed_solution(&mut rng);
+        let (transaction_id, transaction) = sample_unconfirmed_transaction(&mut rng);
+        primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
+        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+
+        // Propose a batch.
+        assert!(primary.propose_batch().await.is_ok());
+        let proposal = primary.proposed_batch.read().clone().unwrap();
+
+        // Advance to the next round, which expires the proposed batch.
+        primary.storage.increment_to_next_round(proposal.round()).unwrap();
+        primary.check_proposed_batch_for_expiration().await.unwrap();
+        assert!(primary.proposed_batch.read().is_none());
+
+        // Initialize a new primary from the same storage (i.e. simulate a restart).
+        let account = accounts[0].1.clone();
+        let restarted = Primary::new(
+            BFTConfig::default(),
+            account,
+            primary.storage.clone(),
+            primary.ledger.clone(),
+            None,
+            &[],
+            None,
+        )
+        .unwrap();
+
+        // Ensure the expired batch was not restored, but the proposed round was.
+        assert!(restarted.proposed_batch.read().is_none());
+        assert_eq!(*restarted.propose_lock.lock().await, proposal.round());
+    }
+
     #[tokio::test]
     async fn test_batch_propose_from_peer_in_round() {
         let round = 2;
@@ -1884,7 +2174,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await;
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -1915,7 +2205,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await;
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -1957,7 +2247,7 @@ mod tests {
 
         // Make sure the primary is aware of the transmissions in the proposal.
         for (transmission_id, transmission) in proposal.transmissions() {
-            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone()).await;
         }
 
         // The author must be known to resolver to pass propose checks.
@@ -16,8 +16,6 @@ use crate::{
     helpers::{BFTSender, Pending, Storage, SyncReceiver},
     Gateway,
     Transport,
-    MAX_BATCH_DELAY_IN_MS,
-    PRIMARY_PING_IN_MS,
 };
 use snarkos_node_bft_events::{CertificateRequest, CertificateResponse, Event};
 use snarkos_node_bft_ledger_service::LedgerService;
@@ -92,7 +90,7 @@ impl<N: Network> Sync<N> {
         self.handles.lock().push(tokio::spawn(async move {
             loop {
                 // Sleep briefly to avoid triggering spam detection.
-                tokio::time::sleep(std::time::Duration::from_millis(PRIMARY_PING_IN_MS)).await;
+                tokio::time::sleep(std::time::Duration::from_millis(self_.gateway.config().primary_ping_in_ms)).await;
                 // Perform the sync routine.
                 let communication = &self_.gateway;
                 // let communication = &node.router;
@@ -228,9 +226,6 @@ impl<N: Network> Sync<N> {
                 }
             })
             .collect::<Vec<_>>();
-        if leader_certificates.is_empty() {
-            return Ok(());
-        }
 
         // Construct a list of the certificates.
         let certificates = blocks
@@ -246,7 +241,8 @@ impl<N: Network> Sync<N> {
             .flatten()
             .collect::<Vec<_>>();
 
-        // If a BFT sender was provided, send the certificate to the BFT.
+        // If a BFT sender was provided, send the certificates to the BFT.
+        // Note: This is sent even if there are no leader certificates, as the BFT restores its DAG from storage after syncing.
         if let Some(bft_sender) = self.bft_sender.get() {
             // Await the callback to continue.
             if let Err(e) = bft_sender.tx_sync_bft_dag_at_bootup.send((leader_certificates, certificates)).await {
@@ -341,7 +337,8 @@ impl<N: Network> Sync<N> {
             }
         }
         // Wait for the certificate to be fetched.
-        match tokio::time::timeout(core::time::Duration::from_millis(MAX_BATCH_DELAY_IN_MS), callback_receiver).await {
+        let timeout = core::time::Duration::from_millis(self.gateway.config().max_batch_delay_in_ms);
+        match tokio::time::timeout(timeout, callback_receiver).await {
             // If the certificate was fetched, return it.
             Ok(result) => Ok(result?),
             // If the certificate was not fetched, return an error.
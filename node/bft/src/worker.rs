@@ -14,13 +14,10 @@
 
 use crate::{
     events::{Event, TransmissionRequest, TransmissionResponse},
-    helpers::{fmt_id, Pending, Ready, Storage, WorkerReceiver},
+    helpers::{fmt_id, Pending, QueueState, Ready, Storage, WorkerReceiver},
+    BFTConfig,
     ProposedBatch,
     Transport,
-    MAX_BATCH_DELAY_IN_MS,
-    MAX_TRANSMISSIONS_PER_BATCH,
-    MAX_TRANSMISSIONS_PER_WORKER_PING,
-    MAX_WORKERS,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkvm::{
@@ -37,10 +34,10 @@ use parking_lot::Mutex;
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Duration};
 use tokio::{sync::oneshot, task::JoinHandle, time::timeout};
 
-const MAX_TRANSMISSIONS_PER_WORKER: usize = MAX_TRANSMISSIONS_PER_BATCH / MAX_WORKERS as usize;
-
 #[derive(Clone)]
 pub struct Worker<N: Network> {
+    /// The BFT parameters.
+    config: BFTConfig,
     /// The worker ID.
     id: u8,
     /// The gateway.
@@ -62,6 +59,7 @@ pub struct Worker<N: Network> {
 impl<N: Network> Worker<N> {
     /// Initializes a new worker instance.
     pub fn new(
+        config: BFTConfig,
         id: u8,
         gateway: Arc<dyn Transport<N>>,
         storage: Storage<N>,
@@ -69,15 +67,18 @@ impl<N: Network> Worker<N> {
         proposed_batch: Arc<ProposedBatch<N>>,
     ) -> Result<Self> {
         // Ensure the worker ID is valid.
-        ensure!(id < MAX_WORKERS, "Invalid worker ID '{id}'");
+        ensure!(id < config.max_workers, "Invalid worker ID '{id}'");
+        // Initialize the ready queue.
+        let ready = Ready::new(config.max_transmissions_per_batch);
         // Return the worker.
         Ok(Self {
+            config,
             id,
             gateway,
             storage,
             ledger,
             proposed_batch,
-            ready: Default::default(),
+            ready,
             pending: Default::default(),
             handles: Default::default(),
         })
@@ -97,6 +98,11 @@ impl<N: Network> Worker<N> {
 }
 
 impl<N: Network> Worker<N> {
+    /// Returns the state of the ready queue.
+    pub fn ready_queue_state(&self) -> QueueState {
+        self.ready.state()
+    }
+
     /// Returns the number of transmissions in the ready queue.
     pub fn num_transmissions(&self) -> usize {
         self.ready.num_transmissions()
@@ -191,17 +197,29 @@ impl<N: Network> Worker<N> {
         Ok((transmission_id, transmission))
     }
 
-    /// Removes up to the specified number of transmissions from the ready queue, and returns them.
+    /// Removes up to the specified number of transmissions from the ready queue, by priority, and returns them.
     pub(crate) fn drain(&self, num_transmissions: usize) -> impl Iterator<Item = (TransmissionID<N>, Transmission<N>)> {
         self.ready.drain(num_transmissions).into_iter()
     }
 
+    /// Removes the transmissions from the ready queue for which the given function returns `false`,
+    /// and returns the number of removed transmissions.
+    pub fn retain_ready(&self, keep: impl FnMut(&TransmissionID<N>, &Transmission<N>) -> bool) -> usize {
+        self.ready.retain(keep)
+    }
+
+    /// Removes the transmissions that entered the ready queue before the given timestamp (in seconds),
+    /// and returns the number of removed transmissions.
+    pub fn expire_ready(&self, cutoff: i64) -> usize {
+        self.ready.expire(cutoff)
+    }
+
     /// Reinserts the specified transmission into the ready queue.
-    pub(crate) fn reinsert(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> bool {
+    pub(crate) async fn reinsert(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> bool {
         // Check if the transmission ID exists.
         if !self.contains_transmission(transmission_id) {
             // Insert the transmission into the ready queue.
-            return self.ready.insert(transmission_id, transmission);
+            return self.ready.insert(transmission_id, transmission).await;
         }
         false
     }
@@ -209,8 +227,12 @@ impl<N: Network> Worker<N> {
     /// Broadcasts a worker ping event.
     pub(crate) fn broadcast_ping(&self) {
         // Retrieve the transmission IDs.
-        let transmission_ids =
-            self.ready.transmission_ids().into_iter().take(MAX_TRANSMISSIONS_PER_WORKER_PING).collect::<IndexSet<_>>();
+        let transmission_ids = self
+            .ready
+            .transmission_ids()
+            .into_iter()
+            .take(self.config.max_transmissions_per_worker_ping())
+            .collect::<IndexSet<_>>();
 
         // Broadcast the ping event.
         if !transmission_ids.is_empty() {
@@ -228,7 +250,7 @@ impl<N: Network> Worker<N> {
         }
         // If the ready queue is full, then skip this transmission.
         // Note: We must prioritize the unconfirmed solutions and unconfirmed transactions, not transmissions.
-        if self.ready.num_transmissions() > MAX_TRANSMISSIONS_PER_WORKER {
+        if self.ready.num_transmissions() > self.config.max_transmissions_per_worker() {
             return;
         }
         // Attempt to fetch the transmission from the peer.
@@ -243,7 +265,7 @@ impl<N: Network> Worker<N> {
                         // Insert the transmission into the ready queue.
                         // Note: This method checks `contains_transmission` again, because by the time the transmission is fetched,
                         // it could have already been inserted into the ready queue.
-                        self_.process_transmission_from_peer(peer_ip, transmission_id, transmission);
+                        self_.process_transmission_from_peer(peer_ip, transmission_id, transmission).await;
                     }
                 }
                 // If the transmission was not fetched, then attempt to fetch it again.
@@ -259,7 +281,7 @@ impl<N: Network> Worker<N> {
     }
 
     /// Handles the incoming transmission from a peer.
-    pub(crate) fn process_transmission_from_peer(
+    pub(crate) async fn process_transmission_from_peer(
         &self,
         peer_ip: SocketAddr,
         transmission_id: TransmissionID<N>,
@@ -280,7 +302,7 @@ impl<N: Network> Worker<N> {
             _ => false,
         };
         // If the transmission ID and transmission type matches, then insert the transmission into the ready queue.
-        if is_well_formed && self.ready.insert(transmission_id, transmission) {
+        if is_well_formed && self.ready.insert(transmission_id, transmission).await {
             trace!("Worker {} - Added transmission '{}' from '{peer_ip}'", self.id, fmt_id(transmission_id));
         }
     }
@@ -305,7 +327,7 @@ impl<N: Network> Worker<N> {
             bail!("Invalid unconfirmed solution '{}': {e}", fmt_id(puzzle_commitment));
         }
         // Adds the prover solution to the ready queue.
-        if self.ready.insert(puzzle_commitment, transmission) {
+        if self.ready.insert(puzzle_commitment, transmission).await {
             trace!("Worker {} - Added unconfirmed solution '{}'", self.id, fmt_id(puzzle_commitment));
         }
         Ok(())
@@ -330,7 +352,7 @@ impl<N: Network> Worker<N> {
             bail!("Invalid unconfirmed transaction '{}': {e}", fmt_id(transaction_id));
         }
         // Adds the transaction to the ready queue.
-        if self.ready.insert(&transaction_id, transmission) {
+        if self.ready.insert(&transaction_id, transmission).await {
             trace!("Worker {} - Added unconfirmed transaction '{}'", self.id, fmt_id(transaction_id));
         }
         Ok(())
@@ -383,7 +405,7 @@ impl<N: Network> Worker<N> {
             bail!("Unable to fetch transmission - failed to send request")
         }
         // Wait for the transmission to be fetched.
-        match timeout(Duration::from_millis(MAX_BATCH_DELAY_IN_MS), callback_receiver).await {
+        match timeout(Duration::from_millis(self.config.max_batch_delay_in_ms), callback_receiver).await {
             // If the transmission was fetched, return it.
             Ok(result) => Ok((transmission_id, result?)),
             // If the transmission was not fetched, return an error.
@@ -439,7 +461,7 @@ impl<N: Network> Worker<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snarkos_node_bft_ledger_service::LedgerService;
+    use snarkos_node_bft_ledger_service::{AdvanceCallback, LedgerService};
     use snarkos_node_bft_storage_service::BFTMemoryService;
     use snarkvm::{
         console::{network::Network, types::Field},
@@ -509,6 +531,7 @@ mod tests {
                 transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
             ) -> Result<Block<N>>;
             fn advance_to_next_block(&self, block: &Block<N>) -> Result<()>;
+            fn on_advance(&self, callback: AdvanceCallback<N>);
         }
     }
 
@@ -528,14 +551,15 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
         let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
         let transmission = Transmission::Solution(data(rng));
 
         // Process the transmission.
-        worker.process_transmission_from_peer(peer_ip, transmission_id, transmission.clone());
+        worker.process_transmission_from_peer(peer_ip, transmission_id, transmission.clone()).await;
         assert!(worker.contains_transmission(transmission_id));
         assert!(worker.ready.contains(transmission_id));
         assert_eq!(worker.get_transmission(transmission_id), Some(transmission));
@@ -564,7 +588,8 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let transmission_id = TransmissionID::Solution(PuzzleCommitment::from_g1_affine(rng.gen()));
         let worker_ = worker.clone();
         let peer_ip = SocketAddr::from(([127, 0, 0, 1], 1234));
@@ -600,7 +625,8 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let puzzle = PuzzleCommitment::from_g1_affine(rng.gen());
         let transmission_id = TransmissionID::Solution(puzzle);
         let worker_ = worker.clone();
@@ -638,7 +664,8 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let puzzle = PuzzleCommitment::from_g1_affine(rng.gen());
         let transmission_id = TransmissionID::Solution(puzzle);
         let worker_ = worker.clone();
@@ -676,7 +703,8 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let transaction_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(&mut rng).into();
         let transmission_id = TransmissionID::Transaction(transaction_id);
         let worker_ = worker.clone();
@@ -714,7 +742,8 @@ mod tests {
         let storage = Storage::<CurrentNetwork>::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
 
         // Create the Worker.
-        let worker = Worker::new(0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), 0, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         let transaction_id: <CurrentNetwork as Network>::TransactionID = Field::<CurrentNetwork>::rand(&mut rng).into();
         let transmission_id = TransmissionID::Transaction(transaction_id);
         let worker_ = worker.clone();
@@ -736,7 +765,7 @@ mod tests {
 #[cfg(test)]
 mod prop_tests {
     use super::*;
-    use crate::Gateway;
+    use crate::{Gateway, MAX_WORKERS};
     use snarkos_node_bft_ledger_service::MockLedgerService;
     use snarkvm::{
         console::account::Address,
@@ -769,7 +798,8 @@ mod prop_tests {
     ) {
         let committee = new_test_committee(4);
         let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(MockLedgerService::new(committee));
-        let worker = Worker::new(id, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
+        let worker =
+            Worker::new(BFTConfig::default(), id, Arc::new(gateway), storage, ledger, Default::default()).unwrap();
         assert_eq!(worker.id(), id);
     }
 
@@ -781,7 +811,7 @@ mod prop_tests {
     ) {
         let committee = new_test_committee(4);
         let ledger: Arc<dyn LedgerService<CurrentNetwork>> = Arc::new(MockLedgerService::new(committee));
-        let worker = Worker::new(id, Arc::new(gateway), storage, ledger, Default::default());
+        let worker = Worker::new(BFTConfig::default(), id, Arc::new(gateway), storage, ledger, Default::default());
         // TODO once Worker implements Debug, simplify this with `unwrap_err`
         if let Err(error) = worker {
             assert_eq!(error.to_string(), format!("Invalid worker ID '{}'", id));
@@ -0,0 +1,54 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{bail, Error};
+
+use std::{fmt, str::FromStr};
+
+/// The backend of the BFT storage service.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StorageBackend {
+    /// The in-memory storage service, which does not persist across restarts.
+    Memory,
+    /// The RocksDB storage service.
+    #[default]
+    RocksDB,
+    /// The append-only segmented file storage service.
+    File,
+}
+
+impl FromStr for StorageBackend {
+    type Err = Error;
+
+    /// Parses the storage backend from a string.
+    fn from_str(backend: &str) -> Result<Self, Self::Err> {
+        match backend {
+            "memory" => Ok(Self::Memory),
+            "rocksdb" => Ok(Self::RocksDB),
+            "file" => Ok(Self::File),
+            _ => bail!("Invalid storage backend '{backend}' (expected 'memory', 'rocksdb', or 'file')"),
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    /// Formats the storage backend as a string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Memory => write!(f, "memory"),
+            Self::RocksDB => write!(f, "rocksdb"),
+            Self::File => write!(f, "file"),
+        }
+    }
+}
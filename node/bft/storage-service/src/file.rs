@@ -0,0 +1,807 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{BFTMemoryService, StorageService};
+use snarkvm::{
+    ledger::narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
+    prelude::{
+        anyhow,
+        bail,
+        ensure,
+        Address,
+        Field,
+        FromBytes,
+        IoResult,
+        Network,
+        Read,
+        Result,
+        Signature,
+        ToBytes,
+        Write,
+    },
+};
+
+use indexmap::{indexset, IndexSet};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+use tracing::{error, warn};
+
+/// The size of a segment, after which a new segment is started.
+const MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024; // 64 MiB
+/// The number of segments, after which the segments are compacted into a single snapshot segment.
+const MAX_SEGMENTS: usize = 8;
+/// The maximum size of a record payload.
+const MAX_RECORD_SIZE: usize = 256 * 1024 * 1024; // 256 MiB
+/// The size of a record header (i.e. the payload size and the payload checksum).
+const RECORD_HEADER_SIZE: usize = 4 + 8;
+/// The file extension of a segment.
+const SEGMENT_EXTENSION: &str = "seg";
+/// The file extension of a snapshot segment that is being written.
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+/// A BFT file storage service.
+///
+/// The storage state is held in memory, and every update is appended to a log of segment files,
+/// which is replayed when the storage is opened. Once the log reaches `MAX_SEGMENTS` segments,
+/// the state is written to a snapshot segment, which supersedes the previous segments.
+#[derive(Debug)]
+pub struct BFTFileStorage<N: Network> {
+    /// The in-memory storage state.
+    memory: BFTMemoryService<N>,
+    /// The segments of the log.
+    segments: Mutex<Segments>,
+}
+
+/// The segments of the log.
+#[derive(Debug)]
+struct Segments {
+    /// The directory of the segments.
+    directory: PathBuf,
+    /// The indices of the segments, in ascending order.
+    indices: Vec<u64>,
+    /// The active segment (i.e. the last segment), opened for appending.
+    active: File,
+    /// The size of the active segment.
+    active_size: u64,
+}
+
+impl<N: Network> BFTFileStorage<N> {
+    /// Initializes a new BFT file storage service, replaying the segments in the given directory.
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self> {
+        let directory = directory.into();
+        // Ensure the directory exists.
+        fs::create_dir_all(&directory)?;
+
+        // Collect the segment indices, and remove any snapshot segment that was not completely written.
+        let mut indices = Vec::new();
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some(SEGMENT_EXTENSION) => match path.file_stem().and_then(|stem| stem.to_str()) {
+                    Some(stem) => indices.push(stem.parse::<u64>()?),
+                    None => bail!("Invalid segment file name '{}'", path.display()),
+                },
+                Some(TEMPORARY_EXTENSION) => fs::remove_file(&path)?,
+                _ => (),
+            }
+        }
+        indices.sort_unstable();
+
+        // Replay the segments.
+        let mut memory = BFTMemoryService::new();
+        let mut num_superseded = 0;
+        for (i, index) in indices.iter().enumerate() {
+            let path = segment_path(&directory, *index);
+            // Read the operations from the segment.
+            let bytes = fs::read(&path)?;
+            let (operations, size) = read_operations::<N>(&bytes)
+                .map_err(|e| anyhow!("Failed to read segment '{}' - {e}", path.display()))?;
+            // If the segment ends with a partially-written record, it must be the last segment.
+            if size < bytes.len() {
+                ensure!(i + 1 == indices.len(), "Segment '{}' is corrupted", path.display());
+                warn!("Truncating a partially-written record from segment '{}'", path.display());
+                OpenOptions::new().write(true).open(&path)?.set_len(size as u64)?;
+            }
+            // If the segment is a snapshot, it supersedes the previous segments.
+            if matches!(operations.first(), Some(Operation::Snapshot)) {
+                memory = BFTMemoryService::new();
+                num_superseded = i;
+            }
+            // Apply the operations.
+            for operation in operations {
+                operation.apply(&memory)?;
+            }
+        }
+        // Remove the superseded segments (i.e. left behind by an interrupted compaction).
+        for index in indices.drain(..num_superseded) {
+            fs::remove_file(segment_path(&directory, index))?;
+        }
+
+        // Open the active segment.
+        if indices.is_empty() {
+            indices.push(0);
+        }
+        let active_index = indices[indices.len() - 1];
+        let active = OpenOptions::new().create(true).append(true).open(segment_path(&directory, active_index))?;
+        let active_size = active.metadata()?.len();
+
+        Ok(Self { memory, segments: Mutex::new(Segments { directory, indices, active, active_size }) })
+    }
+
+    /// Appends the given operation to the log, and then applies it to the in-memory state.
+    fn write(&self, operation: Operation<N>) -> Result<()> {
+        // Acquire the segments lock, to ensure the log is in the same order as the in-memory state.
+        let mut segments = self.segments.lock();
+        // Ensure the operation applies, as a logged operation must apply when the log is replayed.
+        operation.check(&self.memory)?;
+        // Append the operation, before it is applied, so the in-memory state never runs ahead of the log.
+        Self::append(&mut segments, &operation.to_bytes_le()?)?;
+        // Apply the operation.
+        operation.apply(&self.memory)?;
+        // Start a new segment, if the active segment is full.
+        // Note: The operation is already applied to the in-memory state, as it may be used for a snapshot.
+        self.rotate_if_full(&mut segments);
+        Ok(())
+    }
+
+    /// Appends the given payload to the active segment, and syncs it to disk.
+    fn append(segments: &mut Segments, payload: &[u8]) -> Result<()> {
+        // Write the record.
+        let record = encode_record(payload)?;
+        if let Err(e) = segments.active.write_all(&record).and_then(|_| segments.active.sync_data()) {
+            // Remove the partially-written record, if any, so that the next record follows a complete record.
+            if let Err(e) = segments.active.set_len(segments.active_size) {
+                error!("Failed to truncate the active segment - {e}");
+            }
+            bail!("Failed to append to the active segment - {e}");
+        }
+        segments.active_size += record.len() as u64;
+        Ok(())
+    }
+
+    /// Starts a new segment if the active segment is full, compacting the segments if there are too many.
+    fn rotate_if_full(&self, segments: &mut Segments) {
+        if segments.active_size >= MAX_SEGMENT_SIZE {
+            // If there are too many segments, compact the segments into a snapshot segment.
+            let result = match segments.indices.len() >= MAX_SEGMENTS {
+                true => self.compact(segments),
+                false => segments.rotate(),
+            };
+            // Note: The record was durably written, so a failure here is not an error for the caller.
+            if let Err(e) = result {
+                error!("Failed to start a new segment - {e}");
+            }
+        }
+    }
+
+    /// Writes the in-memory state to a new snapshot segment, and removes the previous segments.
+    fn compact(&self, segments: &mut Segments) -> Result<()> {
+        let index = segments.next_index();
+        let path = segment_path(&segments.directory, index);
+        let temporary_path = path.with_extension(TEMPORARY_EXTENSION);
+
+        // Write the snapshot to a temporary file.
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        for operation in std::iter::once(Operation::Snapshot).chain(self.snapshot()) {
+            writer.write_all(&encode_record(&operation.to_bytes_le()?)?)?;
+        }
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+        // Move the snapshot into place, to ensure a snapshot segment is never partially written.
+        fs::rename(&temporary_path, &path)?;
+        sync_directory(&segments.directory)?;
+
+        // Open the snapshot segment as the active segment.
+        segments.active = OpenOptions::new().append(true).open(&path)?;
+        segments.active_size = segments.active.metadata()?.len();
+        let previous_indices = std::mem::replace(&mut segments.indices, vec![index]);
+
+        // Remove the previous segments.
+        // Note: If this is interrupted, the previous segments are removed when the storage is next opened.
+        for previous_index in previous_indices {
+            if let Err(e) = fs::remove_file(segment_path(&segments.directory, previous_index)) {
+                warn!("Failed to remove segment {previous_index} after compaction - {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the operations that reconstruct the in-memory state.
+    fn snapshot(&self) -> Vec<Operation<N>> {
+        let mut operations = Vec::new();
+        // Add the certificates.
+        for certificate in self.memory.certificates() {
+            operations.push(Operation::InsertCertificate(certificate));
+        }
+        // Add the transmissions.
+        for (transmission_id, (transmission, certificate_ids)) in self.memory.transmission_entries() {
+            let mut transmission = Some(transmission);
+            for certificate_id in certificate_ids {
+                // Note: The transmission is only provided for the first certificate ID.
+                let missing_transmissions = transmission.take().map(|t| (transmission_id, t)).into_iter().collect();
+                operations.push(Operation::InsertTransmissions(
+                    certificate_id,
+                    indexset! { transmission_id },
+                    missing_transmissions,
+                ));
+            }
+        }
+        // Add the proposal journal.
+        if let Some(journal) = self.memory.get_proposal_journal() {
+            operations.push(Operation::StoreProposalJournal(journal));
+        }
+        // Add the proposal signatures.
+        for (batch_id, signature) in self.memory.proposal_signature_entries() {
+            operations.push(Operation::InsertProposalSignature(batch_id, signature));
+        }
+        // Add the signed batches.
+        for (round, author, batch_id) in self.memory.signed_batches() {
+            operations.push(Operation::InsertSignedBatch(round, author, batch_id));
+        }
+        operations
+    }
+}
+
+impl Segments {
+    /// Returns the index of the next segment.
+    fn next_index(&self) -> u64 {
+        self.indices.last().map_or(0, |index| index + 1)
+    }
+
+    /// Starts a new (empty) active segment.
+    fn rotate(&mut self) -> Result<()> {
+        let index = self.next_index();
+        self.active = OpenOptions::new().create_new(true).append(true).open(segment_path(&self.directory, index))?;
+        self.active_size = 0;
+        self.indices.push(index);
+        sync_directory(&self.directory)
+    }
+}
+
+/// Returns the path of the segment with the given index.
+fn segment_path(directory: &Path, index: u64) -> PathBuf {
+    directory.join(format!("{index:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// Syncs the given directory, to ensure the creation and removal of files is durable.
+fn sync_directory(directory: &Path) -> Result<()> {
+    #[cfg(unix)]
+    File::open(directory)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = directory;
+    Ok(())
+}
+
+/// Returns the checksum of the given payload.
+fn checksum(payload: &[u8]) -> [u8; 8] {
+    let mut checksum = [0u8; 8];
+    checksum.copy_from_slice(&Sha256::digest(payload)[..8]);
+    checksum
+}
+
+/// Encodes the given payload as a record, prefixed by its size and checksum.
+fn encode_record(payload: &[u8]) -> Result<Vec<u8>> {
+    ensure!(payload.len() <= MAX_RECORD_SIZE, "Record of {} bytes exceeds the maximum size", payload.len());
+    let mut record = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+    record.extend_from_slice(&u32::try_from(payload.len())?.to_le_bytes());
+    record.extend_from_slice(&checksum(payload));
+    record.extend_from_slice(payload);
+    Ok(record)
+}
+
+/// Returns the given length as a `u32`, for encoding an operation.
+fn encode_len(len: usize) -> IoResult<u32> {
+    u32::try_from(len).map_err(|_| snarkvm::prelude::error(format!("Length {len} exceeds the encoding limit")))
+}
+
+/// Reads the operations from the given segment bytes, returning the operations and the number of bytes read.
+///
+/// Reading stops at an incomplete or mismatching record at the end of the segment,
+/// which is the result of a partial write. A mismatching record that is followed by further records is a corruption, and returns an error,
+/// as discarding the records after it could discard signed batches (i.e. the slashing protection state).
+/// As the size of an incomplete record may itself be corrupted, it is only a partial write if no valid record follows it.
+fn read_operations<N: Network>(bytes: &[u8]) -> Result<(Vec<Operation<N>>, usize)> {
+    let mut operations = Vec::new();
+    let mut offset = 0;
+    while bytes.len() - offset >= RECORD_HEADER_SIZE {
+        // Read the record header.
+        let size = u32::from_le_bytes(bytes[offset..offset + 4].try_into()?) as usize;
+        let expected_checksum = &bytes[offset + 4..offset + RECORD_HEADER_SIZE];
+        // Read the payload.
+        let start = offset + RECORD_HEADER_SIZE;
+        let end = start.saturating_add(size);
+        let Some(payload) = bytes.get(start..end) else {
+            // Only the last record may be partially written.
+            ensure!(!has_record_after(bytes, offset), "Found a corrupted record at offset {offset}");
+            break;
+        };
+        if size > MAX_RECORD_SIZE || checksum(payload) != expected_checksum {
+            // Only the last record may be partially written.
+            ensure!(end == bytes.len(), "Found a corrupted record at offset {offset}");
+            break;
+        }
+        // Note: A record with a valid checksum must decode, otherwise the segment is corrupted.
+        operations.push(Operation::from_bytes_le(payload)?);
+        offset = start + size;
+    }
+    Ok((operations, offset))
+}
+
+/// Returns `true` if a record with a matching checksum starts after the given offset in the segment bytes.
+fn has_record_after(bytes: &[u8], offset: usize) -> bool {
+    (offset + 1..=bytes.len().saturating_sub(RECORD_HEADER_SIZE)).any(|offset| {
+        let size =
+            u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]]) as usize;
+        let start = offset + RECORD_HEADER_SIZE;
+        size <= MAX_RECORD_SIZE
+            && bytes.get(start..start + size).is_some_and(|payload| checksum(payload) == bytes[offset + 4..start])
+    })
+}
+
+/// An update to the storage state, as recorded in the log.
+enum Operation<N: Network> {
+    /// Marks the start of a snapshot segment, which supersedes the previous segments.
+    Snapshot,
+    /// Inserts the certificate ID for the transmission IDs, using the missing transmissions.
+    InsertTransmissions(Field<N>, IndexSet<TransmissionID<N>>, HashMap<TransmissionID<N>, Transmission<N>>),
+    /// Removes the certificate ID for the transmission IDs.
+    RemoveTransmissions(Field<N>, IndexSet<TransmissionID<N>>),
+    /// Inserts the certificate.
+    InsertCertificate(BatchCertificate<N>),
+    /// Removes the certificate.
+    RemoveCertificate(BatchCertificate<N>),
+    /// Stores the proposal journal.
+    StoreProposalJournal(Vec<u8>),
+    /// Inserts the signed batch for the round and author.
+    InsertSignedBatch(u64, Address<N>, Field<N>),
+    /// Removes the signed batches up to (and including) the round.
+    RemoveSignedBatches(u64),
+    /// Inserts the signature for the journaled proposal with the batch ID.
+    InsertProposalSignature(Field<N>, Signature<N>),
+}
+
+impl<N: Network> Operation<N> {
+    /// Ensures the operation applies to the given in-memory state.
+    fn check(&self, memory: &BFTMemoryService<N>) -> Result<()> {
+        match self {
+            Self::InsertSignedBatch(round, author, batch_id) => memory.check_signed_batch(*round, *author, *batch_id),
+            _ => Ok(()),
+        }
+    }
+
+    /// Applies the operation to the given in-memory state.
+    fn apply(self, memory: &BFTMemoryService<N>) -> Result<()> {
+        match self {
+            Self::Snapshot => (),
+            Self::InsertTransmissions(certificate_id, transmission_ids, missing_transmissions) => {
+                memory.insert_transmissions(certificate_id, transmission_ids, missing_transmissions)
+            }
+            Self::RemoveTransmissions(certificate_id, transmission_ids) => {
+                memory.remove_transmissions(&certificate_id, &transmission_ids)
+            }
+            Self::InsertCertificate(certificate) => memory.insert_certificate(&certificate),
+            Self::RemoveCertificate(certificate) => memory.remove_certificate(&certificate),
+            Self::StoreProposalJournal(journal) => memory.store_proposal_journal(journal)?,
+            Self::InsertSignedBatch(round, author, batch_id) => memory.insert_signed_batch(round, author, batch_id)?,
+            Self::RemoveSignedBatches(round) => memory.remove_signed_batches(round),
+            Self::InsertProposalSignature(batch_id, signature) => {
+                memory.insert_proposal_signature(batch_id, signature)?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> ToBytes for Operation<N> {
+    /// Writes the operation to the buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        match self {
+            Self::InsertTransmissions(certificate_id, transmission_ids, missing_transmissions) => {
+                0u8.write_le(&mut writer)?;
+                certificate_id.write_le(&mut writer)?;
+                encode_len(transmission_ids.len())?.write_le(&mut writer)?;
+                for transmission_id in transmission_ids {
+                    transmission_id.write_le(&mut writer)?;
+                }
+                encode_len(missing_transmissions.len())?.write_le(&mut writer)?;
+                for (transmission_id, transmission) in missing_transmissions {
+                    transmission_id.write_le(&mut writer)?;
+                    transmission.write_le(&mut writer)?;
+                }
+                Ok(())
+            }
+            Self::RemoveTransmissions(certificate_id, transmission_ids) => {
+                1u8.write_le(&mut writer)?;
+                certificate_id.write_le(&mut writer)?;
+                encode_len(transmission_ids.len())?.write_le(&mut writer)?;
+                for transmission_id in transmission_ids {
+                    transmission_id.write_le(&mut writer)?;
+                }
+                Ok(())
+            }
+            Self::InsertCertificate(certificate) => {
+                2u8.write_le(&mut writer)?;
+                certificate.write_le(&mut writer)
+            }
+            Self::RemoveCertificate(certificate) => {
+                3u8.write_le(&mut writer)?;
+                certificate.write_le(&mut writer)
+            }
+            Self::StoreProposalJournal(journal) => {
+                4u8.write_le(&mut writer)?;
+                encode_len(journal.len())?.write_le(&mut writer)?;
+                writer.write_all(journal)
+            }
+            Self::InsertSignedBatch(round, author, batch_id) => {
+                5u8.write_le(&mut writer)?;
+                round.write_le(&mut writer)?;
+                author.write_le(&mut writer)?;
+                batch_id.write_le(&mut writer)
+            }
+            Self::RemoveSignedBatches(round) => {
+                6u8.write_le(&mut writer)?;
+                round.write_le(&mut writer)
+            }
+            Self::Snapshot => 7u8.write_le(&mut writer),
+            Self::InsertProposalSignature(batch_id, signature) => {
+                8u8.write_le(&mut writer)?;
+                batch_id.write_le(&mut writer)?;
+                signature.write_le(&mut writer)
+            }
+        }
+    }
+}
+
+impl<N: Network> FromBytes for Operation<N> {
+    /// Reads the operation from the buffer.
+    ///
+    /// Note: The buffer is bounded by the record size, which bounds the number of entries that can be read.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        match u8::read_le(&mut reader)? {
+            0 => {
+                let certificate_id = Field::read_le(&mut reader)?;
+                let num_transmission_ids = u32::read_le(&mut reader)?;
+                let transmission_ids =
+                    (0..num_transmission_ids).map(|_| TransmissionID::read_le(&mut reader)).collect::<IoResult<_>>()?;
+                let num_missing_transmissions = u32::read_le(&mut reader)?;
+                let missing_transmissions = (0..num_missing_transmissions)
+                    .map(|_| Ok((TransmissionID::read_le(&mut reader)?, Transmission::read_le(&mut reader)?)))
+                    .collect::<IoResult<_>>()?;
+                Ok(Self::InsertTransmissions(certificate_id, transmission_ids, missing_transmissions))
+            }
+            1 => {
+                let certificate_id = Field::read_le(&mut reader)?;
+                let num_transmission_ids = u32::read_le(&mut reader)?;
+                let transmission_ids =
+                    (0..num_transmission_ids).map(|_| TransmissionID::read_le(&mut reader)).collect::<IoResult<_>>()?;
+                Ok(Self::RemoveTransmissions(certificate_id, transmission_ids))
+            }
+            2 => Ok(Self::InsertCertificate(BatchCertificate::read_le(&mut reader)?)),
+            3 => Ok(Self::RemoveCertificate(BatchCertificate::read_le(&mut reader)?)),
+            4 => {
+                let num_bytes = u32::read_le(&mut reader)?;
+                let mut journal = Vec::new();
+                reader.take(num_bytes as u64).read_to_end(&mut journal)?;
+                match journal.len() == num_bytes as usize {
+                    true => Ok(Self::StoreProposalJournal(journal)),
+                    false => Err(snarkvm::prelude::error("Failed to read the proposal journal")),
+                }
+            }
+            5 => {
+                let round = u64::read_le(&mut reader)?;
+                let author = Address::read_le(&mut reader)?;
+                let batch_id = Field::read_le(&mut reader)?;
+                Ok(Self::InsertSignedBatch(round, author, batch_id))
+            }
+            6 => Ok(Self::RemoveSignedBatches(u64::read_le(&mut reader)?)),
+            7 => Ok(Self::Snapshot),
+            8 => {
+                let batch_id = Field::read_le(&mut reader)?;
+                let signature = Signature::read_le(&mut reader)?;
+                Ok(Self::InsertProposalSignature(batch_id, signature))
+            }
+            variant => Err(snarkvm::prelude::error(format!("Invalid operation variant ({variant})"))),
+        }
+    }
+}
+
+impl<N: Network> StorageService<N> for BFTFileStorage<N> {
+    /// Returns `true` if the storage contains the specified `transmission ID`.
+    fn contains_transmission(&self, transmission_id: TransmissionID<N>) -> bool {
+        self.memory.contains_transmission(transmission_id)
+    }
+
+    /// Returns the transmission for the given `transmission ID`.
+    /// If the transmission ID does not exist in storage, `None` is returned.
+    fn get_transmission(&self, transmission_id: TransmissionID<N>) -> Option<Transmission<N>> {
+        self.memory.get_transmission(transmission_id)
+    }
+
+    /// Returns the missing transmissions in storage from the given transmissions.
+    fn find_missing_transmissions(
+        &self,
+        batch_header: &BatchHeader<N>,
+        transmissions: HashMap<TransmissionID<N>, Transmission<N>>,
+    ) -> Result<HashMap<TransmissionID<N>, Transmission<N>>> {
+        self.memory.find_missing_transmissions(batch_header, transmissions)
+    }
+
+    /// Inserts the given certificate ID for each of the transmission IDs, using the missing transmissions map, into storage.
+    fn insert_transmissions(
+        &self,
+        certificate_id: Field<N>,
+        transmission_ids: IndexSet<TransmissionID<N>>,
+        missing_transmissions: HashMap<TransmissionID<N>, Transmission<N>>,
+    ) {
+        let operation = Operation::InsertTransmissions(certificate_id, transmission_ids, missing_transmissions);
+        if let Err(e) = self.write(operation) {
+            error!("Failed to insert the transmissions for certificate {certificate_id} into storage - {e}");
+        }
+    }
+
+    /// Removes the certificate ID for the transmissions from storage.
+    ///
+    /// If the transmission no longer references any certificate IDs, the entry is removed from storage.
+    fn remove_transmissions(&self, certificate_id: &Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>) {
+        let operation = Operation::RemoveTransmissions(*certificate_id, transmission_ids.clone());
+        if let Err(e) = self.write(operation) {
+            error!("Failed to remove the transmissions for certificate {certificate_id} from storage - {e}");
+        }
+    }
+
+    /// Returns the certificates in storage, ordered by round.
+    fn certificates(&self) -> Vec<BatchCertificate<N>> {
+        self.memory.certificates()
+    }
+
+    /// Inserts the given certificate into the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn insert_certificate(&self, certificate: &BatchCertificate<N>) {
+        if let Err(e) = self.write(Operation::InsertCertificate(certificate.clone())) {
+            error!("Failed to insert certificate {} into storage - {e}", certificate.id());
+        }
+    }
+
+    /// Removes the given certificate from the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn remove_certificate(&self, certificate: &BatchCertificate<N>) {
+        if let Err(e) = self.write(Operation::RemoveCertificate(certificate.clone())) {
+            error!("Failed to remove certificate {} from storage - {e}", certificate.id());
+        }
+    }
+
+    /// Returns the proposal journal from storage, if one exists.
+    fn get_proposal_journal(&self) -> Option<Vec<u8>> {
+        self.memory.get_proposal_journal()
+    }
+
+    /// Stores the given proposal journal in storage, replacing the existing journal and its signatures.
+    fn store_proposal_journal(&self, journal: Vec<u8>) -> Result<()> {
+        self.write(Operation::StoreProposalJournal(journal))
+    }
+
+    /// Returns the signatures recorded for the journaled proposal with the given `batch ID`.
+    fn get_proposal_signatures(&self, batch_id: Field<N>) -> Vec<Signature<N>> {
+        self.memory.get_proposal_signatures(batch_id)
+    }
+
+    /// Records the given signature for the journaled proposal with the given `batch ID`.
+    fn insert_proposal_signature(&self, batch_id: Field<N>, signature: Signature<N>) -> Result<()> {
+        self.write(Operation::InsertProposalSignature(batch_id, signature))
+    }
+
+    /// Returns the `(round, author, batch ID)` entries of the batches signed by this validator, ordered by round.
+    fn signed_batches(&self) -> Vec<(u64, Address<N>, Field<N>)> {
+        self.memory.signed_batches()
+    }
+
+    /// Records that this validator signed the batch with the given `batch ID`, for the given `round` and `author`.
+    fn insert_signed_batch(&self, round: u64, author: Address<N>, batch_id: Field<N>) -> Result<()> {
+        // Note: A conflicting signed batch is refused before it is appended to the log.
+        self.write(Operation::InsertSignedBatch(round, author, batch_id))
+    }
+
+    /// Removes the signed batch entries for all rounds up to (and including) the given `round`,
+    /// except for the highest signed round of each author.
+    fn remove_signed_batches(&self, round: u64) {
+        if let Err(e) = self.write(Operation::RemoveSignedBatches(round)) {
+            error!("Failed to remove the signed batches up to round {round} from storage - {e}");
+        }
+    }
+
+    /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
+    #[cfg(any(test, feature = "test"))]
+    fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
+        self.memory.as_hashmap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        ledger::narwhal::batch_certificate::test_helpers::sample_batch_certificate_for_round,
+        prelude::TestRng,
+    };
+
+    type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+    /// Inserts a sample certificate, its transmissions, a proposal journal, and a signed batch into the storage.
+    fn populate(storage: &BFTFileStorage<CurrentNetwork>, rng: &mut TestRng) -> BatchCertificate<CurrentNetwork> {
+        let certificate = sample_batch_certificate_for_round(2, rng);
+        let transmissions = certificate.transmission_ids().iter().map(|id| (*id, Transmission::Ratification)).collect();
+        storage.insert_transmissions(certificate.id(), certificate.transmission_ids().clone(), transmissions);
+        storage.insert_certificate(&certificate);
+        storage.store_proposal_journal(vec![1, 2, 3]).unwrap();
+        storage.insert_signed_batch(certificate.round(), certificate.author(), certificate.batch_id()).unwrap();
+        certificate
+    }
+
+    /// Asserts that the storage contains the state inserted by `populate`.
+    fn assert_populated(storage: &BFTFileStorage<CurrentNetwork>, certificate: &BatchCertificate<CurrentNetwork>) {
+        assert_eq!(storage.certificates(), vec![certificate.clone()]);
+        assert_eq!(storage.as_hashmap().len(), certificate.transmission_ids().len());
+        assert_eq!(storage.get_proposal_journal(), Some(vec![1, 2, 3]));
+        assert_eq!(storage.signed_batches(), vec![(certificate.round(), certificate.author(), certificate.batch_id())]);
+    }
+
+    #[test]
+    fn test_reopen() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage, and reopen it.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        let certificate = populate(&storage, rng);
+        drop(storage);
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        assert_populated(&storage, &certificate);
+
+        // Remove the certificate, and reopen the storage.
+        storage.remove_transmissions(&certificate.id(), certificate.transmission_ids());
+        storage.remove_certificate(&certificate);
+        drop(storage);
+        let storage = BFTFileStorage::<CurrentNetwork>::open(temp_dir.path()).unwrap();
+        assert!(storage.certificates().is_empty());
+        assert!(storage.as_hashmap().is_empty());
+    }
+
+    #[test]
+    fn test_partially_written_record() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        let certificate = populate(&storage, rng);
+        drop(storage);
+
+        // Append a partially-written record to the segment.
+        let path = segment_path(temp_dir.path(), 0);
+        let size = fs::metadata(&path).unwrap().len();
+        let record =
+            encode_record(&Operation::<CurrentNetwork>::RemoveCertificate(certificate.clone()).to_bytes_le().unwrap())
+                .unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&record[..record.len() / 2]).unwrap();
+        drop(file);
+
+        // Ensure the partially-written record is truncated, and the remaining state is restored.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), size);
+        assert_populated(&storage, &certificate);
+    }
+
+    #[test]
+    fn test_corrupted_record() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        populate(&storage, rng);
+        drop(storage);
+
+        // Corrupt the payload of the first record in the segment.
+        let path = segment_path(temp_dir.path(), 0);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[RECORD_HEADER_SIZE] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+
+        // Ensure the storage refuses to open, and the records after the corrupted record are kept.
+        assert!(BFTFileStorage::<CurrentNetwork>::open(temp_dir.path()).is_err());
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_corrupted_record_size() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        populate(&storage, rng);
+        drop(storage);
+
+        // Corrupt the size of the first record in the segment, so that it runs past the end of the segment.
+        let path = segment_path(temp_dir.path(), 0);
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+
+        // Ensure the storage refuses to open, rather than truncating the records after the corrupted record.
+        assert!(BFTFileStorage::<CurrentNetwork>::open(temp_dir.path()).is_err());
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_refused_operation_is_not_logged() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        let certificate = populate(&storage, rng);
+        let path = segment_path(temp_dir.path(), 0);
+        let size = fs::metadata(&path).unwrap().len();
+
+        // Ensure a conflicting signed batch is refused, without being appended to the log.
+        let (round, author) = (certificate.round(), certificate.author());
+        assert!(storage.insert_signed_batch(round, author, Field::from_u64(1)).is_err());
+        assert_eq!(fs::metadata(&path).unwrap().len(), size);
+        drop(storage);
+
+        // Ensure the storage reopens with the original state.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        assert_populated(&storage, &certificate);
+    }
+
+    #[test]
+    fn test_compact() {
+        let rng = &mut TestRng::default();
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        // Populate the storage, across several segments.
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        storage.segments.lock().rotate().unwrap();
+        let certificate = populate(&storage, rng);
+        storage.segments.lock().rotate().unwrap();
+        storage.remove_signed_batches(0);
+        assert_eq!(storage.segments.lock().indices, vec![0, 1, 2]);
+
+        // Compact the segments.
+        storage.compact(&mut storage.segments.lock()).unwrap();
+        assert_eq!(storage.segments.lock().indices, vec![3]);
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+        // Ensure the snapshot segment remains the active segment.
+        storage.store_proposal_journal(vec![1, 2, 3]).unwrap();
+        drop(storage);
+
+        // Restore a superseded segment (i.e. an interrupted compaction), and reopen the storage.
+        fs::write(
+            segment_path(temp_dir.path(), 1),
+            encode_record(&Operation::<CurrentNetwork>::StoreProposalJournal(vec![4]).to_bytes_le().unwrap()).unwrap(),
+        )
+        .unwrap();
+        let storage = BFTFileStorage::open(temp_dir.path()).unwrap();
+        assert_populated(&storage, &certificate);
+        assert_eq!(storage.segments.lock().indices, vec![3]);
+        assert!(!segment_path(temp_dir.path(), 1).exists());
+    }
+}
@@ -15,6 +15,11 @@
 #![forbid(unsafe_code)]
 #![allow(clippy::type_complexity)]
 
+#[cfg(feature = "file")]
+pub mod file;
+#[cfg(feature = "file")]
+pub use file::*;
+
 #[cfg(feature = "memory")]
 pub mod memory;
 #[cfg(feature = "memory")]
@@ -25,5 +30,11 @@ pub mod persistent;
 #[cfg(feature = "persistent")]
 pub use persistent::*;
 
+pub mod backend;
+pub use backend::*;
+
 pub mod traits;
 pub use traits::*;
+
+#[cfg(test)]
+mod tests;
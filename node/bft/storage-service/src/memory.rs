@@ -14,8 +14,8 @@
 
 use crate::StorageService;
 use snarkvm::{
-    ledger::narwhal::{BatchHeader, Transmission, TransmissionID},
-    prelude::{bail, Field, Network, Result},
+    ledger::narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
+    prelude::{bail, ensure, Address, Field, Network, Result, Signature},
 };
 
 use indexmap::{indexset, map::Entry, IndexMap, IndexSet};
@@ -28,6 +28,18 @@ use tracing::error;
 pub struct BFTMemoryService<N: Network> {
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: RwLock<IndexMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>>,
+    /// The map of `round` to a list of `(certificate ID, batch ID, author)` entries.
+    rounds: RwLock<IndexMap<u64, IndexSet<(Field<N>, Field<N>, Address<N>)>>>,
+    /// The map of `certificate ID` to `certificate`.
+    certificates: RwLock<IndexMap<Field<N>, BatchCertificate<N>>>,
+    /// The map of `batch ID` to `round`.
+    batch_ids: RwLock<IndexMap<Field<N>, u64>>,
+    /// The proposal journal.
+    proposal_journal: RwLock<Option<Vec<u8>>>,
+    /// The map of `batch ID` to the signatures recorded for the journaled proposal.
+    proposal_signatures: RwLock<IndexMap<Field<N>, Vec<Signature<N>>>>,
+    /// The map of `round` to `(author, batch ID)` entries, for the batches signed by this validator.
+    signed_batches: RwLock<IndexMap<u64, IndexMap<Address<N>, Field<N>>>>,
 }
 
 impl<N: Network> Default for BFTMemoryService<N> {
@@ -40,7 +52,43 @@ impl<N: Network> Default for BFTMemoryService<N> {
 impl<N: Network> BFTMemoryService<N> {
     /// Initializes a new BFT in-memory storage service.
     pub fn new() -> Self {
-        Self { transmissions: Default::default() }
+        Self {
+            transmissions: Default::default(),
+            rounds: Default::default(),
+            certificates: Default::default(),
+            batch_ids: Default::default(),
+            proposal_journal: Default::default(),
+            proposal_signatures: Default::default(),
+            signed_batches: Default::default(),
+        }
+    }
+
+    /// Returns the `(transmission ID, (transmission, certificate IDs))` entries.
+    #[cfg(feature = "file")]
+    pub(crate) fn transmission_entries(&self) -> Vec<(TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>))> {
+        self.transmissions.read().iter().map(|(transmission_id, entry)| (*transmission_id, entry.clone())).collect()
+    }
+
+    /// Ensures a different batch was not already signed for the given `round` and `author`.
+    #[cfg(feature = "file")]
+    pub(crate) fn check_signed_batch(&self, round: u64, author: Address<N>, batch_id: Field<N>) -> Result<()> {
+        if let Some(signed_batch_id) = self.signed_batches.read().get(&round).and_then(|entries| entries.get(&author)) {
+            ensure!(
+                *signed_batch_id == batch_id,
+                "Already signed batch {signed_batch_id} for round {round} from '{author}' (refusing {batch_id})"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the `(batch ID, signature)` entries recorded for the journaled proposal.
+    #[cfg(feature = "file")]
+    pub(crate) fn proposal_signature_entries(&self) -> Vec<(Field<N>, Signature<N>)> {
+        self.proposal_signatures
+            .read()
+            .iter()
+            .flat_map(|(batch_id, signatures)| signatures.iter().map(|signature| (*batch_id, *signature)))
+            .collect()
     }
 }
 
@@ -143,6 +191,143 @@ impl<N: Network> StorageService<N> for BFTMemoryService<N> {
         }
     }
 
+    /// Returns the certificates in storage, ordered by round.
+    fn certificates(&self) -> Vec<BatchCertificate<N>> {
+        // Acquire the certificates read lock.
+        let certificates = self.certificates.read();
+        // Sort the rounds in ascending order.
+        let mut rounds = self.rounds.read().clone();
+        rounds.sort_keys();
+        // Collect the certificates for each round.
+        rounds
+            .values()
+            .flat_map(|entries| entries.iter().filter_map(|(certificate_id, _, _)| certificates.get(certificate_id)))
+            .cloned()
+            .collect()
+    }
+
+    /// Inserts the given certificate into the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn insert_certificate(&self, certificate: &BatchCertificate<N>) {
+        // Retrieve the round.
+        let round = certificate.round();
+        // Retrieve the certificate ID.
+        let certificate_id = certificate.id();
+        // Retrieve the batch ID.
+        let batch_id = certificate.batch_id();
+
+        // Insert the round to certificate ID entry.
+        self.rounds.write().entry(round).or_default().insert((certificate_id, batch_id, certificate.author()));
+        // Insert the certificate.
+        self.certificates.write().insert(certificate_id, certificate.clone());
+        // Insert the batch ID.
+        self.batch_ids.write().insert(batch_id, round);
+    }
+
+    /// Removes the given certificate from the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn remove_certificate(&self, certificate: &BatchCertificate<N>) {
+        // Retrieve the round.
+        let round = certificate.round();
+        // Retrieve the certificate ID.
+        let certificate_id = certificate.id();
+        // Retrieve the batch ID.
+        let batch_id = certificate.batch_id();
+
+        // Update the round.
+        {
+            // Acquire the write lock.
+            let mut rounds = self.rounds.write();
+            // Remove the round to certificate ID entry.
+            if let Some(entries) = rounds.get_mut(&round) {
+                entries.remove(&(certificate_id, batch_id, certificate.author()));
+                // If the round is empty, remove it.
+                if entries.is_empty() {
+                    rounds.remove(&round);
+                }
+            }
+        }
+        // Remove the certificate.
+        self.certificates.write().remove(&certificate_id);
+        // Remove the batch ID.
+        self.batch_ids.write().remove(&batch_id);
+    }
+
+    /// Returns the proposal journal from storage, if one exists.
+    fn get_proposal_journal(&self) -> Option<Vec<u8>> {
+        self.proposal_journal.read().clone()
+    }
+
+    /// Stores the given proposal journal in storage, replacing the existing journal and its signatures.
+    fn store_proposal_journal(&self, journal: Vec<u8>) -> Result<()> {
+        *self.proposal_journal.write() = Some(journal);
+        self.proposal_signatures.write().clear();
+        Ok(())
+    }
+
+    /// Returns the signatures recorded for the journaled proposal with the given `batch ID`.
+    fn get_proposal_signatures(&self, batch_id: Field<N>) -> Vec<Signature<N>> {
+        self.proposal_signatures.read().get(&batch_id).cloned().unwrap_or_default()
+    }
+
+    /// Records the given signature for the journaled proposal with the given `batch ID`.
+    fn insert_proposal_signature(&self, batch_id: Field<N>, signature: Signature<N>) -> Result<()> {
+        let mut proposal_signatures = self.proposal_signatures.write();
+        let signatures = proposal_signatures.entry(batch_id).or_default();
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+        }
+        Ok(())
+    }
+
+    /// Returns the `(round, author, batch ID)` entries of the batches signed by this validator, ordered by round.
+    fn signed_batches(&self) -> Vec<(u64, Address<N>, Field<N>)> {
+        let mut signed_batches = self
+            .signed_batches
+            .read()
+            .iter()
+            .flat_map(|(round, entries)| entries.iter().map(|(author, batch_id)| (*round, *author, *batch_id)))
+            .collect::<Vec<_>>();
+        // Sort the entries by round.
+        signed_batches.sort_by_key(|(round, _, _)| *round);
+        signed_batches
+    }
+
+    /// Records that this validator signed the batch with the given `batch ID`, for the given `round` and `author`.
+    fn insert_signed_batch(&self, round: u64, author: Address<N>, batch_id: Field<N>) -> Result<()> {
+        let mut signed_batches = self.signed_batches.write();
+        // Retrieve the entries for the round.
+        let entries = signed_batches.entry(round).or_default();
+        // Ensure a different batch was not already signed for the round and author.
+        if let Some(signed_batch_id) = entries.get(&author) {
+            ensure!(
+                *signed_batch_id == batch_id,
+                "Already signed batch {signed_batch_id} for round {round} from '{author}' (refusing {batch_id})"
+            );
+        }
+        entries.insert(author, batch_id);
+        Ok(())
+    }
+
+    /// Removes the signed batch entries for all rounds up to (and including) the given `round`,
+    /// except for the highest signed round of each author.
+    fn remove_signed_batches(&self, round: u64) {
+        let mut signed_batches = self.signed_batches.write();
+        // Determine the highest signed round of each author.
+        let mut highest_rounds = HashMap::new();
+        for (signed_round, entries) in signed_batches.iter() {
+            for author in entries.keys() {
+                let highest_round = highest_rounds.entry(*author).or_insert(*signed_round);
+                *highest_round = (*highest_round).max(*signed_round);
+            }
+        }
+        // Remove the entries up to the given round, keeping the highest signed round of each author.
+        signed_batches.retain(|signed_round, entries| {
+            if *signed_round <= round {
+                entries.retain(|author, _| highest_rounds.get(author) == Some(signed_round));
+            }
+            !entries.is_empty()
+        });
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
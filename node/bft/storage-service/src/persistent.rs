@@ -15,7 +15,7 @@
 use crate::StorageService;
 use snarkvm::{
     ledger::{
-        narwhal::{BatchHeader, Transmission, TransmissionID},
+        narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
         store::helpers::{
             rocksdb::{
                 internal::{self, BFTMap, Database, MapID},
@@ -25,32 +25,106 @@ use snarkvm::{
             MapRead,
         },
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, ensure, Address, Field, Network, Result, Signature},
 };
 
-use indexmap::{indexset, IndexSet};
-use snarkvm::ledger::store::cow_to_cloned;
+use aleo_std::StorageMode;
+use indexmap::{indexset, IndexMap, IndexSet};
+use parking_lot::Mutex;
+use snarkvm::ledger::store::{cow_to_cloned, cow_to_copied};
 use std::{borrow::Cow, collections::HashMap};
 use tracing::error;
 
+/// The RocksDB map prefixes for the BFT entries that are not defined in snarkVM.
+// Note: These values are chosen far above the snarkVM `DataID` range, so that they never collide with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+enum PersistentMap {
+    Rounds = 0xBF00,
+    Certificates = 0xBF01,
+    BatchIDs = 0xBF02,
+    ProposalJournal = 0xBF03,
+    SignedBatches = 0xBF04,
+    ProposalSignatures = 0xBF05,
+}
+
+impl From<PersistentMap> for u16 {
+    fn from(id: PersistentMap) -> u16 {
+        id as u16
+    }
+}
+
+/// The key of the proposal journal in its map.
+const PROPOSAL_JOURNAL_KEY: u8 = 0;
+
 /// A BFT persistent storage service.
 #[derive(Debug)]
 pub struct BFTPersistentStorage<N: Network> {
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: DataMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>,
+    /// The map of `round` to a list of `(certificate ID, batch ID, author)` entries.
+    rounds: DataMap<u64, IndexSet<(Field<N>, Field<N>, Address<N>)>>,
+    /// The map of `certificate ID` to `certificate`.
+    certificates: DataMap<Field<N>, BatchCertificate<N>>,
+    /// The map of `batch ID` to `round`.
+    batch_ids: DataMap<Field<N>, u64>,
+    /// The map holding the proposal journal (under a single key).
+    proposal_journal: DataMap<u8, Vec<u8>>,
+    /// The map of `batch ID` to the signatures recorded for the journaled proposal.
+    proposal_signatures: DataMap<Field<N>, Vec<Signature<N>>>,
+    /// The map of `round` to `(author, batch ID)` entries, for the batches signed by this validator.
+    signed_batches: DataMap<u64, IndexMap<Address<N>, Field<N>>>,
+    /// The lock guarding the read-modify-write updates to the `rounds` map.
+    dag_lock: Mutex<()>,
+    /// The lock guarding the read-modify-write updates to the `signed_batches` map.
+    signing_lock: Mutex<()>,
 }
 
 impl<N: Network> BFTPersistentStorage<N> {
     /// Initializes a new BFT persistent storage service.
-    pub fn open(dev: Option<u16>) -> Result<Self> {
-        Ok(Self { transmissions: internal::RocksDB::open_map(N::ID, dev, MapID::BFT(BFTMap::Transmissions))? })
+    pub fn open(storage_mode: StorageMode) -> Result<Self> {
+        Ok(Self {
+            transmissions: internal::RocksDB::open_map(N::ID, storage_mode.clone(), MapID::BFT(BFTMap::Transmissions))?,
+            rounds: internal::RocksDB::open_map(N::ID, storage_mode.clone(), PersistentMap::Rounds)?,
+            certificates: internal::RocksDB::open_map(N::ID, storage_mode.clone(), PersistentMap::Certificates)?,
+            batch_ids: internal::RocksDB::open_map(N::ID, storage_mode.clone(), PersistentMap::BatchIDs)?,
+            proposal_journal: internal::RocksDB::open_map(N::ID, storage_mode.clone(), PersistentMap::ProposalJournal)?,
+            proposal_signatures: internal::RocksDB::open_map(
+                N::ID,
+                storage_mode.clone(),
+                PersistentMap::ProposalSignatures,
+            )?,
+            signed_batches: internal::RocksDB::open_map(N::ID, storage_mode, PersistentMap::SignedBatches)?,
+            dag_lock: Default::default(),
+            signing_lock: Default::default(),
+        })
     }
 
     /// Initializes a new BFT persistent storage service.
     #[cfg(any(test, feature = "test"))]
     pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
         Ok(Self {
-            transmissions: internal::RocksDB::open_map_testing(temp_dir, dev, MapID::BFT(BFTMap::Transmissions))?,
+            transmissions: internal::RocksDB::open_map_testing(
+                temp_dir.clone(),
+                dev,
+                MapID::BFT(BFTMap::Transmissions),
+            )?,
+            rounds: internal::RocksDB::open_map_testing(temp_dir.clone(), dev, PersistentMap::Rounds)?,
+            certificates: internal::RocksDB::open_map_testing(temp_dir.clone(), dev, PersistentMap::Certificates)?,
+            batch_ids: internal::RocksDB::open_map_testing(temp_dir.clone(), dev, PersistentMap::BatchIDs)?,
+            proposal_journal: internal::RocksDB::open_map_testing(
+                temp_dir.clone(),
+                dev,
+                PersistentMap::ProposalJournal,
+            )?,
+            proposal_signatures: internal::RocksDB::open_map_testing(
+                temp_dir.clone(),
+                dev,
+                PersistentMap::ProposalSignatures,
+            )?,
+            signed_batches: internal::RocksDB::open_map_testing(temp_dir, dev, PersistentMap::SignedBatches)?,
+            dag_lock: Default::default(),
+            signing_lock: Default::default(),
         })
     }
 }
@@ -191,10 +265,212 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         }
     }
 
+    /// Returns the certificates in storage, ordered by round.
+    fn certificates(&self) -> Vec<BatchCertificate<N>> {
+        // Note: The `certificates` map is the source of truth, as the `rounds` map is only an index over it.
+        let mut certificates =
+            self.certificates.values_confirmed().map(|certificate| cow_to_cloned!(certificate)).collect::<Vec<_>>();
+        // Sort the certificates by round.
+        certificates.sort_by_key(|certificate| certificate.round());
+        certificates
+    }
+
+    /// Inserts the given certificate into the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn insert_certificate(&self, certificate: &BatchCertificate<N>) {
+        // Retrieve the round.
+        let round = certificate.round();
+        // Retrieve the certificate ID.
+        let certificate_id = certificate.id();
+        // Retrieve the batch ID.
+        let batch_id = certificate.batch_id();
+
+        // Acquire the DAG lock.
+        let _lock = self.dag_lock.lock();
+        // Insert the certificate.
+        if let Err(e) = self.certificates.insert(certificate_id, certificate.clone()) {
+            error!("Failed to insert certificate {certificate_id} into storage - {e}");
+            return;
+        }
+        // Insert the round to certificate ID entry.
+        let mut entries = match self.rounds.get_confirmed(&round) {
+            Ok(Some(entries)) => cow_to_cloned!(entries),
+            Ok(None) => IndexSet::new(),
+            Err(e) => {
+                error!("Failed to retrieve round {round} from storage - {e}");
+                IndexSet::new()
+            }
+        };
+        entries.insert((certificate_id, batch_id, certificate.author()));
+        if let Err(e) = self.rounds.insert(round, entries) {
+            error!("Failed to insert round {round} for certificate {certificate_id} into storage - {e}");
+        }
+        // Insert the batch ID.
+        if let Err(e) = self.batch_ids.insert(batch_id, round) {
+            error!("Failed to insert batch {batch_id} into storage - {e}");
+        }
+    }
+
+    /// Removes the given certificate from the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn remove_certificate(&self, certificate: &BatchCertificate<N>) {
+        // Retrieve the round.
+        let round = certificate.round();
+        // Retrieve the certificate ID.
+        let certificate_id = certificate.id();
+        // Retrieve the batch ID.
+        let batch_id = certificate.batch_id();
+
+        // Acquire the DAG lock.
+        let _lock = self.dag_lock.lock();
+        // Remove the round to certificate ID entry.
+        match self.rounds.get_confirmed(&round) {
+            Ok(Some(entries)) => {
+                let mut entries = cow_to_cloned!(entries);
+                entries.remove(&(certificate_id, batch_id, certificate.author()));
+                // If the round is empty, remove it. Otherwise, update the round entry.
+                let result = match entries.is_empty() {
+                    true => self.rounds.remove(&round),
+                    false => self.rounds.insert(round, entries),
+                };
+                if let Err(e) = result {
+                    error!("Failed to remove certificate {certificate_id} from round {round} in storage - {e}");
+                }
+            }
+            Ok(None) => { /* no-op */ }
+            Err(e) => error!("Failed to retrieve round {round} from storage - {e}"),
+        }
+        // Remove the certificate.
+        if let Err(e) = self.certificates.remove(&certificate_id) {
+            error!("Failed to remove certificate {certificate_id} from storage - {e}");
+        }
+        // Remove the batch ID.
+        if let Err(e) = self.batch_ids.remove(&batch_id) {
+            error!("Failed to remove batch {batch_id} from storage - {e}");
+        }
+    }
+
+    /// Returns the proposal journal from storage, if one exists.
+    fn get_proposal_journal(&self) -> Option<Vec<u8>> {
+        match self.proposal_journal.get_confirmed(&PROPOSAL_JOURNAL_KEY) {
+            Ok(journal) => journal.map(|journal| cow_to_cloned!(journal)),
+            Err(error) => {
+                error!("Failed to get the proposal journal from storage - {error}");
+                None
+            }
+        }
+    }
+
+    /// Stores the given proposal journal in storage, replacing the existing journal and its signatures.
+    fn store_proposal_journal(&self, journal: Vec<u8>) -> Result<()> {
+        self.proposal_journal.insert(PROPOSAL_JOURNAL_KEY, journal)?;
+        // Remove the signatures of the previous proposal.
+        // Note: If this is interrupted, the remaining signatures do not match the batch ID of the journaled proposal.
+        let batch_ids =
+            self.proposal_signatures.keys_confirmed().map(|batch_id| cow_to_copied!(batch_id)).collect::<Vec<_>>();
+        for batch_id in batch_ids {
+            self.proposal_signatures.remove(&batch_id)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the signatures recorded for the journaled proposal with the given `batch ID`.
+    fn get_proposal_signatures(&self, batch_id: Field<N>) -> Vec<Signature<N>> {
+        match self.proposal_signatures.get_confirmed(&batch_id) {
+            Ok(signatures) => signatures.map(|signatures| cow_to_cloned!(signatures)).unwrap_or_default(),
+            Err(error) => {
+                error!("Failed to get the proposal signatures from storage - {error}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Records the given signature for the journaled proposal with the given `batch ID`.
+    ///
+    /// Note: The signatures of a proposal are recorded by a single writer, i.e. the primary that proposed it.
+    fn insert_proposal_signature(&self, batch_id: Field<N>, signature: Signature<N>) -> Result<()> {
+        let mut signatures = self.get_proposal_signatures(batch_id);
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+            self.proposal_signatures.insert(batch_id, signatures)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the `(round, author, batch ID)` entries of the batches signed by this validator, ordered by round.
+    fn signed_batches(&self) -> Vec<(u64, Address<N>, Field<N>)> {
+        let mut signed_batches = self
+            .signed_batches
+            .iter_confirmed()
+            .flat_map(|(round, entries)| {
+                let round = cow_to_copied!(round);
+                cow_to_cloned!(entries).into_iter().map(move |(author, batch_id)| (round, author, batch_id))
+            })
+            .collect::<Vec<_>>();
+        // Sort the entries by round.
+        signed_batches.sort_by_key(|(round, _, _)| *round);
+        signed_batches
+    }
+
+    /// Records that this validator signed the batch with the given `batch ID`, for the given `round` and `author`.
+    fn insert_signed_batch(&self, round: u64, author: Address<N>, batch_id: Field<N>) -> Result<()> {
+        // Acquire the signing lock.
+        let _lock = self.signing_lock.lock();
+        // Retrieve the entries for the round.
+        let mut entries = match self.signed_batches.get_confirmed(&round)? {
+            Some(entries) => cow_to_cloned!(entries),
+            None => IndexMap::new(),
+        };
+        // Ensure a different batch was not already signed for the round and author.
+        if let Some(signed_batch_id) = entries.get(&author) {
+            ensure!(
+                *signed_batch_id == batch_id,
+                "Already signed batch {signed_batch_id} for round {round} from '{author}' (refusing {batch_id})"
+            );
+            // If the batch was already signed, there is nothing to write.
+            return Ok(());
+        }
+        entries.insert(author, batch_id);
+        self.signed_batches.insert(round, entries)
+    }
+
+    /// Removes the signed batch entries for all rounds up to (and including) the given `round`,
+    /// except for the highest signed round of each author.
+    fn remove_signed_batches(&self, round: u64) {
+        // Acquire the signing lock.
+        let _lock = self.signing_lock.lock();
+        // Determine the highest signed round of each author.
+        let mut highest_rounds = HashMap::new();
+        for (signed_round, entries) in self.signed_batches.iter_confirmed() {
+            let signed_round = cow_to_copied!(signed_round);
+            for author in entries.keys() {
+                let highest_round = highest_rounds.entry(*author).or_insert(signed_round);
+                *highest_round = (*highest_round).max(signed_round);
+            }
+        }
+        // Retrieve the entries for the rounds up to the given round.
+        let rounds = self
+            .signed_batches
+            .iter_confirmed()
+            .map(|(signed_round, entries)| (cow_to_copied!(signed_round), cow_to_cloned!(entries)))
+            .filter(|(signed_round, _)| *signed_round <= round)
+            .collect::<Vec<_>>();
+        // Remove the entries for the rounds, keeping the highest signed round of each author.
+        for (signed_round, mut entries) in rounds {
+            let num_entries = entries.len();
+            entries.retain(|author, _| highest_rounds.get(author) == Some(&signed_round));
+            let result = match entries.is_empty() {
+                true => self.signed_batches.remove(&signed_round),
+                false if entries.len() < num_entries => self.signed_batches.insert(signed_round, entries),
+                false => Ok(()),
+            };
+            if let Err(e) = result {
+                error!("Failed to remove the signed batches for round {signed_round} from storage - {e}");
+            }
+        }
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
-        use snarkvm::ledger::store::cow_to_copied;
         self.transmissions.iter_confirmed().map(|(k, v)| (cow_to_copied!(k), cow_to_cloned!(v))).collect()
     }
 }
@@ -0,0 +1,232 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The conformance tests that every storage service must pass.
+
+use crate::StorageService;
+use snarkvm::{
+    ledger::narwhal::{
+        batch_certificate::test_helpers::sample_batch_certificate_for_round,
+        BatchCertificate,
+        Transmission,
+        TransmissionID,
+    },
+    prelude::{Field, PrivateKey, TestRng, Uniform},
+};
+
+use indexmap::{indexset, IndexSet};
+use std::collections::HashMap;
+
+type CurrentNetwork = snarkvm::prelude::Testnet3;
+
+/// Returns the transmissions for the given certificate.
+fn sample_transmissions(
+    certificate: &BatchCertificate<CurrentNetwork>,
+) -> HashMap<TransmissionID<CurrentNetwork>, Transmission<CurrentNetwork>> {
+    certificate
+        .transmission_ids()
+        .iter()
+        .map(|transmission_id| (*transmission_id, Transmission::Ratification))
+        .collect()
+}
+
+/// Runs the conformance tests on the given (empty) storage service.
+pub(crate) fn check_storage_service(service: &dyn StorageService<CurrentNetwork>, rng: &mut TestRng) {
+    check_transmissions(service, rng);
+    check_certificates(service, rng);
+    check_proposal_journal(service, rng);
+    check_signed_batches(service, rng);
+}
+
+/// Checks the transmission methods of the storage service.
+fn check_transmissions(service: &dyn StorageService<CurrentNetwork>, rng: &mut TestRng) {
+    // Sample a certificate and its transmissions.
+    let certificate = sample_batch_certificate_for_round(2, rng);
+    let certificate_id = certificate.id();
+    let transmission_ids = certificate.transmission_ids().clone();
+    let transmissions = sample_transmissions(&certificate);
+    assert!(!transmission_ids.is_empty());
+
+    // Ensure the transmissions are missing.
+    for transmission_id in &transmission_ids {
+        assert!(!service.contains_transmission(*transmission_id));
+        assert!(service.get_transmission(*transmission_id).is_none());
+    }
+    let missing_transmissions =
+        service.find_missing_transmissions(certificate.batch_header(), transmissions.clone()).unwrap();
+    assert_eq!(missing_transmissions, transmissions);
+    // Ensure a missing transmission must be provided.
+    assert!(service.find_missing_transmissions(certificate.batch_header(), Default::default()).is_err());
+
+    // Insert the transmissions.
+    service.insert_transmissions(certificate_id, transmission_ids.clone(), missing_transmissions);
+    for transmission_id in &transmission_ids {
+        assert!(service.contains_transmission(*transmission_id));
+        assert_eq!(service.get_transmission(*transmission_id), Some(Transmission::Ratification));
+    }
+    assert!(service.find_missing_transmissions(certificate.batch_header(), Default::default()).unwrap().is_empty());
+
+    // Insert the transmissions for another certificate ID.
+    let other_certificate_id = Field::from_u64(1);
+    service.insert_transmissions(other_certificate_id, transmission_ids.clone(), Default::default());
+    let expected = transmission_ids
+        .iter()
+        .map(|id| (*id, (Transmission::Ratification, indexset! { certificate_id, other_certificate_id })))
+        .collect::<HashMap<_, _>>();
+    assert_eq!(service.as_hashmap(), expected);
+
+    // Remove the transmissions for the first certificate ID, and ensure they remain for the other certificate ID.
+    service.remove_transmissions(&certificate_id, &transmission_ids);
+    for transmission_id in &transmission_ids {
+        assert!(service.contains_transmission(*transmission_id));
+    }
+    // Remove the transmissions for the other certificate ID, and ensure they are removed.
+    service.remove_transmissions(&other_certificate_id, &transmission_ids);
+    assert!(service.as_hashmap().is_empty());
+}
+
+/// Checks the certificate methods of the storage service.
+fn check_certificates(service: &dyn StorageService<CurrentNetwork>, rng: &mut TestRng) {
+    assert!(service.certificates().is_empty());
+
+    // Insert certificates out of round order.
+    let certificate_3 = sample_batch_certificate_for_round(3, rng);
+    let certificate_2 = sample_batch_certificate_for_round(2, rng);
+    let other_certificate_3 = sample_batch_certificate_for_round(3, rng);
+    service.insert_certificate(&certificate_3);
+    service.insert_certificate(&certificate_2);
+    service.insert_certificate(&other_certificate_3);
+    // Ensure inserting a certificate is idempotent.
+    service.insert_certificate(&certificate_2);
+
+    // Ensure the certificates are ordered by round.
+    let certificates = service.certificates();
+    assert_eq!(certificates.len(), 3);
+    assert_eq!(certificates[0], certificate_2);
+    assert_eq!(certificates[1..].iter().collect::<IndexSet<_>>(), indexset! { &certificate_3, &other_certificate_3 });
+
+    // Remove the certificates.
+    service.remove_certificate(&certificate_3);
+    assert_eq!(service.certificates(), vec![certificate_2.clone(), other_certificate_3.clone()]);
+    service.remove_certificate(&certificate_2);
+    service.remove_certificate(&other_certificate_3);
+    assert!(service.certificates().is_empty());
+    // Ensure removing a missing certificate is a no-op.
+    service.remove_certificate(&certificate_2);
+    assert!(service.certificates().is_empty());
+}
+
+/// Checks the proposal journal methods of the storage service.
+fn check_proposal_journal(service: &dyn StorageService<CurrentNetwork>, rng: &mut TestRng) {
+    assert!(service.get_proposal_journal().is_none());
+    // Store the journal.
+    service.store_proposal_journal(vec![1, 2, 3]).unwrap();
+    assert_eq!(service.get_proposal_journal(), Some(vec![1, 2, 3]));
+
+    // Sample the signatures for the journaled proposal.
+    let batch_id = Field::rand(rng);
+    let [signature_a, signature_b] =
+        [(); 2].map(|_| PrivateKey::<CurrentNetwork>::new(rng).unwrap().sign(&[batch_id], rng).unwrap());
+    assert!(service.get_proposal_signatures(batch_id).is_empty());
+    // Record the signatures, including a duplicate.
+    for signature in [signature_a, signature_b, signature_a] {
+        service.insert_proposal_signature(batch_id, signature).unwrap();
+    }
+    assert_eq!(service.get_proposal_signatures(batch_id), vec![signature_a, signature_b]);
+    assert!(service.get_proposal_signatures(Field::rand(rng)).is_empty());
+
+    // Ensure the journal is replaced, along with its signatures.
+    service.store_proposal_journal(vec![4]).unwrap();
+    assert_eq!(service.get_proposal_journal(), Some(vec![4]));
+    assert!(service.get_proposal_signatures(batch_id).is_empty());
+}
+
+/// Checks the signed batch methods of the storage service.
+fn check_signed_batches(service: &dyn StorageService<CurrentNetwork>, rng: &mut TestRng) {
+    assert!(service.signed_batches().is_empty());
+
+    // Sample the authors and batch IDs.
+    let [certificate_a, certificate_b, certificate_c] =
+        [3, 2, 4].map(|round| sample_batch_certificate_for_round(round, rng));
+    let entry = |certificate: &BatchCertificate<CurrentNetwork>| {
+        (certificate.round(), certificate.author(), certificate.batch_id())
+    };
+
+    // Insert the signed batches out of round order.
+    for certificate in [&certificate_a, &certificate_b, &certificate_c] {
+        let (round, author, batch_id) = entry(certificate);
+        service.insert_signed_batch(round, author, batch_id).unwrap();
+    }
+    // Ensure inserting the same signed batch again succeeds.
+    let (round, author, batch_id) = entry(&certificate_a);
+    service.insert_signed_batch(round, author, batch_id).unwrap();
+    // Ensure a different batch for the same round and author is refused.
+    assert!(service.insert_signed_batch(round, author, Field::from_u64(1)).is_err());
+    // Ensure a batch from the same author for another round is accepted.
+    service.insert_signed_batch(round + 10, author, Field::from_u64(1)).unwrap();
+
+    // Ensure the signed batches are ordered by round.
+    let signed_batches = service.signed_batches();
+    assert_eq!(
+        signed_batches,
+        vec![
+            entry(&certificate_b),
+            entry(&certificate_a),
+            entry(&certificate_c),
+            (round + 10, author, Field::from_u64(1))
+        ]
+    );
+
+    // Remove the signed batches up to round 3, which keeps the highest signed round of each author.
+    service.remove_signed_batches(3);
+    assert_eq!(
+        service.signed_batches(),
+        vec![entry(&certificate_b), entry(&certificate_c), (round + 10, author, Field::from_u64(1))]
+    );
+    // Ensure a signed batch is pruned once the author has signed a higher round.
+    let (round_b, author_b, _) = entry(&certificate_b);
+    service.insert_signed_batch(round_b + 3, author_b, Field::from_u64(2)).unwrap();
+    service.remove_signed_batches(u64::MAX);
+    assert_eq!(
+        service.signed_batches(),
+        vec![
+            entry(&certificate_c),
+            (round_b + 3, author_b, Field::from_u64(2)),
+            (round + 10, author, Field::from_u64(1))
+        ]
+    );
+}
+
+#[cfg(feature = "memory")]
+#[test]
+fn test_memory_conformance() {
+    let service = crate::BFTMemoryService::new();
+    check_storage_service(&service, &mut TestRng::default());
+}
+
+#[cfg(feature = "persistent")]
+#[test]
+fn test_persistent_conformance() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let service = crate::BFTPersistentStorage::open_testing(temp_dir.path().to_owned(), None).unwrap();
+    check_storage_service(&service, &mut TestRng::default());
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn test_file_conformance() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let service = crate::BFTFileStorage::open(temp_dir.path()).unwrap();
+    check_storage_service(&service, &mut TestRng::default());
+}
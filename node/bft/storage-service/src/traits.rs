@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use snarkvm::{
-    ledger::narwhal::{BatchHeader, Transmission, TransmissionID},
-    prelude::{Field, Network, Result},
+    ledger::narwhal::{BatchCertificate, BatchHeader, Transmission, TransmissionID},
+    prelude::{Address, Field, Network, Result, Signature},
 };
 
 use indexmap::IndexSet;
@@ -48,6 +48,45 @@ pub trait StorageService<N: Network>: Debug + Send + Sync {
     /// If the transmission no longer references any certificate IDs, the entry is removed from storage.
     fn remove_transmissions(&self, certificate_id: &Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>);
 
+    /// Returns the certificates in storage, ordered by round.
+    ///
+    /// This is used to restore the certificate DAG when the node restarts.
+    fn certificates(&self) -> Vec<BatchCertificate<N>>;
+
+    /// Inserts the given certificate into the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn insert_certificate(&self, certificate: &BatchCertificate<N>);
+
+    /// Removes the given certificate from the `rounds`, `certificates`, and `batch_ids` maps in storage.
+    fn remove_certificate(&self, certificate: &BatchCertificate<N>);
+
+    /// Returns the proposal journal from storage, if one exists.
+    fn get_proposal_journal(&self) -> Option<Vec<u8>>;
+
+    /// Stores the given proposal journal in storage, replacing the existing journal and its signatures.
+    ///
+    /// This method returns an error if the journal was not durably written,
+    /// in which case the caller must not act upon the journaled state.
+    fn store_proposal_journal(&self, journal: Vec<u8>) -> Result<()>;
+
+    /// Returns the signatures recorded for the journaled proposal with the given `batch ID`.
+    fn get_proposal_signatures(&self, batch_id: Field<N>) -> Vec<Signature<N>>;
+
+    /// Records the given signature for the journaled proposal with the given `batch ID`.
+    fn insert_proposal_signature(&self, batch_id: Field<N>, signature: Signature<N>) -> Result<()>;
+
+    /// Returns the `(round, author, batch ID)` entries of the batches signed by this validator, ordered by round.
+    fn signed_batches(&self) -> Vec<(u64, Address<N>, Field<N>)>;
+
+    /// Records that this validator signed the batch with the given `batch ID`, for the given `round` and `author`.
+    ///
+    /// This method returns an error if a different batch was already signed for the given `round` and `author`,
+    /// or if the entry was not durably written, in which case the caller must not sign the batch.
+    fn insert_signed_batch(&self, round: u64, author: Address<N>, batch_id: Field<N>) -> Result<()>;
+
+    /// Removes the signed batch entries for all rounds up to (and including) the given `round`,
+    /// except for the highest signed round of each author, which is kept for slashing protection.
+    fn remove_signed_batches(&self, round: u64);
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>;
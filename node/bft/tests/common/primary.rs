@@ -20,6 +20,7 @@ use crate::common::{
 use snarkos_account::Account;
 use snarkos_node_bft::{
     helpers::{init_primary_channels, PrimarySender, Storage},
+    BFTConfig,
     Primary,
     BFT,
     MAX_BATCH_DELAY_IN_MS,
@@ -56,6 +57,7 @@ use std::{
     time::Duration,
 };
 
+use aleo_std::StorageMode;
 use indexmap::IndexMap;
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -155,11 +157,28 @@ impl TestNetwork {
             let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), MAX_GC_ROUNDS);
 
             let (primary, bft) = if config.bft {
-                let bft = BFT::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let bft = BFT::<CurrentNetwork>::new(
+                    BFTConfig::default(),
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    &[],
+                    Some(id as u16),
+                )
+                .unwrap();
                 (bft.primary().clone(), Some(bft))
             } else {
-                let primary =
-                    Primary::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let primary = Primary::<CurrentNetwork>::new(
+                    BFTConfig::default(),
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    &[],
+                    Some(id as u16),
+                )
+                .unwrap();
                 (primary, None)
             };
 
@@ -389,5 +408,5 @@ fn genesis_ledger(
         })
         .clone();
     // Initialize the ledger with the genesis block.
-    CurrentLedger::load(block, None).unwrap()
+    CurrentLedger::load(block, StorageMode::Production).unwrap()
 }
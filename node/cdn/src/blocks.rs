@@ -16,6 +16,7 @@
 // https://github.com/rust-lang/rust-clippy/issues/6446
 #![allow(clippy::await_holding_lock)]
 
+use crate::{mirrors::Mirrors, Manifest, ManifestSource};
 use snarkvm::prelude::{
     block::Block,
     store::{cow_to_copied, ConsensusStorage},
@@ -40,30 +41,53 @@ use std::{
 };
 
 /// The number of blocks per file.
-const BLOCKS_PER_FILE: u32 = 50;
+pub const BLOCKS_PER_FILE: u32 = 50;
 /// The desired number of concurrent requests to the CDN.
 const CONCURRENT_REQUESTS: u32 = 16;
 /// Maximum number of pending sync blocks.
 const MAXIMUM_PENDING_BLOCKS: u32 = BLOCKS_PER_FILE * CONCURRENT_REQUESTS * 2;
-/// Maximum number of attempts for a request to the CDN.
+/// Maximum number of attempts for a request to the CDN, where each attempt tries every mirror.
 const MAXIMUM_REQUEST_ATTEMPTS: u8 = 10;
+/// The maximum duration of a connection attempt to a mirror, after which the mirror is failed over.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The maximum duration of a request to a mirror, including the download of a bundle.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
 /// The supported network.
 const NETWORK_ID: u16 = 3;
 
-/// Loads blocks from a CDN into the ledger.
+/// The configuration of the CDN to sync blocks from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CdnConfig {
+    /// The base URLs of the mirrors of the CDN.
+    pub mirrors: Vec<String>,
+    /// The source of the manifest of the CDN.
+    pub manifest: ManifestSource,
+}
+
+/// A representation of the 'latest.json' file object.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LatestState {
+    pub exclusive_height: u32,
+    pub inclusive_height: u32,
+    pub hash: String,
+}
+
+/// Loads blocks from a CDN into the ledger, up to the given (exclusive) end height, or to the CDN height.
 ///
 /// On success, this function returns the completed block height.
 /// On failure, this function returns the last successful block height (if any), along with the error.
 pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
-    base_url: &str,
+    base_urls: &[String],
+    manifest_source: &ManifestSource,
     ledger: Ledger<N, C>,
+    end_height: Option<u32>,
     shutdown: Arc<AtomicBool>,
 ) -> Result<u32, (u32, anyhow::Error)> {
     // Fetch the node height.
     let start_height = ledger.latest_height() + 1;
     // Load the blocks from the CDN into the ledger.
     let ledger_clone = ledger.clone();
-    let result = load_blocks(base_url, start_height, None, shutdown, move |block: Block<N>| {
+    let result = load_blocks(base_urls, manifest_source, start_height, end_height, shutdown, move |block: Block<N>| {
         ledger_clone.advance_to_next_block(&block)
     })
     .await;
@@ -80,7 +104,8 @@ pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
             // Retrieve the latest height, according to the ledger.
             let node_height = cow_to_copied!(ledger.vm().block_store().heights().max().unwrap_or_default());
             // Check the integrity of the latest height.
-            if &node_height != completed_height {
+            // Note: The ledger may be ahead of the sync height, if the peers added the next blocks first.
+            if node_height < *completed_height {
                 return Err((*completed_height, anyhow!("The ledger height does not match the last sync height")));
             }
 
@@ -98,10 +123,16 @@ pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
 
 /// Loads blocks from a CDN and process them with the given function.
 ///
+/// The base URLs are mirrors of the CDN, and may also be `file://` paths to local directories of block bundles.
+/// The block bundles are downloaded concurrently from the mirrors, in proportion to their observed throughput,
+/// and a failed request is retried from another mirror. The bundles are checked against the manifest
+/// from the given source, and the `http(s)` mirrors listed in the manifest are used as well.
+///
 /// On success, this function returns the completed block height.
 /// On failure, this function returns the last successful block height (if any), along with the error.
 pub async fn load_blocks<N: Network>(
-    base_url: &str,
+    base_urls: &[String],
+    manifest_source: &ManifestSource,
     start_height: u32,
     end_height: Option<u32>,
     shutdown: Arc<AtomicBool>,
@@ -113,15 +144,15 @@ pub async fn load_blocks<N: Network>(
     }
 
     // Create a Client to maintain a connection pool throughout the sync.
-    let client = match Client::builder().build() {
+    let client = match Client::builder().connect_timeout(CONNECT_TIMEOUT).timeout(REQUEST_TIMEOUT).build() {
         Ok(client) => client,
         Err(error) => {
             return Err((start_height.saturating_sub(1), anyhow!("Failed to create a CDN request client - {error}")));
         }
     };
 
-    // Fetch the CDN height.
-    let cdn_height = match cdn_height::<BLOCKS_PER_FILE>(&client, base_url).await {
+    // Fetch the CDN height, from the first mirror that responds.
+    let cdn_height = match mirrors_cdn_height(&client, base_urls).await {
         Ok(cdn_height) => cdn_height,
         Err(error) => return Err((start_height, error)),
     };
@@ -133,6 +164,14 @@ pub async fn load_blocks<N: Network>(
         ));
     }
 
+    // Load the manifest of the CDN.
+    let manifest = match load_manifest(&client, base_urls, manifest_source).await {
+        Ok(manifest) => manifest,
+        Err(error) => return Err((start_height, error)),
+    };
+    // Prepare the mirrors, including the HTTP(S) mirrors listed in the manifest.
+    let mirrors = Arc::new(Mirrors::new(base_urls.iter().chain(manifest.http_mirrors()).cloned()));
+
     // If the end height is not specified, set it to the CDN height.
     // If the end height is greater than the CDN height, set the end height to the CDN height.
     let end_height = cmp::min(end_height.unwrap_or(cdn_height), cdn_height);
@@ -155,97 +194,133 @@ pub async fn load_blocks<N: Network>(
 
     // A collection of downloaded blocks pending insertion into the ledger.
     let pending_blocks: Arc<Mutex<Vec<Block<N>>>> = Default::default();
+    // The indicator of whether the download of a bundle failed on every attempt.
+    let failed: Arc<AtomicBool> = Default::default();
 
     // Start a timer.
     let timer = Instant::now();
 
     // Spawn a background task responsible for concurrent downloads.
     let pending_blocks_clone = pending_blocks.clone();
-    let base_url = base_url.to_owned();
+    let mirrors_clone = mirrors.clone();
+    let failed_clone = failed.clone();
     let shutdown_clone = shutdown.clone();
     tokio::spawn(async move {
-        download_block_bundles(client, base_url, cdn_start, cdn_end, pending_blocks_clone, shutdown_clone).await;
+        download_block_bundles(
+            client,
+            mirrors_clone,
+            manifest,
+            cdn_start,
+            cdn_end,
+            pending_blocks_clone,
+            failed_clone,
+            shutdown_clone,
+        )
+        .await;
     });
 
     // A loop for inserting the pending blocks into the ledger.
-    let mut current_height = start_height.saturating_sub(1);
-    while current_height < end_height - 1 {
+    // Note: The next height is tracked, rather than the last inserted height, as the sync may start at genesis.
+    let mut next_height = start_height;
+    while next_height < end_height {
         // If we are instructed to shut down, abort.
         if shutdown.load(Ordering::Relaxed) {
-            info!("Stopping block sync at {} - shutting down", current_height);
-            // We can shut down cleanly from here, as the node hasn't been started yet.
-            std::process::exit(0);
+            info!("Stopping block sync at {} - shutting down", next_height.saturating_sub(1));
+            return Err((next_height.saturating_sub(1), anyhow!("The block sync was interrupted by a shutdown")));
         }
 
-        let mut candidate_blocks = pending_blocks.lock();
-
-        // Obtain the height of the nearest pending block.
-        let Some(next_height) = candidate_blocks.first().map(|b| b.height()) else {
-            debug!("No pending blocks yet");
-            drop(candidate_blocks);
-            tokio::time::sleep(Duration::from_secs(3)).await;
-            continue;
+        // Obtain the next blocks to insert, or the duration to wait for them.
+        // Note: The lock is released within this scope, so that it is not held across an await point.
+        let next_blocks = {
+            let mut candidate_blocks = pending_blocks.lock();
+            // Obtain the height of the nearest pending block.
+            match candidate_blocks.first().map(|b| b.height()) {
+                None => {
+                    // If the remaining blocks can no longer be downloaded, abort.
+                    if failed.load(Ordering::Relaxed) {
+                        let error = anyhow!("Failed to download block {next_height} from the CDN");
+                        return Err((next_height.saturating_sub(1), error));
+                    }
+                    debug!("No pending blocks yet");
+                    Err(Duration::from_secs(3))
+                }
+                // Wait if the nearest pending block is not the next one that can be inserted.
+                Some(height) if height > next_height => {
+                    // If the gap can no longer be filled, abort.
+                    if failed.load(Ordering::Relaxed) {
+                        let error = anyhow!("Failed to download block {next_height} from the CDN");
+                        return Err((next_height.saturating_sub(1), error));
+                    }
+                    // There is a gap in pending blocks, we need to wait.
+                    debug!("Waiting for the first relevant blocks ({} pending)", candidate_blocks.len());
+                    Err(Duration::from_secs(1))
+                }
+                Some(_) => {
+                    // Obtain the first BLOCKS_PER_FILE applicable blocks.
+                    // Note: The bundle at the end of the sync may hold fewer blocks, so this does not assume
+                    // a full bundle.
+                    let num_blocks = candidate_blocks.len().min(BLOCKS_PER_FILE as usize);
+                    let retained_blocks = candidate_blocks.split_off(num_blocks);
+                    Ok(std::mem::replace(&mut *candidate_blocks, retained_blocks))
+                }
+            }
+        };
+        let next_blocks = match next_blocks {
+            Ok(next_blocks) => next_blocks,
+            Err(delay) => {
+                tokio::time::sleep(delay).await;
+                continue;
+            }
         };
-
-        // Wait if the nearest pending block is not the next one that can be inserted.
-        if next_height > current_height + 1 {
-            // There is a gap in pending blocks, we need to wait.
-            debug!("Waiting for the first relevant blocks ({} pending)", candidate_blocks.len());
-            drop(candidate_blocks);
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            continue;
-        }
-
-        // Obtain the first BLOCKS_PER_FILE applicable blocks.
-        let retained_blocks = candidate_blocks.split_off(BLOCKS_PER_FILE as usize);
-        let next_blocks = std::mem::replace(&mut *candidate_blocks, retained_blocks);
-        drop(candidate_blocks);
 
         // Attempt to advance the ledger using the CDN block bundle.
         let mut process_clone = process.clone();
+        let mirrors_clone = mirrors.clone();
         let shutdown_clone = shutdown.clone();
-        current_height = tokio::task::spawn_blocking(move || {
+        next_height = tokio::task::spawn_blocking(move || {
             for block in next_blocks.into_iter().filter(|b| (start_height..end_height).contains(&b.height())) {
                 // If we are instructed to shut down, abort.
                 if shutdown_clone.load(Ordering::Relaxed) {
-                    info!("Stopping block sync at {} - the node is shutting down", current_height);
-                    // We can shut down cleanly from here, as the node hasn't been started yet.
-                    std::process::exit(0);
+                    break;
                 }
 
                 // Insert the block into the ledger.
                 process_clone(block)?;
-                current_height += 1;
+                next_height += 1;
 
                 // Log the progress.
-                log_progress::<BLOCKS_PER_FILE>(timer, current_height, cdn_start, cdn_end, "block");
+                log_progress::<BLOCKS_PER_FILE>(timer, next_height - 1, cdn_start, cdn_end, "block", &mirrors_clone);
             }
 
-            Ok(current_height)
+            Ok(next_height)
         })
         .await
-        .map_err(|e| (current_height, e.into()))?
-        .map_err(|e| (current_height, e))?;
+        .map_err(|e| (next_height.saturating_sub(1), e.into()))?
+        .map_err(|e| (next_height.saturating_sub(1), e))?;
     }
 
-    Ok(current_height)
+    Ok(next_height.saturating_sub(1))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn download_block_bundles<N: Network>(
     client: Client,
-    base_url: String,
+    mirrors: Arc<Mirrors>,
+    manifest: Manifest,
     cdn_start: u32,
     cdn_end: u32,
     pending_blocks: Arc<Mutex<Vec<Block<N>>>>,
+    failed: Arc<AtomicBool>,
     shutdown: Arc<AtomicBool>,
 ) {
     // Keep track of the number of concurrent requests.
     let active_requests: Arc<AtomicU32> = Default::default();
+    let manifest = Arc::new(manifest);
 
     let mut start = cdn_start;
-    while start < cdn_end - 1 {
-        // If we are instructed to shut down, stop downloading.
-        if shutdown.load(Ordering::Relaxed) {
+    while start < cdn_end {
+        // If we are instructed to shut down, or a bundle could not be downloaded, stop downloading.
+        if shutdown.load(Ordering::Relaxed) || failed.load(Ordering::Relaxed) {
             break;
         }
 
@@ -276,10 +351,11 @@ async fn download_block_bundles<N: Network>(
             }
 
             let client_clone = client.clone();
-            let base_url_clone = base_url.clone();
+            let mirrors_clone = mirrors.clone();
+            let manifest_clone = manifest.clone();
             let pending_blocks_clone = pending_blocks.clone();
             let active_requests_clone = active_requests.clone();
-            let shutdown_clone = shutdown.clone();
+            let failed_clone = failed.clone();
             tokio::spawn(async move {
                 // Increment the number of active requests.
                 active_requests_clone.fetch_add(1, Ordering::Relaxed);
@@ -287,17 +363,46 @@ async fn download_block_bundles<N: Network>(
                 let ctx = format!("blocks {start} to {end}");
                 debug!("Requesting {ctx} (of {cdn_end})");
 
-                // Prepare the URL.
-                let blocks_url = format!("{base_url_clone}/{start}.{end}.blocks");
                 let ctx = format!("blocks {start} to {end}");
-                // Download blocks, retrying on failure.
+                // Download blocks, failing over to another mirror on failure, and retrying once every mirror failed.
                 let mut attempts = 0;
+                let mut failed_mirrors = Vec::new();
                 let request_time = Instant::now();
 
                 loop {
-                    // Fetch the blocks.
-                    match cdn_get(client_clone.clone(), &blocks_url, &ctx).await {
-                        Ok::<Vec<Block<N>>, _>(blocks) => {
+                    // Select the mirror to download from.
+                    let Some(index) = mirrors_clone.select(&failed_mirrors) else {
+                        // Increment the attempt counter, and wait with a linear backoff, or abort in
+                        // case the maximum number of attempts has been breached.
+                        attempts += 1;
+                        if attempts > MAXIMUM_REQUEST_ATTEMPTS {
+                            warn!("Maximum number of requests for {ctx} reached - stopping the CDN sync...");
+                            failed_clone.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                        tokio::time::sleep(Duration::from_secs(attempts as u64 * 10)).await;
+                        warn!("Retrying {ctx} from all mirrors ({attempts} attempt(s) so far)");
+                        failed_mirrors.clear();
+                        continue;
+                    };
+                    // Prepare the URL.
+                    let blocks_url = format!("{}/{start}.{end}.blocks", mirrors_clone.url(index));
+                    let attempt_time = Instant::now();
+                    // Fetch and verify the blocks.
+                    match cdn_get_bundle::<N>(
+                        &client_clone,
+                        &blocks_url,
+                        start,
+                        end,
+                        cdn_end,
+                        manifest_clone.clone(),
+                        &ctx,
+                    )
+                    .await
+                    {
+                        Ok((blocks, num_bytes)) => {
+                            // Record the throughput of the mirror.
+                            mirrors_clone.record_success(index, num_bytes, attempt_time.elapsed());
                             // Keep the collection of pending blocks sorted by the height.
                             let mut pending_blocks = pending_blocks_clone.lock();
                             for block in blocks {
@@ -310,16 +415,10 @@ async fn download_block_bundles<N: Network>(
                             break;
                         }
                         Err(error) => {
-                            // Increment the attempt counter, and wait with a linear backoff, or abort in
-                            // case the maximum number of attempts has been breached.
-                            attempts += 1;
-                            if attempts > MAXIMUM_REQUEST_ATTEMPTS {
-                                warn!("Maximum number of requests to {blocks_url} reached - shutting down...");
-                                shutdown_clone.store(true, Ordering::Relaxed);
-                                break;
-                            }
-                            tokio::time::sleep(Duration::from_secs(attempts as u64 * 10)).await;
-                            warn!("{error} - retrying ({attempts} attempt(s) so far)");
+                            // Skip the mirror for this request, until every mirror failed.
+                            mirrors_clone.record_failure(index);
+                            failed_mirrors.push(index);
+                            warn!("{error} - failing over to another mirror");
                         }
                     }
                 }
@@ -339,35 +438,79 @@ async fn download_block_bundles<N: Network>(
     debug!("Finished network requests to the CDN");
 }
 
+/// Loads the manifest of the CDN from the given source.
+///
+/// A manifest from the mirrors is fetched from the first mirror that serves a valid one (matching the checksum,
+/// if one is given). If no mirror serves a manifest, the bundles are not checked against a manifest, unless
+/// the manifest is pinned to a checksum.
+async fn load_manifest(client: &Client, base_urls: &[String], source: &ManifestSource) -> Result<Manifest> {
+    // If the manifest is a local file, read it.
+    if let ManifestSource::File(path) = source {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(error) => bail!("Failed to read the CDN manifest at '{}' - {error}", path.display()),
+        };
+        return match source.decode(&bytes) {
+            Ok(manifest) => Ok(manifest),
+            Err(error) => bail!("Failed to parse the CDN manifest at '{}' - {error}", path.display()),
+        };
+    }
+    // Fetch the manifest from the first mirror that serves a valid one.
+    for base_url in base_urls {
+        let bytes = match cdn_fetch(client, &format!("{base_url}/manifest.json")).await {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                debug!("Failed to fetch the CDN manifest - {error} (from {base_url})");
+                continue;
+            }
+        };
+        match source.decode(&bytes) {
+            Ok(manifest) => return Ok(manifest),
+            Err(error) => warn!("Failed to parse the CDN manifest - {error} (from {base_url})"),
+        }
+    }
+    // If the manifest is pinned, ensure it was found.
+    if source.is_trusted() {
+        bail!("None of the CDN mirrors serves the pinned manifest");
+    }
+    debug!("The block bundles will not be checked against a manifest");
+    Ok(Manifest::default())
+}
+
+/// Retrieves the CDN height from the first of the given mirrors that responds.
+///
+/// This is the (exclusive) height up to which `sync_ledger_with_cdn` loads blocks from the CDN.
+pub async fn fetch_cdn_height(base_urls: &[String]) -> Result<u32> {
+    // Create a Client for the request.
+    let client = match Client::builder().connect_timeout(CONNECT_TIMEOUT).timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(error) => bail!("Failed to create a CDN request client - {error}"),
+    };
+    mirrors_cdn_height(&client, base_urls).await
+}
+
+/// Retrieves the CDN height from the first of the given mirrors that responds.
+async fn mirrors_cdn_height(client: &Client, base_urls: &[String]) -> Result<u32> {
+    let mut result = Err(anyhow!("No CDN mirrors were provided"));
+    for base_url in base_urls {
+        result = cdn_height::<BLOCKS_PER_FILE>(client, base_url).await;
+        match &result {
+            Ok(_) => break,
+            Err(error) => warn!("{error} (from {base_url})"),
+        }
+    }
+    result
+}
+
 /// Retrieves the CDN height with the given base URL.
 ///
 /// Note: This function decrements the tip by a few blocks, to ensure the
 /// tip is not on a block that is not yet available on the CDN.
 async fn cdn_height<const BLOCKS_PER_FILE: u32>(client: &Client, base_url: &str) -> Result<u32> {
-    // A representation of the 'latest.json' file object.
-    #[derive(Deserialize, Serialize, Debug)]
-    struct LatestState {
-        exclusive_height: u32,
-        inclusive_height: u32,
-        hash: String,
-    }
     // Prepare the URL.
     let latest_json_url = format!("{base_url}/latest.json");
-    // Send the request.
-    let response = match client.get(latest_json_url).send().await {
-        Ok(response) => response,
-        Err(error) => bail!("Failed to fetch the CDN height - {error}"),
-    };
-    // Parse the response.
-    let bytes = match response.bytes().await {
-        Ok(bytes) => bytes,
-        Err(error) => bail!("Failed to parse the CDN height response - {error}"),
-    };
-    // Parse the bytes for the string.
-    let latest_state_string = match bincode::deserialize::<String>(&bytes) {
-        Ok(string) => string,
-        Err(error) => bail!("Failed to deserialize the CDN height response - {error}"),
-    };
+    // Fetch the string.
+    let latest_state_string = cdn_get::<String>(client.clone(), &latest_json_url, "the CDN height").await?;
     // Parse the string for the tip.
     let tip = match serde_json::from_str::<LatestState>(&latest_state_string) {
         Ok(latest) => latest.exclusive_height,
@@ -382,14 +525,9 @@ async fn cdn_height<const BLOCKS_PER_FILE: u32>(client: &Client, base_url: &str)
 /// Retrieves the objects from the CDN with the given URL.
 async fn cdn_get<T: 'static + DeserializeOwned + Send>(client: Client, url: &str, ctx: &str) -> Result<T> {
     // Fetch the bytes from the given URL.
-    let response = match client.get(url).send().await {
-        Ok(response) => response,
-        Err(error) => bail!("Failed to fetch {ctx} - {error}"),
-    };
-    // Parse the response.
-    let bytes = match response.bytes().await {
+    let bytes = match cdn_fetch(&client, url).await {
         Ok(bytes) => bytes,
-        Err(error) => bail!("Failed to parse {ctx} - {error}"),
+        Err(error) => bail!("Failed to fetch {ctx} - {error}"),
     };
     // Parse the objects.
     match tokio::task::spawn_blocking(move || bincode::deserialize::<T>(&bytes)).await {
@@ -399,6 +537,45 @@ async fn cdn_get<T: 'static + DeserializeOwned + Send>(client: Client, url: &str
     }
 }
 
+/// Retrieves the block bundle from the CDN with the given URL, and verifies it against the manifest,
+/// given the (exclusive) end height of the sync.
+///
+/// On success, this function returns the blocks, along with the size of the bundle in bytes.
+async fn cdn_get_bundle<N: Network>(
+    client: &Client,
+    url: &str,
+    start: u32,
+    end: u32,
+    sync_end: u32,
+    manifest: Arc<Manifest>,
+    ctx: &str,
+) -> Result<(Vec<Block<N>>, usize)> {
+    // Fetch the bytes from the given URL.
+    let bytes = match cdn_fetch(client, url).await {
+        Ok(bytes) => bytes,
+        Err(error) => bail!("Failed to fetch {ctx} - {error}"),
+    };
+    let num_bytes = bytes.len();
+    // Verify the blocks.
+    match tokio::task::spawn_blocking(move || manifest.verify_bundle(start, end, sync_end, &bytes)).await {
+        Ok(Ok(blocks)) => Ok((blocks, num_bytes)),
+        Ok(Err(error)) => bail!("Failed to verify {ctx} from {url} - {error}"),
+        Err(error) => bail!("Failed to join task for {ctx} - {error}"),
+    }
+}
+
+/// Fetches the bytes from the given URL, which may be a `file://` path.
+async fn cdn_fetch(client: &Client, url: &str) -> Result<Vec<u8>> {
+    // If the URL is a local path, read the file.
+    if let Some(path) = url.strip_prefix("file://") {
+        return Ok(tokio::fs::read(path).await?);
+    }
+    // Send the request.
+    let response = client.get(url).send().await?.error_for_status()?;
+    // Parse the response.
+    Ok(response.bytes().await?.to_vec())
+}
+
 /// Logs the progress of the sync.
 fn log_progress<const OBJECTS_PER_FILE: u32>(
     timer: Instant,
@@ -406,11 +583,12 @@ fn log_progress<const OBJECTS_PER_FILE: u32>(
     cdn_start: u32,
     mut cdn_end: u32,
     object_name: &str,
+    mirrors: &Mirrors,
 ) {
     // Subtract 1, as the end of the range is exclusive.
-    cdn_end -= 1;
+    cdn_end = cdn_end.saturating_sub(1);
     // Compute the percentage completed.
-    let percentage = current_index * 100 / cdn_end;
+    let percentage = current_index * 100 / cdn_end.max(1);
     // Compute the number of files processed so far.
     let num_files_done = 1 + (current_index - cdn_start) / OBJECTS_PER_FILE;
     // Compute the number of files remaining.
@@ -421,8 +599,8 @@ fn log_progress<const OBJECTS_PER_FILE: u32>(
     let slowdown = 100 * num_files_remaining as u128;
     // Compute the time remaining (in millis).
     let time_remaining = num_files_remaining as u128 * millis_per_file + slowdown;
-    // Prepare the estimate message (in secs).
-    let estimate = format!("(est. {} minutes remaining)", time_remaining / (60 * 1000));
+    // Prepare the estimate message (in secs), along with the throughput of the mirrors.
+    let estimate = format!("(est. {} minutes remaining, {})", time_remaining / (60 * 1000), mirrors.summary());
     // Log the progress.
     info!("Synced up to {object_name} {current_index} of {cdn_end} - {percentage}% complete {}", estimate.dimmed());
 }
@@ -430,13 +608,18 @@ fn log_progress<const OBJECTS_PER_FILE: u32>(
 #[cfg(test)]
 mod tests {
     use crate::{
-        blocks::{cdn_get, cdn_height, log_progress, BLOCKS_PER_FILE},
+        blocks::{cdn_get, cdn_height, load_manifest, log_progress, LatestState, BLOCKS_PER_FILE},
         load_blocks,
+        mirrors::Mirrors,
+        BundleEntry,
+        Manifest,
+        ManifestSource,
     };
-    use snarkvm::prelude::{block::Block, Testnet3};
+    use snarkvm::prelude::{block::Block, FromBytes, Network, Testnet3};
 
     use parking_lot::RwLock;
-    use std::{sync::Arc, time::Instant};
+    use sha2::{Digest, Sha256};
+    use std::{path::Path, sync::Arc, time::Instant};
 
     type CurrentNetwork = Testnet3;
 
@@ -452,7 +635,10 @@ mod tests {
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            let completed_height = load_blocks(TEST_BASE_URL, start, end, Default::default(), process).await.unwrap();
+            let completed_height =
+                load_blocks(&[TEST_BASE_URL.to_string()], &Default::default(), start, end, Default::default(), process)
+                    .await
+                    .unwrap();
             assert_eq!(blocks.read().len(), expected);
             if expected > 0 {
                 assert_eq!(blocks.read().last().unwrap().height(), completed_height);
@@ -492,6 +678,99 @@ mod tests {
         check_load_blocks(start_height, end_height, 188);
     }
 
+    /// Writes the given bundle of blocks 0 to 50 to a local CDN directory, and returns its URL.
+    fn write_cdn_directory(directory: &Path, bundle: &[u8], manifest: Option<&Manifest>) -> String {
+        std::fs::write(directory.join(format!("0.{BLOCKS_PER_FILE}.blocks")), bundle).unwrap();
+        // Write the 'latest.json' file.
+        let latest = LatestState {
+            exclusive_height: BLOCKS_PER_FILE,
+            inclusive_height: BLOCKS_PER_FILE - 1,
+            hash: Default::default(),
+        };
+        let latest = bincode::serialize(&serde_json::to_string(&latest).unwrap()).unwrap();
+        std::fs::write(directory.join("latest.json"), latest).unwrap();
+        // Write the 'manifest.json' file.
+        if let Some(manifest) = manifest {
+            let manifest = bincode::serialize(&serde_json::to_string(manifest).unwrap()).unwrap();
+            std::fs::write(directory.join("manifest.json"), manifest).unwrap();
+        }
+        format!("file://{}", directory.display())
+    }
+
+    #[test]
+    fn test_load_blocks_from_directory() {
+        // Write the genesis block as a bundle to a local directory.
+        let directory = tempfile::tempdir().unwrap();
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let bundle = bincode::serialize(&vec![genesis.clone()]).unwrap();
+        let base_url = write_cdn_directory(directory.path(), &bundle, None);
+
+        let blocks = Arc::new(RwLock::new(Vec::new()));
+        let blocks_clone = blocks.clone();
+        let process = move |block: Block<CurrentNetwork>| {
+            blocks_clone.write().push(block);
+            Ok(())
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let client = reqwest::Client::new();
+            assert_eq!(cdn_height::<BLOCKS_PER_FILE>(&client, &base_url).await.unwrap(), BLOCKS_PER_FILE);
+            // Ensure the blocks are loaded, as the sync ends inside the bundle.
+            let completed_height =
+                load_blocks(&[base_url], &Default::default(), 0, Some(1), Default::default(), process).await.unwrap();
+            assert_eq!(completed_height, 0);
+            assert_eq!(*blocks.read(), vec![genesis]);
+        });
+    }
+
+    #[test]
+    fn test_load_blocks_from_mirror() {
+        // Sample a bundle of the genesis block, and its corrupted copy.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let bundle = bincode::serialize(&vec![genesis.clone()]).unwrap();
+        let mut corrupted = bundle.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+
+        // Write the corrupted bundle to the CDN, along with a manifest that lists a local mirror.
+        let mut manifest = Manifest { mirrors: vec!["file:///etc".to_string()], ..Default::default() };
+        manifest.bundles.insert(0, BundleEntry::new(BLOCKS_PER_FILE, &bundle, &[genesis.clone()]).unwrap());
+        let directory = tempfile::tempdir().unwrap();
+        let base_url = write_cdn_directory(directory.path(), &corrupted, Some(&manifest));
+        // Write the bundle to the mirror, along with a manifest that cannot be parsed.
+        let mirror_directory = tempfile::tempdir().unwrap();
+        let mirror_url = write_cdn_directory(mirror_directory.path(), &bundle, None);
+        std::fs::write(mirror_directory.path().join("manifest.json"), b"invalid").unwrap();
+
+        let blocks = Arc::new(RwLock::new(Vec::new()));
+        let blocks_clone = blocks.clone();
+        let process = move |block: Block<CurrentNetwork>| {
+            blocks_clone.write().push(block);
+            Ok(())
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let client = reqwest::Client::new();
+            let base_urls = [mirror_url.clone(), base_url.clone()];
+            // Ensure the manifest is loaded from the next mirror if a mirror serves an invalid one.
+            assert_eq!(load_manifest(&client, &base_urls, &ManifestSource::Mirrors).await.unwrap(), manifest);
+            // Ensure a pinned manifest is only loaded if its checksum matches.
+            let bytes = std::fs::read(directory.path().join("manifest.json")).unwrap();
+            let checksum = format!("sha256:{:x}", Sha256::digest(&bytes)).parse::<ManifestSource>().unwrap();
+            assert_eq!(load_manifest(&client, &base_urls, &checksum).await.unwrap(), manifest);
+            let other = format!("sha256:{:x}", Sha256::digest(b"invalid")).parse::<ManifestSource>().unwrap();
+            assert!(load_manifest(&client, &base_urls, &other).await.is_err());
+
+            // Ensure the corrupted bundle is rejected, and the bundle is loaded from the mirror.
+            let base_urls = [base_url, mirror_url];
+            let completed_height =
+                load_blocks(&base_urls, &checksum, 0, Some(1), Default::default(), process).await.unwrap();
+            assert_eq!(completed_height, 0);
+            assert_eq!(*blocks.read(), vec![genesis]);
+        });
+    }
+
     #[test]
     fn test_cdn_height() {
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -520,16 +799,17 @@ mod tests {
         let cdn_start = 0;
         let cdn_end = 100;
         let object_name = "blocks";
-        log_progress::<10>(timer, 0, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 10, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 20, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 30, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 40, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 50, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 60, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 70, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 80, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 90, cdn_start, cdn_end, object_name);
-        log_progress::<10>(timer, 100, cdn_start, cdn_end, object_name);
+        let mirrors = Mirrors::new([TEST_BASE_URL.to_string()]);
+        log_progress::<10>(timer, 0, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 10, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 20, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 30, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 40, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 50, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 60, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 70, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 80, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 90, cdn_start, cdn_end, object_name, &mirrors);
+        log_progress::<10>(timer, 100, cdn_start, cdn_end, object_name, &mirrors);
     }
 }
@@ -18,4 +18,9 @@
 extern crate tracing;
 
 mod blocks;
-pub use blocks::{load_blocks, sync_ledger_with_cdn};
+pub use blocks::{fetch_cdn_height, load_blocks, sync_ledger_with_cdn, CdnConfig, LatestState, BLOCKS_PER_FILE};
+
+mod manifest;
+pub use manifest::*;
+
+mod mirrors;
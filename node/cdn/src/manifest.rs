@@ -0,0 +1,239 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{block::Block, Deserialize, Network, Serialize};
+
+use anyhow::{bail, ensure, Result};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+/// The manifest of the block bundles on a CDN.
+///
+/// The manifest is served as 'manifest.json' (encoded like 'latest.json'), and is optional.
+/// When present, each downloaded bundle is checked against it before its blocks are processed,
+/// and a bundle that fails the check is downloaded again from the next mirror.
+///
+/// As the mirrors serve the manifest alongside the bundles, a manifest from a mirror only guards against
+/// corrupted bundles. To guard against a malicious mirror, the manifest must come from a [`ManifestSource`]
+/// that is trusted, i.e. a local file or a checksum given out of band.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Manifest {
+    /// The base URLs of the mirrors that serve the same bundles (only `http(s)` URLs are used).
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    /// The bundle entries, keyed by the start height of the bundle.
+    pub bundles: BTreeMap<u32, BundleEntry>,
+}
+
+/// The manifest entry of a block bundle.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BundleEntry {
+    /// The (exclusive) end height of the bundle.
+    pub end: u32,
+    /// The SHA-256 checksum of the bundle, in hex.
+    pub checksum: String,
+    /// The hash of the first block in the bundle.
+    pub first_hash: String,
+    /// The hash of the last block in the bundle.
+    pub last_hash: String,
+}
+
+impl BundleEntry {
+    /// Initializes the manifest entry for the bundle ending at the given height.
+    pub fn new<N: Network>(end: u32, bytes: &[u8], blocks: &[Block<N>]) -> Result<Self> {
+        let (Some(first), Some(last)) = (blocks.first(), blocks.last()) else {
+            bail!("Cannot create a manifest entry for an empty bundle");
+        };
+        Ok(Self {
+            end,
+            checksum: checksum(bytes),
+            first_hash: first.hash().to_string(),
+            last_hash: last.hash().to_string(),
+        })
+    }
+}
+
+/// The source of the manifest of a CDN.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ManifestSource {
+    /// The manifest is fetched from the first mirror that serves a valid one, if any.
+    #[default]
+    Mirrors,
+    /// The manifest is read from the given local file.
+    File(PathBuf),
+    /// The manifest is fetched from the first mirror that serves one with the given SHA-256 checksum (in hex).
+    Checksum(String),
+}
+
+impl FromStr for ManifestSource {
+    type Err = anyhow::Error;
+
+    /// Parses the manifest source, given as `sha256:<checksum>` or as the path to a local file.
+    fn from_str(source: &str) -> Result<Self> {
+        match source.strip_prefix("sha256:") {
+            Some(checksum) => {
+                ensure!(
+                    checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit()),
+                    "Invalid SHA-256 checksum '{checksum}'"
+                );
+                Ok(Self::Checksum(checksum.to_ascii_lowercase()))
+            }
+            None => Ok(Self::File(PathBuf::from(source))),
+        }
+    }
+}
+
+impl ManifestSource {
+    /// Returns `true` if the manifest is trusted, i.e. it does not rely on the mirrors alone.
+    pub fn is_trusted(&self) -> bool {
+        !matches!(self, Self::Mirrors)
+    }
+
+    /// Returns the manifest from the given bytes (encoded like 'latest.json'), if they match the source.
+    pub fn decode(&self, bytes: &[u8]) -> Result<Manifest> {
+        if let Self::Checksum(expected) = self {
+            ensure!(checksum(bytes) == *expected, "The manifest checksum does not match '{expected}'");
+        }
+        Ok(serde_json::from_str(&bincode::deserialize::<String>(bytes)?)?)
+    }
+}
+
+impl Manifest {
+    /// Returns the mirrors listed in the manifest that are served over `http(s)`.
+    ///
+    /// Other mirrors, such as `file://` paths, are ignored, so that a manifest cannot make the node read local files.
+    pub fn http_mirrors(&self) -> impl Iterator<Item = &String> {
+        self.mirrors.iter().filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+    }
+
+    /// Returns the blocks of the bundle at the given range, if the bundle is intact.
+    ///
+    /// This checks that the blocks form a hash chain with the expected heights, and if the manifest
+    /// has an entry for the bundle, that the checksum and the first and last block hashes match it.
+    /// If the manifest has an entry for the previous bundle, this also checks that the bundle extends it.
+    /// The bundle must hold every block in its range, except for the bundle that holds the (exclusive)
+    /// end height of the sync, which only needs to hold the blocks before it.
+    pub fn verify_bundle<N: Network>(
+        &self,
+        start: u32,
+        end: u32,
+        sync_end: u32,
+        bytes: &[u8],
+    ) -> Result<Vec<Block<N>>> {
+        // Retrieve the manifest entry, and ensure the checksum matches.
+        let entry = self.bundles.get(&start);
+        if let Some(entry) = entry {
+            ensure!(entry.end == end, "The manifest expects the bundle to end at {}", entry.end);
+            ensure!(entry.checksum == checksum(bytes), "The bundle checksum does not match the manifest");
+        }
+
+        // Deserialize the blocks.
+        let blocks: Vec<Block<N>> = bincode::deserialize(bytes)?;
+        let min_blocks = end.min(sync_end).saturating_sub(start).max(1) as usize;
+        ensure!(
+            (min_blocks..=(end - start) as usize).contains(&blocks.len()),
+            "The bundle has {} blocks, instead of {min_blocks} to {}",
+            blocks.len(),
+            end - start
+        );
+        // Ensure the blocks form a hash chain, starting at the given height.
+        for (i, block) in blocks.iter().enumerate() {
+            ensure!(block.height() == start + i as u32, "Expected block {} in the bundle", start + i as u32);
+            if i > 0 {
+                ensure!(
+                    block.previous_hash() == blocks[i - 1].hash(),
+                    "Block {} does not extend the chain",
+                    block.height()
+                );
+            }
+        }
+        // Ensure the first block extends the last block of the previous bundle in the manifest.
+        if let Some((_, previous)) = self.bundles.range(..start).next_back().filter(|(_, entry)| entry.end == start) {
+            ensure!(
+                blocks[0].previous_hash().to_string() == previous.last_hash,
+                "Block {start} does not extend the previous bundle in the manifest"
+            );
+        }
+        // Ensure the first and last block hashes match the manifest.
+        if let Some(entry) = entry {
+            ensure!(BundleEntry::new(end, bytes, &blocks)? == *entry, "The bundle blocks do not match the manifest");
+        }
+        Ok(blocks)
+    }
+}
+
+/// Returns the SHA-256 checksum of the given bytes, in hex.
+fn checksum(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{FromBytes, Testnet3};
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_verify_bundle() {
+        // Sample a bundle of the genesis block.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let bytes = bincode::serialize(&vec![genesis.clone()]).unwrap();
+
+        // Ensure the bundle is verified without a manifest entry.
+        let mut manifest = Manifest::default();
+        assert_eq!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 1, &bytes).unwrap(), vec![genesis.clone()]);
+        // Ensure the bundle is rejected at the wrong height.
+        assert!(manifest.verify_bundle::<CurrentNetwork>(50, 100, 51, &bytes).is_err());
+        // Ensure the bundle is rejected if it is missing blocks before the end of the sync.
+        assert!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 2, &bytes).is_err());
+        assert!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 100, &bytes).is_err());
+
+        // Ensure the bundle is verified with a manifest entry.
+        manifest.bundles.insert(0, BundleEntry::new(50, &bytes, &[genesis.clone()]).unwrap());
+        assert_eq!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 1, &bytes).unwrap(), vec![genesis.clone()]);
+
+        // Ensure a bundle with a different checksum is rejected.
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 1, &corrupted).is_err());
+        // Ensure a bundle with a different first block is rejected.
+        manifest.bundles.get_mut(&0).unwrap().first_hash = "ab1".to_string();
+        assert!(manifest.verify_bundle::<CurrentNetwork>(0, 50, 1, &bytes).is_err());
+    }
+
+    #[test]
+    fn test_manifest_source() {
+        let manifest = Manifest {
+            mirrors: vec!["https://a.b".to_string(), "file:///etc".to_string(), "http://c.d".to_string()],
+            ..Default::default()
+        };
+        // Ensure only the HTTP(S) mirrors are used.
+        assert_eq!(manifest.http_mirrors().collect::<Vec<_>>(), vec!["https://a.b", "http://c.d"]);
+
+        let bytes = bincode::serialize(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        // Ensure the manifest is decoded from the mirrors and from a file.
+        assert_eq!(ManifestSource::Mirrors.decode(&bytes).unwrap(), manifest);
+        assert_eq!("/tmp/manifest.json".parse::<ManifestSource>().unwrap().decode(&bytes).unwrap(), manifest);
+        // Ensure the manifest is decoded if it matches the checksum.
+        let source = format!("sha256:{}", checksum(&bytes).to_uppercase()).parse::<ManifestSource>().unwrap();
+        assert_eq!(source, ManifestSource::Checksum(checksum(&bytes)));
+        assert_eq!(source.decode(&bytes).unwrap(), manifest);
+        // Ensure a manifest with another checksum is rejected.
+        let other = bincode::serialize(&serde_json::to_string(&Manifest::default()).unwrap()).unwrap();
+        assert!(source.decode(&other).is_err());
+        // Ensure an invalid checksum is rejected.
+        assert!("sha256:abc".parse::<ManifestSource>().is_err());
+    }
+}
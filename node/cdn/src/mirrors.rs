@@ -0,0 +1,157 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// The mirrors of a CDN, along with their observed throughput.
+pub(crate) struct Mirrors {
+    /// The base URLs of the mirrors.
+    urls: Vec<String>,
+    /// The statistics of each mirror.
+    stats: Mutex<Vec<MirrorStats>>,
+    /// The time at which the mirrors were first used.
+    start: Instant,
+}
+
+/// The statistics of a mirror.
+#[derive(Clone, Debug, Default)]
+struct MirrorStats {
+    /// The number of bytes downloaded from the mirror.
+    num_bytes: u64,
+    /// The time spent on successful requests to the mirror.
+    elapsed: Duration,
+    /// The number of requests in flight to the mirror.
+    num_requests: u32,
+    /// The number of failed requests to the mirror.
+    num_failures: u32,
+}
+
+impl MirrorStats {
+    /// Returns the score of the mirror, where requests go to the mirror with the highest score.
+    ///
+    /// The score is the observed throughput (in bytes per second), shared among the requests in flight
+    /// and reduced by the failures. An untried mirror is tried first, while a mirror that only failed
+    /// is tried last.
+    fn score(&self) -> f64 {
+        let throughput = match (self.elapsed.is_zero(), self.num_failures) {
+            (true, 0) => f64::MAX,
+            (true, _) => 0.0,
+            (false, _) => self.num_bytes as f64 / self.elapsed.as_secs_f64(),
+        };
+        throughput / (self.num_requests as f64 + 1.0) / (self.num_failures as f64 + 1.0)
+    }
+}
+
+impl Mirrors {
+    /// Initializes the mirrors from the given base URLs, without duplicates.
+    pub fn new(urls: impl IntoIterator<Item = String>) -> Self {
+        let mut unique_urls = Vec::new();
+        for url in urls {
+            let url = url.trim_end_matches('/').to_string();
+            if !url.is_empty() && !unique_urls.contains(&url) {
+                unique_urls.push(url);
+            }
+        }
+        let stats = Mutex::new(vec![MirrorStats::default(); unique_urls.len()]);
+        Self { urls: unique_urls, stats, start: Instant::now() }
+    }
+
+    /// Returns the base URL of the mirror at the given index.
+    pub fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    /// Selects the mirror for the next request, skipping the given mirrors, and marks the request as in flight.
+    ///
+    /// Returns `None` if every mirror is skipped.
+    pub fn select(&self, skipped: &[usize]) -> Option<usize> {
+        let mut stats = self.stats.lock();
+        // Select the mirror with the highest score, preferring the first one on ties.
+        let index = (0..self.urls.len())
+            .rev()
+            .filter(|index| !skipped.contains(index))
+            .max_by(|a, b| stats[*a].score().total_cmp(&stats[*b].score()))?;
+        stats[index].num_requests += 1;
+        Some(index)
+    }
+
+    /// Records a successful request to the mirror at the given index.
+    pub fn record_success(&self, index: usize, num_bytes: usize, elapsed: Duration) {
+        let mut stats = self.stats.lock();
+        stats[index].num_requests = stats[index].num_requests.saturating_sub(1);
+        stats[index].num_bytes += num_bytes as u64;
+        stats[index].elapsed += elapsed;
+    }
+
+    /// Records a failed request to the mirror at the given index.
+    pub fn record_failure(&self, index: usize) {
+        let mut stats = self.stats.lock();
+        stats[index].num_requests = stats[index].num_requests.saturating_sub(1);
+        stats[index].num_failures += 1;
+    }
+
+    /// Returns a summary of the download progress across the mirrors.
+    pub fn summary(&self) -> String {
+        let num_bytes = self.stats.lock().iter().map(|stats| stats.num_bytes).sum::<u64>();
+        let throughput = num_bytes as f64 / self.start.elapsed().as_secs_f64().max(1.0) / (1024.0 * 1024.0);
+        format!("from {} mirror(s) at {throughput:.2} MiB/s", self.urls.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        let mirrors = Mirrors::new(["a", "b/", "a/", ""].map(String::from));
+        assert_eq!((mirrors.url(0), mirrors.url(1)), ("a", "b"));
+
+        // Ensure the untried mirrors are selected in order.
+        assert_eq!(mirrors.select(&[]), Some(0));
+        assert_eq!(mirrors.select(&[]), Some(1));
+        assert_eq!(mirrors.select(&[0, 1]), None);
+        // Ensure the faster mirror receives more requests.
+        mirrors.record_success(0, 3500, Duration::from_secs(1));
+        mirrors.record_success(1, 1000, Duration::from_secs(1));
+        assert_eq!(mirrors.select(&[]), Some(0));
+        assert_eq!(mirrors.select(&[]), Some(0));
+        assert_eq!(mirrors.select(&[]), Some(0));
+        assert_eq!(mirrors.select(&[]), Some(1));
+        // Ensure a skipped mirror is not selected.
+        assert_eq!(mirrors.select(&[0]), Some(1));
+        // Ensure a failing mirror receives fewer requests.
+        for _ in 0..10 {
+            mirrors.record_failure(0);
+        }
+        assert_eq!(mirrors.select(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_select_after_failures() {
+        let mirrors = Mirrors::new(["a", "b", "c"].map(String::from));
+
+        // Ensure a mirror that only failed is tried after the untried and the working mirrors.
+        assert_eq!(mirrors.select(&[]), Some(0));
+        mirrors.record_failure(0);
+        assert_eq!(mirrors.select(&[]), Some(1));
+        mirrors.record_success(1, 1, Duration::from_secs(100));
+        assert_eq!(mirrors.select(&[]), Some(2));
+        mirrors.record_failure(2);
+        assert_eq!(mirrors.select(&[]), Some(1));
+        // Ensure a mirror that only failed is still tried once every other mirror is skipped.
+        assert_eq!(mirrors.select(&[1, 2]), Some(0));
+    }
+}
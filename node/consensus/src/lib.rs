@@ -22,38 +22,84 @@ use snarkos_node_bft::{
     helpers::{
         fmt_id,
         init_consensus_channels,
+        now,
+        transaction_priority,
         ConsensusReceiver,
+        Insertion,
         PrimaryReceiver,
         PrimarySender,
+        Priority,
+        PriorityQueue,
+        QueueState,
+        Sender,
         Storage as NarwhalStorage,
     },
     spawn_blocking,
+    BFTConfig,
     BFT,
-    MAX_GC_ROUNDS,
-    MAX_TRANSMISSIONS_PER_BATCH,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
-use snarkos_node_bft_storage_service::BFTPersistentStorage;
+use snarkos_node_bft_storage_service::{
+    BFTFileStorage,
+    BFTMemoryService,
+    BFTPersistentStorage,
+    StorageBackend,
+    StorageService,
+};
 use snarkvm::{
     ledger::{
-        block::Transaction,
+        block::{Block, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         narwhal::{Data, Subdag, Transmission, TransmissionID},
     },
     prelude::*,
 };
 
+use aleo_std::StorageMode;
 use anyhow::Result;
 use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
-use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
+use std::{collections::HashSet, future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
 use tokio::{
-    sync::{oneshot, OnceCell},
+    sync::{broadcast, mpsc, oneshot, OnceCell},
     task::JoinHandle,
 };
 
+/// The maximum number of unconfirmed solutions in the queue.
+const MAX_QUEUED_SOLUTIONS: usize = 1 << 12;
+/// The maximum total size of the unconfirmed solutions in the queue, in bytes.
+const MAX_QUEUED_SOLUTIONS_IN_BYTES: u64 = 1 << 22; // 4 MiB
+/// The maximum number of unconfirmed transactions in the queue.
+const MAX_QUEUED_TRANSACTIONS: usize = 1 << 14;
+/// The maximum total size of the unconfirmed transactions in the queue, in bytes.
+const MAX_QUEUED_TRANSACTIONS_IN_BYTES: u64 = 1 << 28; // 256 MiB
+
+/// The default time-to-live of the unconfirmed transmissions in the memory pool, in seconds.
+pub const DEFAULT_TRANSMISSION_TTL_IN_SECS: u64 = 60 * 60; // 1 hour
+
+/// The unconfirmed solutions queue, by prover.
+type SolutionsQueue<N> = PriorityQueue<PuzzleCommitment<N>, ProverSolution<N>, Address<N>>;
+/// The unconfirmed transactions queue, by fee payer (or by transaction, if its fee is private) and fee per byte.
+type TransactionsQueue<N> = PriorityQueue<<N as Network>::TransactionID, Transaction<N>, Sender<N>>;
+
+/// The maximum number of notifications kept for each lagging subscriber.
+const MAX_NOTIFICATION_BACKLOG: usize = 1024;
+
+/// The status of an unconfirmed transaction in the memory pool of this node, from the earliest to the latest stage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemoryPoolStatus<N: Network> {
+    /// The transaction is in the unconfirmed transactions queue, waiting for capacity in the workers.
+    Queued,
+    /// The transaction is in the ready queue of the given worker.
+    Ready { worker_id: u8 },
+    /// The transaction is in the batch proposed by this node, which was proposed at the given timestamp.
+    Proposed { round: u64, timestamp: i64 },
+    /// The transaction is in a certificate of the DAG that is not yet committed.
+    Certified { round: u64, certificate_id: Field<N>, timestamp: i64 },
+}
+
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
     /// The ledger.
@@ -62,42 +108,75 @@ pub struct Consensus<N: Network> {
     bft: BFT<N>,
     /// The primary sender.
     primary_sender: Arc<OnceCell<PrimarySender<N>>>,
+    /// The sender of the requests to refill the workers from the unconfirmed queues.
+    refill_sender: Arc<OnceCell<mpsc::Sender<()>>>,
     /// The unconfirmed solutions queue.
-    solutions_queue: Arc<Mutex<IndexMap<PuzzleCommitment<N>, ProverSolution<N>>>>,
+    solutions_queue: Arc<Mutex<SolutionsQueue<N>>>,
     /// The unconfirmed transactions queue.
-    transactions_queue: Arc<Mutex<IndexMap<N::TransactionID, Transaction<N>>>>,
+    transactions_queue: Arc<Mutex<TransactionsQueue<N>>>,
+    /// The time-to-live of the unconfirmed transmissions in the memory pool, in seconds.
+    transmission_ttl: u64,
     /// The recently-seen unconfirmed solutions.
     seen_solutions: Arc<Mutex<LruCache<PuzzleCommitment<N>, ()>>>,
-    /// The recently-seen unconfirmed transactions.
-    seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The recently-seen unconfirmed transactions, along with the timestamp at which they were first seen.
+    seen_transactions: Arc<Mutex<LruCache<N::TransactionID, i64>>>,
+    /// The sender of the blocks that are added to the ledger.
+    block_sender: broadcast::Sender<Block<N>>,
+    /// The sender of the unconfirmed transmissions that are added to the memory pool.
+    memory_pool_sender: broadcast::Sender<Transmission<N>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
 
 impl<N: Network> Consensus<N> {
     /// Initializes a new instance of consensus.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        bft_config: BFTConfig,
         account: Account<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
-        dev: Option<u16>,
+        transmission_ttl: u64,
+        storage_backend: StorageBackend,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
+        // Retrieve the development ID, if the node is in development mode.
+        let dev = storage_mode.dev();
         // Initialize the Narwhal transmissions.
-        let transmissions = Arc::new(BFTPersistentStorage::open(dev)?);
+        let transmissions: Arc<dyn StorageService<N>> = match storage_backend {
+            StorageBackend::Memory => Arc::new(BFTMemoryService::new()),
+            StorageBackend::RocksDB => Arc::new(BFTPersistentStorage::open(storage_mode)?),
+            StorageBackend::File => {
+                // Note: The segments are stored within the ledger directory, so that they are removed with the ledger.
+                let directory = aleo_std::aleo_ledger_dir(N::ID, storage_mode).join("bft");
+                Arc::new(BFTFileStorage::open(directory)?)
+            }
+        };
+        info!("Using the '{storage_backend}' storage backend for the BFT");
         // Initialize the Narwhal storage.
-        let storage = NarwhalStorage::new(ledger.clone(), transmissions, MAX_GC_ROUNDS);
+        let storage = NarwhalStorage::new(ledger.clone(), transmissions, bft_config.max_gc_rounds);
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger.clone(), ip, trusted_validators, dev)?;
+        let bft = BFT::new(bft_config, account, storage, ledger.clone(), ip, trusted_validators, dev)?;
         // Return the consensus.
         Ok(Self {
             ledger,
             bft,
             primary_sender: Default::default(),
-            solutions_queue: Default::default(),
-            transactions_queue: Default::default(),
+            refill_sender: Default::default(),
+            solutions_queue: Arc::new(Mutex::new(PriorityQueue::new(
+                MAX_QUEUED_SOLUTIONS,
+                MAX_QUEUED_SOLUTIONS_IN_BYTES,
+            ))),
+            transactions_queue: Arc::new(Mutex::new(PriorityQueue::new(
+                MAX_QUEUED_TRANSACTIONS,
+                MAX_QUEUED_TRANSACTIONS_IN_BYTES,
+            ))),
+            transmission_ttl,
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            block_sender: broadcast::channel(MAX_NOTIFICATION_BACKLOG).0,
+            memory_pool_sender: broadcast::channel(MAX_NOTIFICATION_BACKLOG).0,
             handles: Default::default(),
         })
     }
@@ -110,6 +189,8 @@ impl<N: Network> Consensus<N> {
 
         // First, initialize the consensus channels.
         let (consensus_sender, consensus_receiver) = init_consensus_channels();
+        // Set the refill sender, so that committed blocks also refill the workers.
+        self.refill_sender.set(consensus_sender.tx_consensus_refill.clone()).expect("Refill sender already set");
         // Then, start the consensus handlers.
         self.start_handlers(consensus_receiver);
         // Lastly, the consensus.
@@ -131,6 +212,16 @@ impl<N: Network> Consensus<N> {
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set")
     }
+
+    /// Returns the sender of the blocks that are added to the ledger, which subscribers can subscribe to.
+    pub const fn block_sender(&self) -> &broadcast::Sender<Block<N>> {
+        &self.block_sender
+    }
+
+    /// Returns a receiver of the unconfirmed transmissions that are added to the memory pool.
+    pub fn subscribe_memory_pool(&self) -> broadcast::Receiver<Transmission<N>> {
+        self.memory_pool_sender.subscribe()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -153,6 +244,21 @@ impl<N: Network> Consensus<N> {
     pub fn num_unconfirmed_transactions(&self) -> usize {
         self.bft.num_unconfirmed_transactions()
     }
+
+    /// Returns the state of the unconfirmed solutions queue.
+    pub fn solutions_queue_state(&self) -> QueueState {
+        self.solutions_queue.lock().state()
+    }
+
+    /// Returns the state of the unconfirmed transactions queue.
+    pub fn transactions_queue_state(&self) -> QueueState {
+        self.transactions_queue.lock().state()
+    }
+
+    /// Returns the combined state of the ready queues of the workers.
+    pub fn ready_queue_state(&self) -> QueueState {
+        self.bft.ready_queue_state()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -177,6 +283,51 @@ impl<N: Network> Consensus<N> {
     }
 }
 
+impl<N: Network> Consensus<N> {
+    /// Returns the status of the given transaction in the memory pool, if it is in the memory pool.
+    pub fn memory_pool_status(&self, transaction_id: &N::TransactionID) -> Option<MemoryPoolStatus<N>> {
+        let transmission_id = TransmissionID::from(transaction_id);
+
+        // Check if the transaction is in a certificate of the DAG.
+        let storage = self.bft.storage();
+        if storage.contains_transmission(transmission_id) {
+            // Find the certificate from the earliest round that includes the transaction.
+            let certificate = (storage.gc_round()..=storage.current_round())
+                .flat_map(|round| storage.get_certificates_for_round(round))
+                .find(|certificate| certificate.transmission_ids().contains(&transmission_id));
+            if let Some(certificate) = certificate {
+                return Some(MemoryPoolStatus::Certified {
+                    round: certificate.round(),
+                    certificate_id: certificate.id(),
+                    timestamp: certificate.batch_header().timestamp(),
+                });
+            }
+        }
+        // Check if the transaction is in the batch proposed by this node.
+        if let Some(proposal) = self.bft.primary().proposed_batch().read().as_ref() {
+            if proposal.contains_transmission(transmission_id) {
+                return Some(MemoryPoolStatus::Proposed { round: proposal.round(), timestamp: proposal.timestamp() });
+            }
+        }
+        // Check if the transaction is in the ready queue of a worker.
+        if let Some(worker) =
+            self.bft.primary().workers().iter().find(|worker| worker.contains_transmission(transmission_id))
+        {
+            return Some(MemoryPoolStatus::Ready { worker_id: worker.id() });
+        }
+        // Check if the transaction is in the unconfirmed transactions queue.
+        match self.transactions_queue.lock().contains_key(transaction_id) {
+            true => Some(MemoryPoolStatus::Queued),
+            false => None,
+        }
+    }
+
+    /// Returns the timestamp at which the given transaction was first seen, if it was recently seen.
+    pub fn transaction_seen_at(&self, transaction_id: &N::TransactionID) -> Option<i64> {
+        self.seen_transactions.lock().peek(transaction_id).copied()
+    }
+}
+
 impl<N: Network> Consensus<N> {
     /// Adds the given unconfirmed solution to the memory pool.
     pub async fn add_unconfirmed_solution(&self, solution: ProverSolution<N>) -> Result<()> {
@@ -195,36 +346,35 @@ impl<N: Network> Consensus<N> {
             }
             // Add the solution to the memory pool.
             trace!("Received unconfirmed solution '{}' in the queue", fmt_id(solution_id));
-            if self.solutions_queue.lock().insert(solution_id, solution).is_some() {
-                bail!("Solution '{}' exists in the memory pool", fmt_id(solution_id));
+            // Note: The solutions are exempt from fees, so they are ordered by their prover and their arrival.
+            let priority = Priority::exempt(solution.to_bytes_le().map_or(0, |bytes| bytes.len()) as u64);
+            let insertion = self.solutions_queue.lock().insert(solution_id, solution, solution.address(), priority);
+            self.update_queue_metrics(insertion.num_dropped(), 0);
+            match insertion {
+                Insertion::Inserted => (),
+                Insertion::Evicted(evicted) => {
+                    let mut seen_solutions = self.seen_solutions.lock();
+                    for (evicted_id, _) in evicted {
+                        debug!("Evicted unconfirmed solution '{}' from the full queue", fmt_id(evicted_id));
+                        // Allow the evicted solution to be received again.
+                        seen_solutions.pop(&evicted_id);
+                    }
+                }
+                Insertion::Duplicate => bail!("Solution '{}' exists in the memory pool", fmt_id(solution_id)),
+                Insertion::Rejected => {
+                    // Allow the solution to be received again.
+                    self.seen_solutions.lock().pop(&solution_id);
+                    bail!("Solution '{}' does not fit in the full queue", fmt_id(solution_id))
+                }
             }
-        }
-
-        // If the memory pool of this node is full, return early.
-        let num_unconfirmed = self.num_unconfirmed_transmissions();
-        if num_unconfirmed > N::MAX_SOLUTIONS || num_unconfirmed > MAX_TRANSMISSIONS_PER_BATCH {
-            return Ok(());
-        }
-        // Retrieve the solutions.
-        let solutions = {
-            // Determine the available capacity.
-            let capacity = N::MAX_SOLUTIONS.saturating_sub(num_unconfirmed);
-            // Acquire the lock on the queue.
-            let mut queue = self.solutions_queue.lock();
-            // Determine the number of solutions to send.
-            let num_solutions = queue.len().min(capacity);
-            // Drain the solutions from the queue.
-            queue.drain(..num_solutions).collect::<Vec<_>>()
-        };
-        // Iterate over the solutions.
-        for (_, solution) in solutions.into_iter() {
-            let solution_id = solution.commitment();
-            trace!("Adding unconfirmed solution '{}' to the memory pool...", fmt_id(solution_id));
-            // Send the unconfirmed solution to the primary.
-            if let Err(e) = self.primary_sender().send_unconfirmed_solution(solution_id, Data::Object(solution)).await {
-                warn!("Failed to add unconfirmed solution '{}' to the memory pool - {e}", fmt_id(solution_id));
+            // Notify the subscribers of the memory pool.
+            if self.memory_pool_sender.receiver_count() > 0 {
+                let _ = self.memory_pool_sender.send(Transmission::from(solution));
             }
         }
+
+        // Refill the workers from the unconfirmed solutions queue.
+        self.process_unconfirmed_solutions().await;
         Ok(())
     }
 
@@ -239,9 +389,13 @@ impl<N: Network> Consensus<N> {
                 bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
             }
             // Check if the transaction was recently seen.
-            if self.seen_transactions.lock().put(transaction_id, ()).is_some() {
-                // If the transaction was recently seen, return early.
-                return Ok(());
+            {
+                let mut seen_transactions = self.seen_transactions.lock();
+                if seen_transactions.contains(&transaction_id) {
+                    // If the transaction was recently seen, return early.
+                    return Ok(());
+                }
+                seen_transactions.put(transaction_id, now());
             }
             // Check if the transaction already exists in the ledger.
             if self.ledger.contains_transmission(&TransmissionID::from(&transaction_id))? {
@@ -249,27 +403,91 @@ impl<N: Network> Consensus<N> {
             }
             // Add the transaction to the memory pool.
             trace!("Received unconfirmed transaction '{}' in the queue", fmt_id(transaction_id));
-            if self.transactions_queue.lock().insert(transaction_id, transaction).is_some() {
-                bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
+            let (sender, priority) = transaction_priority(&transaction);
+            let insertion =
+                self.transactions_queue.lock().insert(transaction_id, transaction.clone(), sender, priority);
+            self.update_queue_metrics(0, insertion.num_dropped());
+            match insertion {
+                Insertion::Inserted => (),
+                Insertion::Evicted(evicted) => {
+                    let mut seen_transactions = self.seen_transactions.lock();
+                    for (evicted_id, _) in evicted {
+                        debug!("Evicted unconfirmed transaction '{}' from the full queue", fmt_id(evicted_id));
+                        // Allow the evicted transaction to be received again.
+                        seen_transactions.pop(&evicted_id);
+                    }
+                }
+                Insertion::Duplicate => bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id)),
+                Insertion::Rejected => {
+                    // Allow the transaction to be received again.
+                    self.seen_transactions.lock().pop(&transaction_id);
+                    bail!("Transaction '{}' has the lowest priority in the full queue", fmt_id(transaction_id))
+                }
+            }
+            // Notify the subscribers of the memory pool.
+            if self.memory_pool_sender.receiver_count() > 0 {
+                let _ = self.memory_pool_sender.send(Transmission::from(transaction));
+            }
+        }
+
+        // Refill the workers from the unconfirmed transactions queue.
+        self.process_unconfirmed_transactions().await;
+        Ok(())
+    }
+}
+
+impl<N: Network> Consensus<N> {
+    /// Sends the unconfirmed solutions from the queue to the primary, up to the available capacity of the workers.
+    async fn process_unconfirmed_solutions(&self) {
+        // If the memory pool of this node is full, return early.
+        let num_unconfirmed = self.num_unconfirmed_transmissions();
+        let max_transmissions_per_batch = self.bft.config().max_transmissions_per_batch;
+        if num_unconfirmed > N::MAX_SOLUTIONS || num_unconfirmed > max_transmissions_per_batch {
+            return;
+        }
+        // Retrieve the solutions.
+        let solutions = {
+            // Determine the available capacity.
+            let capacity = N::MAX_SOLUTIONS.saturating_sub(num_unconfirmed);
+            // Acquire the lock on the queue.
+            let mut queue = self.solutions_queue.lock();
+            // Determine the number of solutions to send.
+            let num_solutions = queue.len().min(capacity);
+            // Drain the solutions from the queue.
+            queue.drain(num_solutions)
+        };
+        self.update_queue_metrics(0, 0);
+        // Iterate over the solutions.
+        for (_, solution) in solutions.into_iter() {
+            let solution_id = solution.commitment();
+            trace!("Adding unconfirmed solution '{}' to the memory pool...", fmt_id(solution_id));
+            // Send the unconfirmed solution to the primary.
+            if let Err(e) = self.primary_sender().send_unconfirmed_solution(solution_id, Data::Object(solution)).await {
+                warn!("Failed to add unconfirmed solution '{}' to the memory pool - {e}", fmt_id(solution_id));
             }
         }
+    }
 
+    /// Sends the unconfirmed transactions from the queue to the primary, up to the available capacity of the workers.
+    async fn process_unconfirmed_transactions(&self) {
         // If the memory pool of this node is full, return early.
         let num_unconfirmed = self.num_unconfirmed_transmissions();
-        if num_unconfirmed > MAX_TRANSMISSIONS_PER_BATCH {
-            return Ok(());
+        let max_transmissions_per_batch = self.bft.config().max_transmissions_per_batch;
+        if num_unconfirmed > max_transmissions_per_batch {
+            return;
         }
         // Retrieve the transactions.
         let transactions = {
             // Determine the available capacity.
-            let capacity = MAX_TRANSMISSIONS_PER_BATCH.saturating_sub(num_unconfirmed);
+            let capacity = max_transmissions_per_batch.saturating_sub(num_unconfirmed);
             // Acquire the lock on the queue.
             let mut queue = self.transactions_queue.lock();
             // Determine the number of transactions to send.
             let num_transactions = queue.len().min(capacity);
-            // Drain the solutions from the queue.
-            queue.drain(..num_transactions).collect::<Vec<_>>()
+            // Drain the transactions from the queue, in order of priority.
+            queue.drain(num_transactions)
         };
+        self.update_queue_metrics(0, 0);
         // Iterate over the transactions.
         for (_, transaction) in transactions.into_iter() {
             let transaction_id = transaction.id();
@@ -281,14 +499,37 @@ impl<N: Network> Consensus<N> {
                 warn!("Failed to add unconfirmed transaction '{}' to the memory pool - {e}", fmt_id(transaction_id));
             }
         }
-        Ok(())
+    }
+
+    /// Requests the workers to be refilled from the unconfirmed queues, as their capacity may have freed up.
+    fn request_refill(&self) {
+        if let Some(refill_sender) = self.refill_sender.get() {
+            // Note: If a refill request is already pending, this request is covered by it.
+            let _ = refill_sender.try_send(());
+        }
     }
 }
 
 impl<N: Network> Consensus<N> {
+    /// Updates the metrics of the unconfirmed queues, with the given number of dropped solutions and transactions.
+    #[allow(unused_variables)]
+    fn update_queue_metrics(&self, num_dropped_solutions: usize, num_dropped_transactions: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            let solutions = self.solutions_queue_state();
+            let transactions = self.transactions_queue_state();
+            metrics::gauge(metrics::consensus::QUEUED_SOLUTIONS, solutions.num_entries as f64);
+            metrics::gauge(metrics::consensus::QUEUED_SOLUTIONS_BYTES, solutions.num_bytes as f64);
+            metrics::gauge(metrics::consensus::QUEUED_TRANSACTIONS, transactions.num_entries as f64);
+            metrics::gauge(metrics::consensus::QUEUED_TRANSACTIONS_BYTES, transactions.num_bytes as f64);
+            metrics::counter(metrics::consensus::DROPPED_SOLUTIONS, num_dropped_solutions as u64);
+            metrics::counter(metrics::consensus::DROPPED_TRANSACTIONS, num_dropped_transactions as u64);
+        }
+    }
+
     /// Starts the consensus handlers.
     fn start_handlers(&self, consensus_receiver: ConsensusReceiver<N>) {
-        let ConsensusReceiver { mut rx_consensus_subdag } = consensus_receiver;
+        let ConsensusReceiver { mut rx_consensus_subdag, mut rx_consensus_refill } = consensus_receiver;
 
         // Process the committed subdag and transmissions from the BFT.
         let self_ = self.clone();
@@ -297,6 +538,34 @@ impl<N: Network> Consensus<N> {
                 self_.process_bft_subdag(committed_subdag, transmissions, callback).await;
             }
         });
+
+        // Process the requests to refill the workers, which are sent after a batch is certified or a block is committed.
+        // Note: This ensures the queued transmissions are processed, even if no new transmissions arrive.
+        let self_ = self.clone();
+        self.spawn(async move {
+            while rx_consensus_refill.recv().await.is_some() {
+                self_.process_unconfirmed_solutions().await;
+                self_.process_unconfirmed_transactions().await;
+            }
+        });
+
+        // Remove the unconfirmed transmissions that are invalidated by each new block, or that have expired.
+        // Note: This covers the blocks that are received through sync, as well as those committed by the BFT.
+        let (tx_block, mut rx_block) = mpsc::unbounded_channel();
+        self.ledger.on_advance(Box::new(move |block: &Block<N>| {
+            let _ = tx_block.send(block.clone());
+        }));
+        let self_ = self.clone();
+        self.spawn(async move {
+            while let Some(block) = rx_block.recv().await {
+                let consensus = self_.clone();
+                if let Err(error) =
+                    tokio::task::spawn_blocking(move || consensus.revalidate_unconfirmed_transmissions(&block)).await
+                {
+                    error!("Unable to revalidate the unconfirmed transmissions - {error}");
+                }
+            }
+        });
     }
 
     /// Processes the committed subdag and transmissions from the BFT.
@@ -311,11 +580,15 @@ impl<N: Network> Consensus<N> {
         let transmissions_ = transmissions.clone();
         let result = spawn_blocking! { self_.try_advance_to_next_block(subdag, transmissions_) };
 
-        // If the block failed to advance, reinsert the transmissions into the memory pool.
-        if let Err(e) = &result {
-            error!("Unable to advance to the next block - {e}");
-            // On failure, reinsert the transmissions into the memory pool.
-            self.reinsert_transmissions(transmissions).await;
+        match &result {
+            // If the block was advanced, request the workers to be refilled.
+            Ok(()) => self.request_refill(),
+            // If the block failed to advance, reinsert the transmissions into the memory pool.
+            Err(e) => {
+                error!("Unable to advance to the next block - {e}");
+                // On failure, reinsert the transmissions into the memory pool.
+                self.reinsert_transmissions(transmissions).await;
+            }
         }
         // Send the callback **after** advancing to the next block.
         // Note: We must await the block to be advanced before sending the callback.
@@ -341,6 +614,10 @@ impl<N: Network> Consensus<N> {
         self.ledger.check_next_block(&next_block)?;
         // Advance to the next block.
         self.ledger.advance_to_next_block(&next_block)?;
+        // Notify the subscribers of the new block.
+        if self.block_sender.receiver_count() > 0 {
+            let _ = self.block_sender.send(next_block.clone());
+        }
 
         #[cfg(feature = "metrics")]
         {
@@ -358,6 +635,81 @@ impl<N: Network> Consensus<N> {
         Ok(())
     }
 
+    /// Removes the unconfirmed transmissions that are included in the given block, or that spend a record
+    /// spent in the block, along with the unconfirmed transmissions that are older than the time-to-live,
+    /// from the queues and the workers.
+    fn revalidate_unconfirmed_transmissions(&self, block: &Block<N>) {
+        // Retrieve the solution IDs, transaction IDs, and serial numbers in the block.
+        let solution_ids = block.puzzle_commitments().into_iter().flatten().copied().collect::<HashSet<_>>();
+        // Note: The unconfirmed IDs of the rejected transactions differ from the IDs of their fee transactions.
+        let transaction_ids = block
+            .transactions()
+            .iter()
+            .filter_map(|confirmed| confirmed.to_unconfirmed_transaction_id().ok())
+            .chain(block.aborted_transaction_ids().iter().copied())
+            .collect::<HashSet<_>>();
+        let serial_numbers = block.serial_numbers().copied().collect::<HashSet<_>>();
+        // Returns `true` if the transaction is not in the block, and does not spend a record spent in the block.
+        let is_valid = |transaction: &Transaction<N>| {
+            !transaction_ids.contains(&transaction.id())
+                && !transaction.serial_numbers().any(|serial_number| serial_numbers.contains(serial_number))
+        };
+        // Determine the timestamp before which the unconfirmed transmissions have expired.
+        let cutoff = now().saturating_sub(i64::try_from(self.transmission_ttl).unwrap_or(i64::MAX));
+
+        // Revalidate the unconfirmed solutions queue.
+        let num_solutions = {
+            let mut queue = self.solutions_queue.lock();
+            let num_invalid = queue.retain(|solution_id, _| !solution_ids.contains(solution_id)).len();
+            let expired = queue.expire(cutoff);
+            // Allow the expired solutions to be received again.
+            let mut seen_solutions = self.seen_solutions.lock();
+            expired.iter().for_each(|solution_id| {
+                seen_solutions.pop(solution_id);
+            });
+            num_invalid + expired.len()
+        };
+        // Revalidate the unconfirmed transactions queue.
+        let num_transactions = {
+            let mut queue = self.transactions_queue.lock();
+            let num_invalid = queue.retain(|_, transaction| is_valid(transaction)).len();
+            let expired = queue.expire(cutoff);
+            // Allow the expired transactions to be received again.
+            let mut seen_transactions = self.seen_transactions.lock();
+            expired.iter().for_each(|transaction_id| {
+                seen_transactions.pop(transaction_id);
+            });
+            num_invalid + expired.len()
+        };
+        self.update_queue_metrics(0, 0);
+
+        // Revalidate the ready queues of the workers.
+        let mut num_ready = 0;
+        for worker in self.bft.primary().workers().iter() {
+            num_ready += worker.retain_ready(|transmission_id, transmission| match (transmission_id, transmission) {
+                (TransmissionID::Ratification, _) => true,
+                (TransmissionID::Solution(solution_id), _) => !solution_ids.contains(solution_id),
+                (TransmissionID::Transaction(transaction_id), _) if transaction_ids.contains(transaction_id) => false,
+                // Note: The transaction is only deserialized if the block spends any records.
+                (TransmissionID::Transaction(_), Transmission::Transaction(transaction))
+                    if !serial_numbers.is_empty() =>
+                {
+                    // If the transaction fails to deserialize, it is left to be rejected in the batch proposal.
+                    transaction.clone().deserialize_blocking().map_or(true, |transaction| is_valid(&transaction))
+                }
+                _ => true,
+            });
+            num_ready += worker.expire_ready(cutoff);
+        }
+
+        if num_solutions + num_transactions + num_ready > 0 {
+            debug!(
+                "Removed {num_solutions} solution(s) and {num_transactions} transaction(s) from the queues, and {num_ready} transmission(s) from the workers, after block {}",
+                block.height()
+            );
+        }
+    }
+
     /// Reinserts the given transmissions into the memory pool.
     async fn reinsert_transmissions(&self, transmissions: IndexMap<TransmissionID<N>, Transmission<N>>) {
         // Iterate over the transmissions.
@@ -408,3 +760,78 @@ impl<N: Network> Consensus<N> {
         self.handles.lock().iter().for_each(|handle| handle.abort());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_node_bft::helpers::{init_primary_channels, PrimaryReceiver};
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkvm::prelude::TestRng;
+
+    use indexmap::IndexSet;
+    use std::time::Duration;
+
+    type CurrentNetwork = snarkvm::console::network::Testnet3;
+
+    #[tokio::test]
+    async fn test_refill_drains_queued_backlog() {
+        let rng = &mut TestRng::default();
+
+        // Initialize the consensus, without running the BFT.
+        let committee = snarkvm::ledger::committee::test_helpers::sample_committee(rng);
+        let ledger = Arc::new(MockLedgerService::new(committee));
+        let account = Account::new(rng).unwrap();
+        let consensus = Consensus::<CurrentNetwork>::new(
+            BFTConfig::default(),
+            account,
+            ledger,
+            None,
+            &[],
+            DEFAULT_TRANSMISSION_TTL_IN_SECS,
+            StorageBackend::Memory,
+            StorageMode::Production,
+        )
+        .unwrap();
+
+        // Initialize the primary, which accepts every unconfirmed transaction.
+        let (primary_sender, primary_receiver) = init_primary_channels();
+        consensus.primary_sender.set(primary_sender).unwrap();
+        let PrimaryReceiver { mut rx_unconfirmed_transaction, .. } = primary_receiver;
+        let (tx_received, mut rx_received) = mpsc::channel(MAX_QUEUED_TRANSACTIONS);
+        tokio::spawn(async move {
+            while let Some((transaction_id, _, callback)) = rx_unconfirmed_transaction.recv().await {
+                callback.send(Ok(())).ok();
+                tx_received.send(transaction_id).await.unwrap();
+            }
+        });
+
+        // Queue a backlog of transactions, as if the workers were full when they arrived.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let transactions =
+            genesis.transactions().iter().map(|confirmed| confirmed.transaction().clone()).collect::<Vec<_>>();
+        for transaction in &transactions {
+            let (sender, priority) = transaction_priority(transaction);
+            assert!(consensus
+                .transactions_queue
+                .lock()
+                .insert(transaction.id(), transaction.clone(), sender, priority)
+                .is_inserted());
+        }
+
+        // Start the consensus handlers, and request a refill, as the BFT does once a batch is certified.
+        let (consensus_sender, consensus_receiver) = init_consensus_channels();
+        consensus.refill_sender.set(consensus_sender.tx_consensus_refill.clone()).unwrap();
+        consensus.start_handlers(consensus_receiver);
+        consensus_sender.tx_consensus_refill.send(()).await.unwrap();
+
+        // Ensure the backlog is drained to the primary, without any further arrivals.
+        let mut received = IndexSet::new();
+        while received.len() < transactions.len() {
+            let transaction_id =
+                tokio::time::timeout(Duration::from_secs(10), rx_received.recv()).await.unwrap().unwrap();
+            received.insert(transaction_id);
+        }
+        assert_eq!(received, transactions.iter().map(|transaction| transaction.id()).collect::<IndexSet<_>>());
+        assert!(consensus.transactions_queue.lock().is_empty());
+    }
+}
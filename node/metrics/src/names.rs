@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) const COUNTER_NAMES: [&str; 1] = [bft::LEADERS_ELECTED];
+pub(super) const COUNTER_NAMES: [&str; 4] =
+    [bft::DROPPED_TRANSMISSIONS, bft::LEADERS_ELECTED, consensus::DROPPED_SOLUTIONS, consensus::DROPPED_TRANSACTIONS];
 
-pub(super) const GAUGE_NAMES: [&str; 12] = [
+pub(super) const GAUGE_NAMES: [&str; 16] = [
     bft::CONNECTED,
     bft::CONNECTING,
     bft::LAST_STORED_ROUND,
@@ -23,6 +24,10 @@ pub(super) const GAUGE_NAMES: [&str; 12] = [
     blocks::TRANSACTIONS,
     consensus::COMMITTED_CERTIFICATES,
     consensus::LAST_COMMITTED_ROUND,
+    consensus::QUEUED_SOLUTIONS,
+    consensus::QUEUED_SOLUTIONS_BYTES,
+    consensus::QUEUED_TRANSACTIONS,
+    consensus::QUEUED_TRANSACTIONS_BYTES,
     router::CONNECTED,
     router::CANDIDATE,
     router::RESTRICTED,
@@ -43,6 +48,7 @@ pub mod bft {
     pub const COMMIT_ROUNDS_LATENCY: &str = "snarkos_bft_commit_rounds_latency_secs"; // <-- This one doesn't even make sense.
     pub const CONNECTED: &str = "snarkos_bft_connected_total";
     pub const CONNECTING: &str = "snarkos_bft_connecting_total";
+    pub const DROPPED_TRANSMISSIONS: &str = "snarkos_bft_dropped_transmissions_total";
     pub const LAST_STORED_ROUND: &str = "snarkos_bft_last_stored_round";
     pub const LEADERS_ELECTED: &str = "snarkos_bft_leaders_elected_total";
     pub const PROPOSAL_ROUND: &str = "snarkos_bft_primary_proposal_round";
@@ -58,6 +64,12 @@ pub mod consensus {
     pub const COMMITTED_CERTIFICATES: &str = "snarkos_consensus_committed_certificates_total";
     pub const LAST_COMMITTED_ROUND: &str = "snarkos_consensus_last_committed_round";
     pub const BLOCK_LATENCY: &str = "snarkos_consensus_block_latency_secs";
+    pub const DROPPED_SOLUTIONS: &str = "snarkos_consensus_dropped_solutions_total";
+    pub const DROPPED_TRANSACTIONS: &str = "snarkos_consensus_dropped_transactions_total";
+    pub const QUEUED_SOLUTIONS: &str = "snarkos_consensus_queued_solutions_total";
+    pub const QUEUED_SOLUTIONS_BYTES: &str = "snarkos_consensus_queued_solutions_bytes";
+    pub const QUEUED_TRANSACTIONS: &str = "snarkos_consensus_queued_transactions_total";
+    pub const QUEUED_TRANSACTIONS_BYTES: &str = "snarkos_consensus_queued_transactions_bytes";
 }
 
 pub mod router {
@@ -18,33 +18,108 @@ use ::time::OffsetDateTime;
 use anyhow::{anyhow, Result};
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    extract::State,
+    http::{HeaderMap, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
-    RequestPartsExt,
 };
-use axum_extra::{
-    headers::authorization::{Authorization, Bearer},
-    TypedHeader,
+use axum_extra::headers::{
+    authorization::{Authorization, Bearer},
+    HeaderMapExt,
 };
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// The time a jwt token is valid for.
 pub const EXPIRATION: i64 = 10 * 365 * 24 * 60 * 60; // 10 years.
 
+/// The minimum length of a JWT secret, in bytes.
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// The JWT secret for the node instance.
+static SECRET: OnceCell<Vec<u8>> = OnceCell::new();
+
 /// Returns the JWT secret for the node instance.
 fn jwt_secret() -> &'static Vec<u8> {
-    static SECRET: OnceCell<Vec<u8>> = OnceCell::new();
+    // If no secret is set, a random secret is used, which only lasts as long as the process.
     SECRET.get_or_init(|| {
         let seed: [u8; 16] = ::rand::thread_rng().gen();
         seed.to_vec()
     })
 }
 
+/// Sets the JWT secret for the node instance, which must happen before any token is issued or verified.
+pub fn set_jwt_secret(secret: &[u8]) -> Result<()> {
+    ensure!(secret.len() >= MIN_SECRET_LENGTH, "The JWT secret must be at least {MIN_SECRET_LENGTH} bytes");
+    SECRET.set(secret.to_vec()).map_err(|_| anyhow!("The JWT secret is already set"))
+}
+
+/// Returns the JWT secret in the given file, after creating the file with a random secret if it does not exist.
+pub fn load_or_create_jwt_secret(path: &Path) -> Result<Vec<u8>> {
+    // If the file does not exist, create it with a random secret.
+    if !path.exists() {
+        let secret = ::rand::thread_rng()
+            .sample_iter(::rand::distributions::Alphanumeric)
+            .take(64)
+            .map(char::from)
+            .collect::<String>();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        // Ensure the secret is only readable by its owner.
+        #[cfg(target_family = "unix")]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        std::io::Write::write_all(&mut options.open(path)?, secret.as_bytes())?;
+    }
+    // Read the secret, ignoring the surrounding whitespace.
+    let secret = std::fs::read_to_string(path)?;
+    Ok(secret.trim().as_bytes().to_vec())
+}
+
+/// The scope of a JSON web token, which grants access to a group of routes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Grants access to the routes that read from the node.
+    Read,
+    /// Grants access to the routes that broadcast transactions and solutions.
+    Broadcast,
+    /// Grants access to the routes that manage the node, and to every other route.
+    Admin,
+}
+
+impl FromStr for Scope {
+    type Err = Error;
+
+    /// Parses the scope from its name.
+    fn from_str(scope: &str) -> Result<Self, Self::Err> {
+        match scope {
+            "read" => Ok(Self::Read),
+            "broadcast" => Ok(Self::Broadcast),
+            "admin" => Ok(Self::Admin),
+            _ => bail!("Invalid scope '{scope}' (expected 'read', 'broadcast' or 'admin')"),
+        }
+    }
+}
+
+impl Display for Scope {
+    /// Prints the name of the scope.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Broadcast => write!(f, "broadcast"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
 /// The Json web token claims.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Claims {
     /// The subject (user).
     sub: String,
@@ -52,14 +127,27 @@ pub struct Claims {
     iat: i64,
     /// Expiration time (as UTC timestamp).
     exp: i64,
+    /// The scopes granted by the token.
+    scopes: Vec<Scope>,
 }
 
 impl Claims {
+    /// Returns the claims of an admin token for the given address.
     pub fn new<N: Network>(address: Address<N>) -> Self {
+        Self::new_with_scopes(address.to_string(), vec![Scope::Admin], EXPIRATION)
+    }
+
+    /// Returns the claims of a token for the given subject, which grants the given scopes for the given duration (in seconds).
+    pub fn new_with_scopes(subject: String, scopes: Vec<Scope>, duration: i64) -> Self {
         let issued_at = OffsetDateTime::now_utc().unix_timestamp();
-        let expiration = issued_at.saturating_add(EXPIRATION);
+        let expiration = issued_at.saturating_add(duration);
+
+        Self { sub: subject, iat: issued_at, exp: expiration, scopes }
+    }
 
-        Self { sub: address.to_string(), iat: issued_at, exp: expiration }
+    /// Returns the subject of the token.
+    pub fn subject(&self) -> &str {
+        &self.sub
     }
 
     /// Returns true if the token is expired.
@@ -67,33 +155,121 @@ impl Claims {
         OffsetDateTime::now_utc().unix_timestamp() >= self.exp
     }
 
+    /// Returns true if the token grants the given scope.
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.contains(&scope) || self.scopes.contains(&Scope::Admin)
+    }
+
     /// Returns the json web token string.
     pub fn to_jwt_string(&self) -> Result<String> {
         encode(&Header::default(), &self, &EncodingKey::from_secret(jwt_secret())).map_err(|e| anyhow!(e))
     }
+
+    /// Returns the claims of the given json web token string, if it is signed with the JWT secret.
+    pub fn from_jwt_str(token: &str) -> Result<Self> {
+        decode::<Claims>(token, &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256))
+            .map(|decoded| decoded.claims)
+            .map_err(|e| anyhow!(e))
+    }
+}
+
+/// Returns `true` if the routes of the given scope require a JWT, where the admin routes always do.
+pub(crate) fn is_protected(scope: Scope, protected_scopes: &[Scope]) -> bool {
+    scope == Scope::Admin || protected_scopes.contains(&scope)
 }
 
-pub async fn auth_middleware(request: Request<Body>, next: Next) -> Result<Response, Response> {
-    // Deconstruct the request to extract the auth token.
-    let (mut parts, body) = request.into_parts();
-    let auth: TypedHeader<Authorization<Bearer>> =
-        parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
-
-    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
-        Ok(decoded) => {
-            let claims = decoded.claims;
-            if claims.is_expired() {
-                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
-            }
+/// Returns the claims of the JSON web token in the `Authorization` header, if there is one.
+///
+/// A token that is invalid or expired is rejected, instead of being ignored.
+pub(crate) fn request_claims(headers: &HeaderMap) -> Result<Option<Claims>, Response> {
+    let Some(auth) = headers.typed_get::<Authorization<Bearer>>() else {
+        return Ok(None);
+    };
+    match Claims::from_jwt_str(auth.token()) {
+        Ok(claims) if claims.is_expired() => {
+            Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response())
         }
+        Ok(claims) => Ok(Some(claims)),
+        Err(_) => Err(StatusCode::UNAUTHORIZED.into_response()),
+    }
+}
 
-        Err(_) => {
-            return Err(StatusCode::UNAUTHORIZED.into_response());
+/// Ensures the given claims grant the given scope, where a missing token is unauthorized.
+pub(crate) fn check_scope(claims: Option<&Claims>, scope: Scope) -> Result<(), Response> {
+    match claims {
+        Some(claims) if claims.has_scope(scope) => Ok(()),
+        Some(_) => {
+            Err((StatusCode::FORBIDDEN, format!("The JSON Web Token lacks the '{scope}' scope")).into_response())
         }
+        None => Err(StatusCode::UNAUTHORIZED.into_response()),
     }
+}
 
-    // Reconstruct the request.
-    let request = Request::from_parts(parts, body);
+pub async fn auth_middleware(
+    State(scope): State<Scope>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Result<Response, Response> {
+    // Ensure the auth token grants the scope of the routes.
+    let claims = request_claims(request.headers())?;
+    check_scope(claims.as_ref(), scope)?;
+
+    // Make the claims available to the handlers.
+    if let Some(claims) = claims {
+        request.extensions_mut().insert(claims);
+    }
 
     Ok(next.run(request).await)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes() {
+        // Ensure the scopes round-trip through their names.
+        for scope in [Scope::Read, Scope::Broadcast, Scope::Admin] {
+            assert_eq!(Scope::from_str(&scope.to_string()).unwrap(), scope);
+        }
+        assert!(Scope::from_str("write").is_err());
+
+        // Ensure the admin scope grants every scope.
+        let claims = Claims::new_with_scopes("admin".to_string(), vec![Scope::Admin], 60);
+        assert!([Scope::Read, Scope::Broadcast, Scope::Admin].into_iter().all(|scope| claims.has_scope(scope)));
+        // Ensure the other scopes only grant themselves.
+        let claims = Claims::new_with_scopes("reader".to_string(), vec![Scope::Read], 60);
+        assert!(claims.has_scope(Scope::Read));
+        assert!(!claims.has_scope(Scope::Broadcast) && !claims.has_scope(Scope::Admin));
+    }
+
+    #[test]
+    fn test_check_scope() {
+        // Ensure a missing token is unauthorized, and a token without the scope is forbidden.
+        assert_eq!(check_scope(None, Scope::Read).unwrap_err().status(), StatusCode::UNAUTHORIZED);
+        let claims = Claims::new_with_scopes("broadcaster".to_string(), vec![Scope::Broadcast], 60);
+        assert_eq!(check_scope(Some(&claims), Scope::Read).unwrap_err().status(), StatusCode::FORBIDDEN);
+        assert!(check_scope(Some(&claims), Scope::Broadcast).is_ok());
+
+        // Ensure the claims of a bearer token are returned, and an invalid token is unauthorized.
+        let mut headers = HeaderMap::new();
+        assert!(request_claims(&headers).unwrap().is_none());
+        headers.insert("authorization", format!("Bearer {}", claims.to_jwt_string().unwrap()).parse().unwrap());
+        assert_eq!(request_claims(&headers).unwrap().unwrap().subject(), "broadcaster");
+        headers.insert("authorization", "Bearer invalid".parse().unwrap());
+        assert_eq!(request_claims(&headers).unwrap_err().status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_load_or_create_jwt_secret() {
+        let directory = std::env::temp_dir().join(format!("snarkos-jwt-{}", ::rand::thread_rng().gen::<u64>()));
+        let path = directory.join("jwt_secret");
+
+        // Ensure the secret is created, and then loaded unchanged.
+        let secret = load_or_create_jwt_secret(&path).unwrap();
+        assert_eq!(secret.len(), 64);
+        assert_eq!(load_or_create_jwt_secret(&path).unwrap(), secret);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
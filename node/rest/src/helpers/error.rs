@@ -17,17 +17,57 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
+/// The kind of a REST error, which determines the code of the error in a JSON-RPC response.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RestErrorKind {
+    /// The requested item does not exist.
+    NotFound,
+    /// The request is malformed, such as an invalid parameter.
+    InvalidInput,
+    /// The route is not available, such as on this node type, or until fewer requests are in progress.
+    Unavailable,
+    /// The request failed on the server.
+    Internal,
+}
+
 /// An enum of error handlers for the REST API server.
-pub struct RestError(pub String);
+pub struct RestError {
+    /// The kind of the error.
+    pub kind: RestErrorKind,
+    /// The message of the error.
+    pub message: String,
+}
+
+impl RestError {
+    /// Initializes a new error for an item that does not exist.
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self { kind: RestErrorKind::NotFound, message: message.into() }
+    }
+
+    /// Initializes a new error for a malformed request.
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self { kind: RestErrorKind::InvalidInput, message: message.into() }
+    }
+
+    /// Initializes a new error for a route that is not available.
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        Self { kind: RestErrorKind::Unavailable, message: message.into() }
+    }
+
+    /// Initializes a new error for a request that failed on the server.
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self { kind: RestErrorKind::Internal, message: message.into() }
+    }
+}
 
 impl IntoResponse for RestError {
     fn into_response(self) -> Response {
-        (StatusCode::INTERNAL_SERVER_ERROR, format!("Something went wrong: {}", self.0)).into_response()
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Something went wrong: {}", self.message)).into_response()
     }
 }
 
 impl From<anyhow::Error> for RestError {
     fn from(err: anyhow::Error) -> Self {
-        Self(err.to_string())
+        Self::internal(err.to_string())
     }
 }
@@ -0,0 +1,433 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::{Block, Input, Output, Transition},
+    Address,
+    Argument,
+    Identifier,
+    Literal,
+    Network,
+    Plaintext,
+    ProgramID,
+};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The indexes that are built by the REST server, which are all disabled by default.
+///
+/// The indexes are held in memory, so they are rebuilt from the ledger in the background on every start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IndexConfig {
+    /// Whether the transactions are indexed by program, function and fee payer.
+    pub transactions: bool,
+    /// Whether the public `credits.aleo` transitions of each address are indexed.
+    pub addresses: bool,
+}
+
+impl IndexConfig {
+    /// Returns `true` if any index is enabled.
+    pub const fn any(&self) -> bool {
+        self.transactions || self.addresses
+    }
+}
+
+/// An entry of the transaction index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct TransactionEntry<N: Network> {
+    /// The ID of the transaction.
+    pub id: N::TransactionID,
+    /// The height of the block that contains the transaction.
+    pub height: u32,
+    /// The status of the transaction, which is 'accepted' or 'rejected'.
+    pub status: &'static str,
+    /// The type of the transaction, which is 'deploy', 'execute' or 'fee'.
+    #[serde(rename = "type")]
+    pub type_: &'static str,
+    /// The locators of the transitions in the transaction, such as `credits.aleo/transfer_public`.
+    pub transitions: Vec<String>,
+    /// The payer of the fee, if the fee is public.
+    pub fee_payer: Option<Address<N>>,
+}
+
+/// The filter of a transaction query, where every given condition must hold.
+#[derive(Clone, Debug)]
+pub struct TransactionFilter<N: Network> {
+    /// The program of a transition in the transaction.
+    pub program: Option<ProgramID<N>>,
+    /// The function of a transition in the transaction, within the given program.
+    pub function: Option<Identifier<N>>,
+    /// The payer of the fee.
+    pub fee_payer: Option<Address<N>>,
+    /// The lowest block height of the transaction.
+    pub from_height: Option<u32>,
+}
+
+/// A secondary index of the transactions in the ledger, by program, function and fee payer.
+///
+/// The index is built in ledger order, as the blocks are added to the ledger.
+pub struct TransactionIndex<N: Network> {
+    inner: RwLock<IndexInner<N>>,
+}
+
+struct IndexInner<N: Network> {
+    /// The indexed transactions, in ledger order, where the position of a transaction is its cursor.
+    entries: Vec<TransactionEntry<N>>,
+    /// The positions of the transactions that call each program.
+    by_program: HashMap<ProgramID<N>, Vec<usize>>,
+    /// The positions of the transactions that call each function.
+    by_function: HashMap<(ProgramID<N>, Identifier<N>), Vec<usize>>,
+    /// The positions of the transactions paid for by each fee payer.
+    by_fee_payer: HashMap<Address<N>, Vec<usize>>,
+    /// The height of the next block to index.
+    next_height: u32,
+}
+
+impl<N: Network> Default for TransactionIndex<N> {
+    fn default() -> Self {
+        Self {
+            inner: RwLock::new(IndexInner {
+                entries: Default::default(),
+                by_program: Default::default(),
+                by_function: Default::default(),
+                by_fee_payer: Default::default(),
+                next_height: 0,
+            }),
+        }
+    }
+}
+
+impl<N: Network> TransactionIndex<N> {
+    /// Returns the height of the next block to index.
+    pub fn next_height(&self) -> u32 {
+        self.inner.read().next_height
+    }
+
+    /// Adds the transactions of the given block to the index, if it is the next block to index.
+    pub fn insert_block(&self, block: &Block<N>) {
+        let mut inner = self.inner.write();
+        // Ensure the block is the next block to index.
+        if block.height() != inner.next_height {
+            return;
+        }
+
+        for confirmed in block.transactions().iter() {
+            let transaction = confirmed.transaction();
+            let position = inner.entries.len();
+
+            // Index the transitions, once per program and function.
+            let mut transitions = Vec::new();
+            for transition in transaction.transitions() {
+                let (program, function) = (*transition.program_id(), *transition.function_name());
+                let positions = inner.by_program.entry(program).or_default();
+                if positions.last() != Some(&position) {
+                    positions.push(position);
+                }
+                let positions = inner.by_function.entry((program, function)).or_default();
+                if positions.last() != Some(&position) {
+                    positions.push(position);
+                }
+                transitions.push(format!("{program}/{function}"));
+            }
+            // Index the fee payer.
+            let fee_payer = transaction.fee_transition().and_then(|fee| fee.payer());
+            if let Some(fee_payer) = fee_payer {
+                inner.by_fee_payer.entry(fee_payer).or_default().push(position);
+            }
+
+            inner.entries.push(TransactionEntry {
+                id: transaction.id(),
+                height: block.height(),
+                status: if confirmed.is_accepted() { "accepted" } else { "rejected" },
+                type_: if transaction.is_deploy() {
+                    "deploy"
+                } else if transaction.is_execute() {
+                    "execute"
+                } else {
+                    "fee"
+                },
+                transitions,
+                fee_payer,
+            });
+        }
+        inner.next_height += 1;
+    }
+
+    /// Returns up to `limit` transactions that match the filter, starting at the given cursor,
+    /// along with the cursor of the next page, if there may be more transactions.
+    pub fn query(
+        &self,
+        filter: &TransactionFilter<N>,
+        cursor: usize,
+        limit: usize,
+    ) -> (Vec<TransactionEntry<N>>, Option<usize>) {
+        let inner = self.inner.read();
+
+        let from_height = filter.from_height.unwrap_or(0);
+
+        // Select the positions of the most specific condition.
+        let positions: &[usize] = match (filter.program, filter.function, filter.fee_payer) {
+            (Some(program), Some(function), _) => {
+                inner.by_function.get(&(program, function)).map(|positions| positions.as_slice()).unwrap_or_default()
+            }
+            (Some(program), None, _) => {
+                inner.by_program.get(&program).map(|positions| positions.as_slice()).unwrap_or_default()
+            }
+            (None, _, Some(fee_payer)) => {
+                inner.by_fee_payer.get(&fee_payer).map(|positions| positions.as_slice()).unwrap_or_default()
+            }
+            // Every transaction matches, so the page is the range of entries after the cursor and the starting height.
+            (None, _, None) => {
+                let start = inner.entries.partition_point(|entry| entry.height < from_height).max(cursor);
+                let end = start.saturating_add(limit).min(inner.entries.len());
+                let entries = inner.entries.get(start..end).unwrap_or_default().to_vec();
+                return (entries, (end < inner.entries.len()).then_some(end));
+            }
+        };
+
+        // Skip the transactions before the cursor and the starting height, as the positions are in ledger order.
+        let start =
+            positions.partition_point(|position| *position < cursor || inner.entries[*position].height < from_height);
+
+        // Collect the transactions that match the remaining conditions.
+        let mut entries = Vec::with_capacity(limit.min(positions.len() - start));
+        for position in &positions[start..] {
+            if entries.len() == limit {
+                return (entries, Some(*position));
+            }
+            let entry = &inner.entries[*position];
+            if filter.fee_payer.is_some() && entry.fee_payer != filter.fee_payer {
+                continue;
+            }
+            entries.push(entry.clone());
+        }
+        (entries, None)
+    }
+}
+
+/// An entry of the address index, which is a transition of `credits.aleo` that publicly involves the address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "")]
+pub struct AddressTransition<N: Network> {
+    /// The ID of the transition.
+    pub transition_id: N::TransitionID,
+    /// The ID of the transaction that contains the transition.
+    pub transaction_id: N::TransactionID,
+    /// The height of the block that contains the transition.
+    pub height: u32,
+    /// The function of the transition, such as `transfer_public`.
+    pub function: Identifier<N>,
+    /// The status of the transaction, which is 'accepted' or 'rejected'.
+    pub status: &'static str,
+}
+
+/// An index of the public `credits.aleo` transitions of each address, such as its transfers and bonds.
+///
+/// The index is built in ledger order, as the blocks are added to the ledger.
+pub struct AddressIndex<N: Network> {
+    inner: RwLock<AddressIndexInner<N>>,
+}
+
+struct AddressIndexInner<N: Network> {
+    /// The transitions of each address, in ledger order.
+    transitions: HashMap<Address<N>, Vec<AddressTransition<N>>>,
+    /// The height of the next block to index.
+    next_height: u32,
+}
+
+impl<N: Network> Default for AddressIndex<N> {
+    fn default() -> Self {
+        Self { inner: RwLock::new(AddressIndexInner { transitions: Default::default(), next_height: 0 }) }
+    }
+}
+
+impl<N: Network> AddressIndex<N> {
+    /// Returns the height of the next block to index.
+    pub fn next_height(&self) -> u32 {
+        self.inner.read().next_height
+    }
+
+    /// Adds the transitions of the given block to the index, if it is the next block to index.
+    pub fn insert_block(&self, block: &Block<N>) {
+        let mut inner = self.inner.write();
+        // Ensure the block is the next block to index.
+        if block.height() != inner.next_height {
+            return;
+        }
+
+        for confirmed in block.transactions().iter() {
+            let transaction = confirmed.transaction();
+            let status = if confirmed.is_accepted() { "accepted" } else { "rejected" };
+
+            for transition in transaction.transitions() {
+                // Only index the transitions of `credits.aleo`.
+                if transition.program_id().to_string() != "credits.aleo" {
+                    continue;
+                }
+                let entry = AddressTransition {
+                    transition_id: *transition.id(),
+                    transaction_id: transaction.id(),
+                    height: block.height(),
+                    function: *transition.function_name(),
+                    status,
+                };
+                for address in public_addresses(transition) {
+                    inner.transitions.entry(address).or_default().push(entry.clone());
+                }
+            }
+        }
+        inner.next_height += 1;
+    }
+
+    /// Returns up to `limit` transitions of the given address, starting at the given cursor,
+    /// along with the cursor of the next page, if there are more transitions.
+    pub fn query(
+        &self,
+        address: &Address<N>,
+        cursor: usize,
+        limit: usize,
+    ) -> (Vec<AddressTransition<N>>, Option<usize>) {
+        let inner = self.inner.read();
+        let transitions = inner.transitions.get(address).map(|transitions| transitions.as_slice()).unwrap_or_default();
+
+        let page = transitions.iter().skip(cursor).take(limit).cloned().collect::<Vec<_>>();
+        let next_cursor = cursor.saturating_add(limit);
+        (page, (next_cursor < transitions.len()).then_some(next_cursor))
+    }
+}
+
+/// Returns the distinct addresses in the public inputs, public outputs and finalize arguments of the transition.
+pub(crate) fn public_addresses<N: Network>(transition: &Transition<N>) -> Vec<Address<N>> {
+    /// Collects the addresses in the given plaintext.
+    fn collect_plaintext<N: Network>(plaintext: &Plaintext<N>, addresses: &mut Vec<Address<N>>) {
+        match plaintext {
+            Plaintext::Literal(Literal::Address(address), _) => {
+                if !addresses.contains(address) {
+                    addresses.push(*address);
+                }
+            }
+            Plaintext::Literal(..) => (),
+            Plaintext::Struct(members, _) => members.values().for_each(|member| collect_plaintext(member, addresses)),
+            Plaintext::Array(elements, _) => elements.iter().for_each(|element| collect_plaintext(element, addresses)),
+        }
+    }
+    /// Collects the addresses in the given finalize arguments.
+    fn collect_arguments<N: Network>(arguments: &[Argument<N>], addresses: &mut Vec<Address<N>>) {
+        for argument in arguments {
+            match argument {
+                Argument::Plaintext(plaintext) => collect_plaintext(plaintext, addresses),
+                Argument::Future(future) => collect_arguments(future.arguments(), addresses),
+            }
+        }
+    }
+
+    let mut addresses = Vec::new();
+    for input in transition.inputs() {
+        if let Input::Public(_, Some(plaintext)) = input {
+            collect_plaintext(plaintext, &mut addresses);
+        }
+    }
+    for output in transition.outputs() {
+        match output {
+            Output::Public(_, Some(plaintext)) => collect_plaintext(plaintext, &mut addresses),
+            Output::Future(_, Some(future)) => collect_arguments(future.arguments(), &mut addresses),
+            _ => (),
+        }
+    }
+    addresses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{FromBytes, Testnet3};
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns a filter with no conditions.
+    fn sample_filter() -> TransactionFilter<CurrentNetwork> {
+        TransactionFilter { program: None, function: None, fee_payer: None, from_height: None }
+    }
+
+    #[test]
+    fn test_transaction_index() {
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let num_transactions = genesis.transactions().len();
+        assert!(num_transactions > 1);
+
+        // Index the genesis block.
+        let index = TransactionIndex::<CurrentNetwork>::default();
+        index.insert_block(&genesis);
+        assert_eq!(index.next_height(), 1);
+        // Ensure a block is only indexed once.
+        index.insert_block(&genesis);
+        assert_eq!(index.query(&sample_filter(), 0, usize::MAX).0.len(), num_transactions);
+
+        // Ensure the transactions are queried by program and function.
+        let credits = ProgramID::from_str("credits.aleo").unwrap();
+        let filter = TransactionFilter { program: Some(credits), ..sample_filter() };
+        let (entries, cursor) = index.query(&filter, 0, usize::MAX);
+        assert_eq!((entries.len(), cursor), (num_transactions, None));
+        let function = Identifier::from_str(entries[0].transitions[0].split('/').nth(1).unwrap()).unwrap();
+        let filter = TransactionFilter { program: Some(credits), function: Some(function), ..sample_filter() };
+        assert!(!index.query(&filter, 0, usize::MAX).0.is_empty());
+        let filter = TransactionFilter { function: Some(Identifier::from_str("missing").unwrap()), ..filter };
+        assert!(index.query(&filter, 0, usize::MAX).0.is_empty());
+        let filter = TransactionFilter { from_height: Some(1), ..sample_filter() };
+        assert!(index.query(&filter, 0, usize::MAX).0.is_empty());
+
+        // Ensure the transactions are paginated.
+        let (first_page, cursor) = index.query(&sample_filter(), 0, 1);
+        assert_eq!(first_page.len(), 1);
+        let (second_page, _) = index.query(&sample_filter(), cursor.unwrap(), 1);
+        assert_eq!(second_page.len(), 1);
+        assert_ne!(first_page[0].id, second_page[0].id);
+        let (last_page, cursor) = index.query(&sample_filter(), num_transactions - 1, 1);
+        assert_eq!((last_page.len(), cursor), (1, None));
+        assert!(index.query(&sample_filter(), num_transactions + 1, 1).0.is_empty());
+    }
+
+    #[test]
+    fn test_address_index() {
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+
+        // Index the genesis block.
+        let index = AddressIndex::<CurrentNetwork>::default();
+        index.insert_block(&genesis);
+        assert_eq!(index.next_height(), 1);
+
+        // Ensure the transitions of each address in the genesis block are indexed.
+        let mut num_indexed = 0;
+        for transition in genesis.transitions() {
+            for address in public_addresses(transition) {
+                let (transitions, _) = index.query(&address, 0, usize::MAX);
+                assert!(transitions.iter().any(|entry| entry.transition_id == *transition.id()));
+                num_indexed += 1;
+            }
+        }
+        assert!(num_indexed > 0);
+
+        // Ensure the transitions are paginated.
+        let address = public_addresses(genesis.transitions().next().unwrap())[0];
+        let num_transitions = index.query(&address, 0, usize::MAX).0.len();
+        let (page, cursor) = index.query(&address, 0, 1);
+        assert_eq!(page.len(), 1);
+        assert_eq!(cursor, (num_transitions > 1).then_some(1));
+    }
+}
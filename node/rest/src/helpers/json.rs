@@ -0,0 +1,29 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::response::{IntoResponse, Response};
+use axum_extra::response::ErasedJson;
+use serde::Serialize;
+
+/// The JSON response of a REST handler.
+///
+/// The response is pretty-printed when it is served over REST, and is serialized directly
+/// into the result of a JSON-RPC method.
+pub struct RestJson<T>(pub T);
+
+impl<T: Serialize> IntoResponse for RestJson<T> {
+    fn into_response(self) -> Response {
+        ErasedJson::pretty(self.0).into_response()
+    }
+}
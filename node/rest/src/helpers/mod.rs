@@ -17,3 +17,15 @@ pub use auth::*;
 
 mod error;
 pub use error::*;
+
+mod index;
+pub use index::*;
+
+mod json;
+pub use json::*;
+
+mod openapi;
+pub use openapi::*;
+
+mod rate_limit;
+pub use rate_limit::*;
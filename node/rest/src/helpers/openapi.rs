@@ -0,0 +1,601 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{is_protected, Scope};
+use crate::{
+    routes::{AddressTransitionPage, Blocks, TransactionPage, TransactionStatus, TransactionStatusResponse},
+    REST_ROUTES,
+};
+use snarkvm::{
+    console::prelude::{Network, Zero},
+    prelude::Field,
+};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// The schema of a request or response body.
+#[derive(Copy, Clone, Debug)]
+pub enum Schema {
+    /// A JSON value of the given primitive type, such as `string`, `integer` or `boolean`.
+    Type(&'static str),
+    /// The given component schema.
+    Ref(&'static str),
+    /// An array of the given component schema.
+    Array(&'static str),
+    /// A stream of server-sent events, whose data is the given component schema.
+    Events(&'static str),
+}
+
+impl Schema {
+    /// Returns the JSON schema.
+    fn to_json(self) -> Value {
+        match self {
+            Self::Type(type_) => json!({ "type": type_ }),
+            Self::Ref(name) | Self::Events(name) => json!({ "$ref": format!("#/components/schemas/{name}") }),
+            Self::Array(name) => {
+                json!({ "type": "array", "items": { "$ref": format!("#/components/schemas/{name}") } })
+            }
+        }
+    }
+
+    /// Returns the media type of the schema.
+    const fn media_type(&self) -> &'static str {
+        match self {
+            Self::Events(_) => "text/event-stream",
+            _ => "application/json",
+        }
+    }
+}
+
+/// The documentation of a REST operation.
+#[derive(Copy, Clone, Debug)]
+pub struct OperationDoc {
+    /// The summary of the operation.
+    pub summary: &'static str,
+    /// The names of the query parameters.
+    pub query: &'static [&'static str],
+    /// The schema of the request body, if any.
+    pub request: Option<Schema>,
+    /// The schema of the response body.
+    pub response: Schema,
+}
+
+/// Returns the documentation of an operation without a request body.
+pub(crate) const fn doc(summary: &'static str, query: &'static [&'static str], response: Schema) -> OperationDoc {
+    OperationDoc { summary, query, request: None, response }
+}
+
+/// Returns the documentation of an operation with a request body.
+pub(crate) const fn doc_with_body(summary: &'static str, request: Schema, response: Schema) -> OperationDoc {
+    OperationDoc { summary, query: &[], request: Some(request), response }
+}
+
+/// The documentation of a REST route.
+#[derive(Copy, Clone, Debug)]
+pub struct RouteDoc {
+    /// The HTTP method, in lowercase.
+    pub method: &'static str,
+    /// The path, in the syntax of the route table (e.g. `/testnet3/block/:height_or_hash`).
+    pub path: &'static str,
+    /// The scope that grants access to the route, or `None` if the route checks the scope of each call.
+    pub scope: Option<Scope>,
+    /// The documentation of the operation.
+    pub operation: OperationDoc,
+}
+
+/// A response body, whose component schema is derived from the serialization of a sample of each of its variants.
+pub(crate) trait Component: Serialize + Sized {
+    /// Returns a sample of each variant of the response body, where each optional field is both set and unset.
+    fn samples() -> Vec<Self>;
+
+    /// Returns the name of the property that tags the variants of the response body, if any.
+    fn tag() -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the component schemas of the properties that hold other components, by property name.
+    /// Note: The empty name refers to the response body itself, if it is an array of components.
+    fn references() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Returns the component schema, as derived from the samples.
+    fn schema() -> Value {
+        let samples = Self::samples()
+            .iter()
+            .map(|sample| serde_json::to_value(sample).expect("A response body is serializable"))
+            .collect::<Vec<_>>();
+        derive_schema(&samples.iter().collect::<Vec<_>>(), Self::tag(), Self::references())
+    }
+}
+
+/// Derives the JSON schema of the given values, where the properties of the given names refer to the given components.
+fn derive_schema(values: &[&Value], tag: Option<&str>, references: &[(&str, &str)]) -> Value {
+    // Returns the schema of a reference to the given component, or to an array of it.
+    let reference = |component: &str, values: &[&Value]| -> Value {
+        let reference = json!({ "$ref": format!("#/components/schemas/{component}") });
+        match values.iter().any(|value| value.is_array()) {
+            true => json!({ "type": "array", "items": reference }),
+            false => reference,
+        }
+    };
+
+    // Group the values by their type, in order of appearance.
+    let mut groups = Vec::<(&str, Vec<&Value>)>::new();
+    for value in values.iter().copied().filter(|value| !value.is_null()) {
+        let type_ = match value {
+            Value::Bool(_) => "boolean",
+            Value::Number(number) if number.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) | Value::Null => "object",
+        };
+        match groups.iter_mut().find(|(group, _)| *group == type_) {
+            Some((_, group)) => group.push(value),
+            None => groups.push((type_, vec![value])),
+        }
+    }
+
+    // Derive the schema of each type.
+    let mut schemas = groups
+        .into_iter()
+        .map(|(type_, values)| match type_ {
+            "object" => {
+                let objects = values.iter().filter_map(|value| value.as_object()).collect::<Vec<_>>();
+                let mut properties = Map::new();
+                let mut required = Vec::new();
+                for key in objects.iter().flat_map(|object| object.keys()) {
+                    if properties.contains_key(key) {
+                        continue;
+                    }
+                    let values = objects.iter().filter_map(|object| object.get(key)).collect::<Vec<_>>();
+                    // A property is required if every variant has it.
+                    if values.len() == objects.len() {
+                        required.push(key.clone());
+                    }
+                    let schema = match references.iter().find(|(name, _)| *name == key.as_str()) {
+                        Some((_, component)) => reference(component, &values),
+                        // The tag of the variants is one of the names of the variants.
+                        None if tag == Some(key.as_str()) => json!({ "type": "string", "enum": values }),
+                        None => derive_schema(&values, None, &[]),
+                    };
+                    properties.insert(key.clone(), schema);
+                }
+                json!({ "type": "object", "required": required, "properties": properties })
+            }
+            "array" => match references.iter().find(|(name, _)| name.is_empty()) {
+                Some((_, component)) => reference(component, &values),
+                None => {
+                    let items = values.iter().filter_map(|value| value.as_array()).flatten().collect::<Vec<_>>();
+                    json!({ "type": "array", "items": derive_schema(&items, None, &[]) })
+                }
+            },
+            type_ => json!({ "type": type_ }),
+        })
+        .collect::<Vec<_>>();
+
+    // Combine the schemas of the types, which may be null.
+    let mut schema = match schemas.len() {
+        0 => json!({}),
+        1 => schemas.remove(0),
+        _ => json!({ "oneOf": schemas }),
+    };
+    if values.iter().any(|value| value.is_null()) {
+        schema["nullable"] = json!(true);
+    }
+    schema
+}
+
+impl<N: Network> Component for TransactionStatusResponse<N> {
+    fn samples() -> Vec<Self> {
+        let field = Field::<N>::zero();
+        let (id, block_hash, fee_transaction_id) = (field.into(), field.into(), field.into());
+        let (height, index, round, timestamp) = (0, 0, 0, 0);
+        let statuses = [
+            TransactionStatus::Unknown,
+            TransactionStatus::Queued,
+            TransactionStatus::Ready { worker_id: 0 },
+            TransactionStatus::Proposed { round, timestamp },
+            TransactionStatus::Certified { round, certificate_id: field, timestamp },
+            TransactionStatus::Confirmed { height, block_hash, index, timestamp },
+            TransactionStatus::Rejected { height, block_hash, index, fee_transaction_id, timestamp },
+            TransactionStatus::Aborted { height, block_hash, timestamp },
+        ];
+        let seen_at = [None, Some(timestamp)];
+        statuses
+            .into_iter()
+            .zip(seen_at.into_iter().cycle())
+            .map(|(status, seen_at)| Self { id, status, seen_at })
+            .collect()
+    }
+
+    fn tag() -> Option<&'static str> {
+        Some("status")
+    }
+}
+
+impl<N: Network> Component for Blocks<N> {
+    fn samples() -> Vec<Self> {
+        let page = |next_cursor| Self::Page { blocks: vec![], next_cursor };
+        vec![Self::Range(vec![]), page(Some(0)), page(None)]
+    }
+
+    fn references() -> &'static [(&'static str, &'static str)] {
+        &[("", "Block"), ("blocks", "Block")]
+    }
+}
+
+impl<N: Network> Component for TransactionPage<N> {
+    fn samples() -> Vec<Self> {
+        let page = |next_cursor, indexed_height| Self { transactions: vec![], next_cursor, indexed_height };
+        vec![page(Some(0), Some(0)), page(None, None)]
+    }
+
+    fn references() -> &'static [(&'static str, &'static str)] {
+        &[("transactions", "TransactionEntry")]
+    }
+}
+
+impl<N: Network> Component for AddressTransitionPage<N> {
+    fn samples() -> Vec<Self> {
+        let page = |next_cursor| Self { transitions: vec![], next_cursor };
+        vec![page(Some(0)), page(None)]
+    }
+
+    fn references() -> &'static [(&'static str, &'static str)] {
+        &[("transitions", "AddressTransition")]
+    }
+}
+
+/// Returns the component schemas of the request and response bodies.
+fn components<N: Network>() -> Value {
+    let mut components = json!({
+        "Block": { "type": "object", "description": "A block, as serialized by snarkVM.", "required": ["block_hash", "previous_hash", "header", "transactions"] },
+        "Transaction": { "type": "object", "description": "A deploy, execute, or fee transaction, as serialized by snarkVM.", "required": ["type", "id"] },
+        "ConfirmedTransaction": { "type": "object", "description": "A transaction with its status and index in a block.", "required": ["status", "type", "index", "transaction"] },
+        "ProverSolution": { "type": "object", "description": "A prover solution, as serialized by snarkVM." },
+        "Transmission": { "type": "object", "description": "An unconfirmed transaction or solution in the memory pool." },
+        "Committee": { "type": "object", "required": ["starting_round", "members", "total_stake"] },
+        "Program": { "type": "string", "description": "The source of an Aleo program." },
+        "MappingValue": { "description": "The mapping value, which is null if the key is absent, or an object with `data` and `height` if metadata is requested." },
+        "QueueState": {
+            "type": "object",
+            "properties": {
+                "num_entries": { "type": "integer", "description": "The number of entries in the queue." },
+                "max_entries": { "type": "integer", "description": "The maximum number of entries in the queue." },
+                "num_bytes": { "type": "integer", "description": "The total size of the entries in the queue, in bytes." },
+                "max_bytes": { "type": "integer", "description": "The maximum total size of the entries in the queue, in bytes." },
+                "num_dropped": { "type": "integer", "description": "The number of entries evicted or rejected, as the queue was full." },
+            },
+        },
+        "MemoryPoolQueue": {
+            "type": "object",
+            "properties": {
+                "solutions": { "$ref": "#/components/schemas/QueueState" },
+                "transactions": { "$ref": "#/components/schemas/QueueState" },
+                "ready": { "$ref": "#/components/schemas/QueueState", "description": "The combined ready queues of the workers." },
+            },
+        },
+        "IpAddress": { "type": "string", "example": "127.0.0.1" },
+        "SocketAddress": { "type": "string", "example": "127.0.0.1:4133" },
+        "PeerRequest": {
+            "type": "object",
+            "required": ["ip"],
+            "properties": {
+                "ip": { "$ref": "#/components/schemas/SocketAddress" },
+                "gateway": { "type": "boolean", "description": "Whether the peer is a validator in the memory pool network." },
+            },
+        },
+        "BanRequest": { "type": "object", "required": ["ip"], "properties": { "ip": { "$ref": "#/components/schemas/IpAddress" } } },
+        "TrustedPeers": {
+            "type": "object",
+            "properties": {
+                "peers": { "type": "array", "items": { "$ref": "#/components/schemas/SocketAddress" } },
+                "validators": { "type": "array", "nullable": true, "items": { "$ref": "#/components/schemas/SocketAddress" } },
+            },
+        },
+        "JsonRpcRequest": {
+            "description": "A JSON-RPC 2.0 request, or a batch of requests.",
+            "oneOf": [
+                { "type": "object", "required": ["jsonrpc", "method"], "properties": { "jsonrpc": { "type": "string", "enum": ["2.0"] }, "method": { "type": "string" }, "params": {}, "id": {} } },
+                { "type": "array", "items": { "type": "object" } },
+            ],
+        },
+        "JsonRpcResponse": {
+            "description": "A JSON-RPC 2.0 response, or a batch of responses.",
+            "oneOf": [
+                { "type": "object", "required": ["jsonrpc", "id"], "properties": { "jsonrpc": { "type": "string", "enum": ["2.0"] }, "result": {}, "error": { "type": "object" }, "id": {} } },
+                { "type": "array", "items": { "type": "object" } },
+            ],
+        },
+        "Simulation": {
+            "type": "object",
+            "required": ["id", "status"],
+            "properties": {
+                "id": { "type": "string" },
+                "status": { "enum": ["invalid", "accepted", "rejected", "aborted"] },
+                "error": { "type": "string" },
+                "fee": { "type": "integer" },
+                "cost": {
+                    "type": "object",
+                    "properties": {
+                        "total": { "type": "integer" },
+                        "storage": { "type": "integer" },
+                        "finalize": { "type": "integer" },
+                        "namespace": { "type": "integer" },
+                    },
+                },
+            },
+        },
+        "FeeEstimate": {
+            "type": "object",
+            "description": "The finalize cost of a call, which excludes the storage cost, as it depends on the size of the proven execution.",
+            "properties": {
+                "program": { "type": "string" },
+                "function": { "type": "string" },
+                "finalize_cost": { "type": "integer", "description": "The total finalize cost, in microcredits, without the storage cost." },
+                "transitions": {
+                    "type": "array",
+                    "items": { "type": "object", "properties": { "transition": { "type": "string" }, "finalize_cost": { "type": "integer" } } },
+                },
+            },
+        },
+        "Balance": {
+            "type": "object",
+            "properties": { "address": { "type": "string" }, "balance": { "type": "integer" }, "height": { "type": "integer" } },
+        },
+        "Bonded": {
+            "type": "object",
+            "properties": { "address": { "type": "string" }, "bonded": {}, "unbonding": {}, "height": { "type": "integer" } },
+        },
+        "TransactionEntry": {
+            "type": "object",
+            "properties": {
+                "id": { "type": "string" },
+                "height": { "type": "integer" },
+                "status": { "type": "string" },
+                "type": { "type": "string" },
+                "transitions": { "type": "array", "items": { "type": "string" } },
+                "fee_payer": { "type": "string", "nullable": true },
+            },
+        },
+        "AddressTransition": {
+            "type": "object",
+            "properties": {
+                "transition_id": { "type": "string" },
+                "transaction_id": { "type": "string" },
+                "height": { "type": "integer" },
+                "function": { "type": "string" },
+                "status": { "type": "string" },
+            },
+        },
+    });
+
+    // Derive the schemas of the response bodies that are defined in this crate.
+    components["TransactionStatus"] = TransactionStatusResponse::<N>::schema();
+    components["BlockPage"] = Blocks::<N>::schema();
+    components["BlockPage"]["description"] =
+        json!("The blocks in the given range, or a page of blocks if no range is given.");
+    components["TransactionPage"] = TransactionPage::<N>::schema();
+    components["AddressTransitionPage"] = AddressTransitionPage::<N>::schema();
+    components
+}
+
+/// Returns the OpenAPI 3 document of the REST routes, where the routes of the given scopes require a JWT.
+pub fn openapi_document<N: Network>(protected_scopes: &[Scope]) -> Value {
+    let mut paths = Map::new();
+    for route in REST_ROUTES {
+        // Convert the path parameters from `:name` to `{name}`.
+        let path = route
+            .path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{name}}}"),
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        // Prepare the path and query parameters.
+        let path_parameters = route
+            .path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }));
+        let query_parameters = route
+            .operation
+            .query
+            .iter()
+            .map(|name| json!({ "name": name, "in": "query", "required": false, "schema": { "type": "string" } }));
+
+        // Prepare the operation.
+        let response = route.operation.response;
+        let mut operation = json!({
+            "summary": route.operation.summary,
+            "parameters": path_parameters.chain(query_parameters).collect::<Vec<_>>(),
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "content": { response.media_type(): { "schema": response.to_json() } },
+                },
+            },
+        });
+        if let Some(request) = route.operation.request {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { request.media_type(): { "schema": request.to_json() } },
+            });
+        }
+
+        // Require a JWT if the scope of the route is protected, or if the route checks the scope of each call
+        // and any scope is protected.
+        let is_protected = match route.scope {
+            Some(scope) => is_protected(scope, protected_scopes),
+            None => !protected_scopes.is_empty(),
+        };
+        if is_protected {
+            operation["security"] = json!([{ "bearerAuth": [] }]);
+            operation["responses"]["401"] = json!({ "description": "The JSON Web Token is missing or invalid." });
+        }
+
+        // Add the operation to the path.
+        match paths.entry(path).or_insert_with(|| json!({})) {
+            Value::Object(operations) => operations.insert(route.method.to_string(), operation),
+            _ => unreachable!("Each path is initialized as an object"),
+        };
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "snarkOS REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": components::<N>(),
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers::index::public_addresses, AddressIndex, TransactionFilter, TransactionIndex};
+    use snarkvm::prelude::{block::Block, FromBytes, Testnet3};
+
+    use std::collections::HashSet;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns `true` if the given value conforms to the given schema, where the references resolve to the given schemas.
+    fn conforms(value: &Value, schema: &Value, schemas: &Map<String, Value>) -> bool {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            return conforms(value, &schemas[name], schemas);
+        }
+        if value.is_null() {
+            return schema["nullable"] == json!(true);
+        }
+        if let Some(variants) = schema["oneOf"].as_array() {
+            return variants.iter().filter(|variant| conforms(value, variant, schemas)).count() == 1;
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            if !variants.contains(value) {
+                return false;
+            }
+        }
+        match (schema["type"].as_str(), value) {
+            (Some("object"), Value::Object(object)) => {
+                let required = schema["required"].as_array().cloned().unwrap_or_default();
+                let properties = schema["properties"].as_object();
+                required.iter().all(|key| object.contains_key(key.as_str().unwrap()))
+                    && object.iter().all(|(key, value)| match properties {
+                        // Ensure every property of the value is declared, if the schema declares its properties.
+                        Some(properties) => properties.get(key).is_some_and(|schema| conforms(value, schema, schemas)),
+                        None => true,
+                    })
+            }
+            (Some("array"), Value::Array(items)) => items.iter().all(|item| conforms(item, &schema["items"], schemas)),
+            (Some("integer"), Value::Number(number)) => !number.is_f64(),
+            (Some("number"), Value::Number(_)) => true,
+            (Some("string"), Value::String(_)) => true,
+            (Some("boolean"), Value::Bool(_)) => true,
+            (Some(_), _) => false,
+            (None, _) => true,
+        }
+    }
+
+    #[test]
+    fn test_routes_are_unique() {
+        // Ensure no route is declared twice.
+        let routes = REST_ROUTES.iter().map(|route| (route.method, route.path)).collect::<HashSet<_>>();
+        assert_eq!(routes.len(), REST_ROUTES.len());
+    }
+
+    #[test]
+    fn test_schemas_are_defined() {
+        let document = openapi_document::<CurrentNetwork>(&[]);
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        // Ensure every referenced schema is defined.
+        let document = document.to_string();
+        for reference in document.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "The schema '{name}' is referenced but not defined");
+        }
+        // Ensure the types are OpenAPI 3.0 types, as nullable types are only written as arrays in OpenAPI 3.1.
+        assert!(!document.contains("\"type\":["), "The OpenAPI 3.0 document has an array of types");
+    }
+
+    #[test]
+    fn test_derived_schemas() {
+        let document = openapi_document::<CurrentNetwork>(&[]);
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let conforms_to = |value: Value, name: &str| conforms(&value, &schemas[name], schemas);
+
+        // Ensure each sample of a derived schema conforms to it.
+        for sample in TransactionStatusResponse::<CurrentNetwork>::samples() {
+            assert!(conforms_to(serde_json::to_value(sample).unwrap(), "TransactionStatus"));
+        }
+        assert!(!conforms_to(json!({ "id": "at1", "status": "lost", "seen_at": null }), "TransactionStatus"));
+        assert!(!conforms_to(json!({ "id": "at1", "status": "queued", "seen": 0 }), "TransactionStatus"));
+
+        // Ensure the responses of the genesis block conform to their schemas.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let blocks = Blocks::Range(vec![genesis.clone()]);
+        assert!(conforms_to(serde_json::to_value(blocks).unwrap(), "BlockPage"));
+        let blocks = Blocks::Page { blocks: vec![genesis.clone()], next_cursor: Some(1) };
+        assert!(conforms_to(serde_json::to_value(blocks).unwrap(), "BlockPage"));
+
+        let index = TransactionIndex::<CurrentNetwork>::default();
+        index.insert_block(&genesis);
+        let filter = TransactionFilter { program: None, function: None, fee_payer: None, from_height: None };
+        let (transactions, next_cursor) = index.query(&filter, 0, 1);
+        let page = TransactionPage { transactions, next_cursor, indexed_height: Some(0) };
+        assert!(conforms_to(serde_json::to_value(page).unwrap(), "TransactionPage"));
+
+        let index = AddressIndex::<CurrentNetwork>::default();
+        index.insert_block(&genesis);
+        let address = public_addresses(genesis.transitions().next().unwrap())[0];
+        let (transitions, next_cursor) = index.query(&address, 0, 1);
+        assert_eq!(transitions.len(), 1);
+        let page = AddressTransitionPage { transitions, next_cursor };
+        assert!(conforms_to(serde_json::to_value(page).unwrap(), "AddressTransitionPage"));
+    }
+
+    #[test]
+    fn test_protected_routes_require_a_token() {
+        let security =
+            |document: &Value, method: &str, path: &str| document["paths"][path][method].get("security").cloned();
+
+        // Ensure the admin routes always require a token, and the other routes only if their scope is protected.
+        let document = openapi_document::<CurrentNetwork>(&[Scope::Read]);
+        assert!(security(&document, "get", "/testnet3/node/address").is_some());
+        assert!(security(&document, "get", "/testnet3/block/{height_or_hash}").is_some());
+        assert!(security(&document, "post", "/testnet3/transaction/broadcast").is_none());
+        assert!(security(&document, "post", "/testnet3/rpc").is_some());
+
+        // Ensure only the admin routes require a token by default.
+        let document = openapi_document::<CurrentNetwork>(&[]);
+        assert!(security(&document, "get", "/testnet3/node/address").is_some());
+        assert!(security(&document, "get", "/testnet3/block/{height_or_hash}").is_none());
+        assert!(security(&document, "post", "/testnet3/rpc").is_none());
+    }
+}
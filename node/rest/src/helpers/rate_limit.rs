@@ -0,0 +1,260 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Claims;
+use crate::{rpc::rpc_request_classes, MAX_BODY_SIZE};
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{header::RETRY_AFTER, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_extra::{
+    headers::authorization::{Authorization, Bearer},
+    TypedHeader,
+};
+use governor::{
+    clock::{Clock, DefaultClock},
+    DefaultKeyedRateLimiter,
+    Quota,
+    RateLimiter,
+};
+use std::{
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    sync::Arc,
+    time::Duration,
+};
+
+/// The class of a REST route, which determines the weight of a request against the rate limits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RouteClass {
+    /// A route that reads a single item, such as a block or a mapping value.
+    Light,
+    /// A route that adds a transmission to the memory pool, or computes a cost.
+    Medium,
+    /// A route that reads a range of items, or executes a program.
+    Heavy,
+}
+
+impl RouteClass {
+    /// Returns the class of the given route path, as declared in the route table.
+    pub fn from_path(path: &str) -> Self {
+        match path {
+            "/testnet3/blocks"
+            | "/testnet3/transactions"
+            | "/testnet3/address/:address/transitions"
+            | "/testnet3/transaction/simulate" => Self::Heavy,
+            "/testnet3/transaction/broadcast"
+            | "/testnet3/solution/broadcast"
+            | "/testnet3/fee/estimate"
+            | "/testnet3/block/:height_or_hash/transactions" => Self::Medium,
+            _ => Self::Light,
+        }
+    }
+
+    /// Returns the class of a request to the given route path, with the given query string.
+    /// Note: A block or transaction subscription from a given height replays a range of blocks, so it is heavy.
+    pub fn from_path_and_query(path: &str, query: Option<&str>) -> Self {
+        let is_replay =
+            query.is_some_and(|query| query.split('&').any(|pair| pair.split('=').next() == Some("from_height")));
+        match path {
+            "/testnet3/subscribe/blocks" | "/testnet3/subscribe/transactions" if is_replay => Self::Heavy,
+            path => Self::from_path(path),
+        }
+    }
+
+    /// Returns the number of requests that a request to this class of route counts as.
+    pub const fn weight(&self) -> u32 {
+        match self {
+            Self::Light => 1,
+            Self::Medium => 2,
+            Self::Heavy => 5,
+        }
+    }
+}
+
+/// The rate limits of the REST server.
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    /// The requests per second (RPS) for each IP.
+    pub ip_rps: u32,
+    /// The requests per second (RPS) for each authenticated token.
+    pub token_rps: u32,
+    /// The IPs that are exempt from the rate limits.
+    pub allowlist: Vec<IpAddr>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self { ip_rps: 10, token_rps: 100, allowlist: vec![] }
+    }
+}
+
+/// The rate limiter of the REST server, which tracks requests by IP, or by token if authenticated.
+pub struct RateLimiterSet {
+    /// The rate limiter for each IP.
+    ips: DefaultKeyedRateLimiter<IpAddr>,
+    /// The rate limiter for each token.
+    tokens: DefaultKeyedRateLimiter<String>,
+    /// The burst size of the IP rate limiter.
+    ip_burst: NonZeroU32,
+    /// The burst size of the token rate limiter.
+    token_burst: NonZeroU32,
+    /// The IPs that are exempt from the rate limits.
+    allowlist: Vec<IpAddr>,
+}
+
+impl RateLimiterSet {
+    /// Initializes a new rate limiter from the given rate limits.
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let ip_burst = NonZeroU32::new(config.ip_rps).unwrap_or(NonZeroU32::MIN);
+        let token_burst = NonZeroU32::new(config.token_rps).unwrap_or(NonZeroU32::MIN);
+        // Note: The buckets replenish at one request per second, up to their burst size.
+        let quota = |burst| Quota::with_period(Duration::from_secs(1)).map(|quota| quota.allow_burst(burst));
+        Self {
+            ips: RateLimiter::keyed(quota(ip_burst).expect("The rate limiting period is non-zero")),
+            tokens: RateLimiter::keyed(quota(token_burst).expect("The rate limiting period is non-zero")),
+            ip_burst,
+            token_burst,
+            allowlist: config.allowlist.clone(),
+        }
+    }
+
+    /// Returns `None` if the request is permitted, or the duration to wait before retrying otherwise.
+    ///
+    /// The request weighs as much as the given classes of routes, such as the methods of a JSON-RPC batch.
+    /// An authenticated request is charged to its own token, as several tokens may be issued for the same subject.
+    pub fn check(&self, ip: IpAddr, token: Option<String>, classes: &[RouteClass]) -> Option<Duration> {
+        // Skip the rate limits for allowlisted IPs.
+        if self.allowlist.contains(&ip) {
+            return None;
+        }
+        // Note: Each weight is capped at the burst size, so heavy routes remain possible with a small burst.
+        let weight = |burst: NonZeroU32| {
+            let weight = classes.iter().map(|class| class.weight().min(burst.get())).fold(0, u32::saturating_add);
+            NonZeroU32::new(weight).unwrap_or(NonZeroU32::MIN)
+        };
+        // Check the rate limit of the token if the request is authenticated, and of the IP otherwise.
+        let outcome = match token {
+            Some(token) => self.tokens.check_key_n(&token, weight(self.token_burst)),
+            None => self.ips.check_key_n(&ip, weight(self.ip_burst)),
+        };
+        match outcome {
+            Ok(Ok(())) => None,
+            Ok(Err(not_until)) => Some(not_until.wait_time_from(DefaultClock::default().now())),
+            // The request weighs more than the burst size, such as a large JSON-RPC batch, so it is never permitted.
+            Err(_) => Some(Duration::from_secs(1)),
+        }
+    }
+
+    /// Removes the state of the IPs and tokens that have fully replenished.
+    pub fn retain_recent(&self) {
+        self.ips.retain_recent();
+        self.tokens.retain_recent();
+    }
+}
+
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiterSet>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    path: Option<MatchedPath>,
+    auth: Option<TypedHeader<Authorization<Bearer>>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    // Determine the classes of the routes that the request calls.
+    let (classes, request) = match path.as_ref().map(|path| path.as_str()) {
+        // Charge a JSON-RPC request with the route of each method it calls, as a batch can call many methods.
+        Some("/testnet3/rpc") => {
+            let (parts, body) = request.into_parts();
+            let Ok(body) = axum::body::to_bytes(body, MAX_BODY_SIZE).await else {
+                return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+            };
+            (rpc_request_classes(&body), Request::from_parts(parts, Body::from(body)))
+        }
+        path => {
+            let query = request.uri().query();
+            (vec![path.map_or(RouteClass::Light, |path| RouteClass::from_path_and_query(path, query))], request)
+        }
+    };
+    // Determine the token, if the request carries a valid token.
+    let token = auth
+        .filter(|auth| Claims::from_jwt_str(auth.token()).is_ok_and(|claims| !claims.is_expired()))
+        .map(|auth| auth.token().to_string());
+
+    match limiter.check(addr.ip(), token, &classes) {
+        None => next.run(request).await,
+        Some(wait_time) => {
+            // Round the wait time up to the next second, as 'Retry-After' is in whole seconds.
+            let retry_after = wait_time.as_secs() + u64::from(wait_time.subsec_nanos() > 0);
+            let retry_after = retry_after.max(1);
+            (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, retry_after.to_string())], "Too many requests")
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limits() {
+        let ip = IpAddr::from([1, 2, 3, 4]);
+        let config = RateLimitConfig { ip_rps: 10, token_rps: 20, allowlist: vec![IpAddr::from([5, 6, 7, 8])] };
+        let limiter = RateLimiterSet::new(&config);
+
+        // Ensure the heavy requests consume the IP limit faster than the light requests.
+        assert!((0..2).all(|_| limiter.check(ip, None, &[RouteClass::Heavy]).is_none()));
+        assert!(limiter.check(ip, None, &[RouteClass::Light]).is_some_and(|wait_time| wait_time > Duration::ZERO));
+
+        // Ensure an authenticated token has a separate limit.
+        let token = Claims::new_with_scopes("operator".to_string(), vec![], 60).to_jwt_string().unwrap();
+        assert!((0..20).all(|_| limiter.check(ip, Some(token.clone()), &[RouteClass::Light]).is_none()));
+        assert!(limiter.check(ip, Some(token), &[RouteClass::Light]).is_some());
+
+        // Ensure another token of the same subject has its own limit.
+        let token = Claims::new_with_scopes("operator".to_string(), vec![], 120).to_jwt_string().unwrap();
+        assert!(limiter.check(ip, Some(token), &[RouteClass::Light]).is_none());
+
+        // Ensure a batch is charged with the weight of each of its routes, and is rejected if it exceeds the burst.
+        let ip = IpAddr::from([2, 3, 4, 5]);
+        assert!(limiter.check(ip, None, &[RouteClass::Heavy, RouteClass::Light, RouteClass::Medium]).is_none());
+        assert!(limiter.check(ip, None, &[RouteClass::Medium, RouteClass::Light]).is_some());
+        let ip = IpAddr::from([3, 4, 5, 6]);
+        assert!(limiter.check(ip, None, &[RouteClass::Heavy; 3]).is_some());
+
+        // Ensure an allowlisted IP is never limited.
+        assert!((0..100).all(|_| limiter.check(config.allowlist[0], None, &[RouteClass::Heavy]).is_none()));
+    }
+
+    #[test]
+    fn test_route_class() {
+        assert_eq!(RouteClass::from_path("/testnet3/blocks"), RouteClass::Heavy);
+        assert_eq!(RouteClass::from_path("/testnet3/transaction/broadcast"), RouteClass::Medium);
+        assert_eq!(RouteClass::from_path("/testnet3/block/height/latest"), RouteClass::Light);
+
+        // Ensure a subscription is heavy only if it replays the blocks from a given height.
+        let path = "/testnet3/subscribe/blocks";
+        assert_eq!(RouteClass::from_path_and_query(path, None), RouteClass::Light);
+        assert_eq!(RouteClass::from_path_and_query(path, Some("from_height=0")), RouteClass::Heavy);
+        let path = "/testnet3/subscribe/transactions";
+        assert_eq!(RouteClass::from_path_and_query(path, Some("a=1&from_height=0")), RouteClass::Heavy);
+        let path = "/testnet3/subscribe/memoryPool";
+        assert_eq!(RouteClass::from_path_and_query(path, Some("from_height=0")), RouteClass::Light);
+    }
+}
@@ -21,6 +21,10 @@ mod helpers;
 pub use helpers::*;
 
 mod routes;
+mod rpc;
+pub use rpc::RPC_METHODS;
+
+mod subscriptions;
 
 use snarkos_node_consensus::Consensus;
 use snarkos_node_router::{
@@ -29,7 +33,7 @@ use snarkos_node_router::{
 };
 use snarkvm::{
     console::{program::ProgramID, types::Field},
-    ledger::narwhal::Data,
+    ledger::{block::Block, narwhal::Data},
     prelude::{cfg_into_iter, store::ConsensusStorage, Ledger, Network},
 };
 
@@ -37,23 +41,39 @@ use anyhow::Result;
 use axum::{
     body::Body,
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
-    http::{header::CONTENT_TYPE, Method, Request, StatusCode},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        Method,
+        Request,
+        StatusCode,
+    },
     middleware,
     middleware::Next,
     response::Response,
-    routing::{get, post},
+    routing::{delete, get, post},
     Json,
 };
-use axum_extra::response::ErasedJson;
+use futures::StreamExt;
 use parking_lot::Mutex;
 use std::{net::SocketAddr, sync::Arc};
-use tokio::{net::TcpListener, task::JoinHandle};
-use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, Semaphore},
+    task::JoinHandle,
+};
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
 
+/// The maximum size of a request body, in bytes.
+pub(crate) const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// The maximum number of transactions that can be simulated concurrently.
+const MAX_CONCURRENT_SIMULATIONS: usize = 2;
+/// The maximum number of subscriptions that can be open concurrently.
+const MAX_CONCURRENT_SUBSCRIPTIONS: usize = 100;
+
 /// A REST API server for the ledger.
 #[derive(Clone)]
 pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
@@ -61,25 +81,54 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     consensus: Option<Consensus<N>>,
     /// The ledger.
     ledger: Ledger<N, C>,
+    /// The sender of the blocks that are added to the ledger.
+    block_sender: broadcast::Sender<Block<N>>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The index of the transactions in the ledger, if it is enabled.
+    transaction_index: Option<Arc<TransactionIndex<N>>>,
+    /// The index of the `credits.aleo` transitions of each address, if it is enabled.
+    address_index: Option<Arc<AddressIndex<N>>>,
+    /// The scopes of the routes that require a JWT.
+    protected_scopes: Arc<[Scope]>,
+    /// The permits of the transaction simulations, which bound the number of concurrent simulations.
+    simulations: Arc<Semaphore>,
+    /// The permits of the subscriptions, which bound the number of concurrent subscriptions.
+    subscriptions: Arc<Semaphore>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
 
 impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     /// Initializes a new instance of the server.
+    #[allow(clippy::too_many_arguments)]
     pub async fn start(
         rest_ip: SocketAddr,
-        rest_rps: u32,
+        rate_limits: RateLimitConfig,
+        indexes: IndexConfig,
+        protected_scopes: &[Scope],
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
+        block_sender: broadcast::Sender<Block<N>>,
         routing: Arc<R>,
     ) -> Result<Self> {
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            block_sender,
+            routing,
+            transaction_index: indexes.transactions.then(Default::default),
+            address_index: indexes.addresses.then(Default::default),
+            protected_scopes: protected_scopes.into(),
+            simulations: Arc::new(Semaphore::new(MAX_CONCURRENT_SIMULATIONS)),
+            subscriptions: Arc::new(Semaphore::new(MAX_CONCURRENT_SUBSCRIPTIONS)),
+            handles: Default::default(),
+        };
+        // Spawn the indexer.
+        server.spawn_indexer();
         // Spawn the server.
-        server.spawn_server(rest_ip, rest_rps).await;
+        server.spawn_server(rest_ip, rate_limits, protected_scopes).await;
         // Return the server.
         Ok(server)
     }
@@ -97,91 +146,71 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 }
 
+impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Spawns a task that adds the blocks in the ledger to the enabled indexes, as they are added to the ledger.
+    ///
+    /// As the indexes are held in memory, the task first catches up with the blocks already in the ledger.
+    fn spawn_indexer(&self) {
+        let transaction_index = self.transaction_index.clone();
+        let address_index = self.address_index.clone();
+        // Start from the lowest height that is not yet indexed, as each index skips the blocks it already has.
+        let next_heights = [
+            transaction_index.as_ref().map(|index| index.next_height()),
+            address_index.as_ref().map(|index| index.next_height()),
+        ];
+        // If no index is enabled, there is nothing to do.
+        let Some(start_height) = next_heights.into_iter().flatten().min() else {
+            return;
+        };
+        let mut blocks = Box::pin(self.block_stream(start_height));
+        self.handles.lock().push(tokio::spawn(async move {
+            while let Some(block) = blocks.next().await {
+                if let Some(transaction_index) = &transaction_index {
+                    transaction_index.insert_block(&block);
+                }
+                if let Some(address_index) = &address_index {
+                    address_index.insert_block(&block);
+                }
+            }
+        }));
+    }
+}
+
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_rps: u32) {
+    async fn spawn_server(&mut self, rest_ip: SocketAddr, rate_limits: RateLimitConfig, protected_scopes: &[Scope]) {
         let cors = CorsLayer::new()
             .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
+            .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+            .allow_headers([AUTHORIZATION, CONTENT_TYPE]);
 
-        // Log the REST rate limit per IP.
-        debug!("REST rate limit per IP - {rest_rps} RPS");
+        // Log the REST rate limits.
+        debug!("REST rate limit per IP - {} RPS, per token - {} RPS", rate_limits.ip_rps, rate_limits.token_rps);
 
         // Prepare the rate limiting setup.
-        let governor_config = Box::new(
-            GovernorConfigBuilder::default()
-                .per_second(1)
-                .burst_size(rest_rps)
-                .error_handler(|error| Response::new(error.to_string()))
-                .finish()
-                .expect("Couldn't set up rate limiting for the REST server!"),
-        );
+        let rate_limiter = Arc::new(RateLimiterSet::new(&rate_limits));
+        // Periodically remove the state of the IPs and tokens that are no longer limited.
+        let rate_limiter_ = rate_limiter.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                rate_limiter_.retain_recent();
+            }
+        }));
+
+        // Prepare the routes, where the routes of each protected scope require a JWT with this scope.
+        let mut routes = axum::Router::new();
+        for (scope, group) in Self::route_groups() {
+            let group = match scope.filter(|scope| is_protected(*scope, protected_scopes)) {
+                Some(scope) => group.route_layer(middleware::from_fn_with_state(scope, auth_middleware)),
+                None => group,
+            };
+            routes = routes.merge(group);
+        }
 
         let router = {
-            axum::Router::new()
-
-            // All the endpoints before the call to `route_layer` are protected with JWT auth.
-            .route("/testnet3/node/address", get(Self::get_node_address))
-            .route_layer(middleware::from_fn(auth_middleware))
-
-            // ----------------- DEPRECATED ROUTES -----------------
-            // The following `GET ../latest/..` routes will be removed before mainnet.
-            // Please refer to the recommended routes for each endpoint:
-
-            // Deprecated: use `/testnet3/block/height/latest` instead.
-            .route("/testnet3/latest/height", get(Self::latest_height))
-            // Deprecated: use `/testnet3/block/hash/latest` instead.
-            .route("/testnet3/latest/hash", get(Self::latest_hash))
-            // Deprecated: use `/testnet3/latest/block/height` instead.
-            .route("/testnet3/latest/block", get(Self::latest_block))
-            // Deprecated: use `/testnet3/stateRoot/latest` instead.
-            .route("/testnet3/latest/stateRoot", get(Self::latest_state_root))
-            // Deprecated: use `/testnet3/committee/latest` instead.
-            .route("/testnet3/latest/committee", get(Self::latest_committee))
-            // ------------------------------------------------------
-
-            // GET ../block/..
-            .route("/testnet3/block/height/latest", get(Self::get_block_height_latest))
-            .route("/testnet3/block/hash/latest", get(Self::get_block_hash_latest))
-            .route("/testnet3/block/latest", get(Self::get_block_latest))
-            .route("/testnet3/block/:height_or_hash", get(Self::get_block))
-            // The path param here is actually only the height, but the name must match the route
-            // above, otherwise there'll be a conflict at runtime.
-            .route("/testnet3/block/:height_or_hash/transactions", get(Self::get_block_transactions))
-
-            // GET and POST ../transaction/..
-            .route("/testnet3/transaction/:id", get(Self::get_transaction))
-            .route("/testnet3/transaction/confirmed/:id", get(Self::get_confirmed_transaction))
-            .route("/testnet3/transaction/broadcast", post(Self::transaction_broadcast))
-
-            // POST ../solution/broadcast
-            .route("/testnet3/solution/broadcast", post(Self::solution_broadcast))
-
-            // GET ../find/..
-            .route("/testnet3/find/blockHash/:tx_id", get(Self::find_block_hash))
-            .route("/testnet3/find/transactionID/deployment/:program_id", get(Self::find_transaction_id_from_program_id))
-            .route("/testnet3/find/transactionID/:transition_id", get(Self::find_transaction_id_from_transition_id))
-            .route("/testnet3/find/transitionID/:input_or_output_id", get(Self::find_transition_id))
-
-            // GET ../peers/..
-            .route("/testnet3/peers/count", get(Self::get_peers_count))
-            .route("/testnet3/peers/all", get(Self::get_peers_all))
-            .route("/testnet3/peers/all/metrics", get(Self::get_peers_all_metrics))
-
-            // GET ../program/..
-            .route("/testnet3/program/:id", get(Self::get_program))
-            .route("/testnet3/program/:id/mappings", get(Self::get_mapping_names))
-            .route("/testnet3/program/:id/mapping/:name/:key", get(Self::get_mapping_value))
-
-            // GET misc endpoints.
-            .route("/testnet3/blocks", get(Self::get_blocks))
-            .route("/testnet3/height/:hash", get(Self::get_height))
-            .route("/testnet3/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
-            .route("/testnet3/memoryPool/solutions", get(Self::get_memory_pool_solutions))
-            .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
-            .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
-            .route("/testnet3/stateRoot/latest", get(Self::get_state_root_latest))
-            .route("/testnet3/committee/latest", get(Self::get_committee_latest))
+            routes
+            // Apply the rate limits, which are weighted by the class of the route.
+            .route_layer(middleware::from_fn_with_state(rate_limiter, rate_limit_middleware))
 
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
@@ -192,11 +221,7 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             // Enable CORS.
             .layer(cors)
             // Cap body size at 10MB.
-            .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
-            .layer(GovernorLayer {
-                // We can leak this because it is created only once and it persists.
-                config: Box::leak(governor_config),
-            })
+            .layer(DefaultBodyLimit::max(MAX_BODY_SIZE))
         };
 
         let rest_listener = TcpListener::bind(rest_ip).await.unwrap();
@@ -208,6 +233,125 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 }
 
+/// Defines the REST routes, grouped by the scope that grants access to them, along with their documentation.
+///
+/// Each route is served and documented from the same entry, so that no route can be added without its schema.
+macro_rules! rest_routes {
+    ($( $scope:expr => [ $( $method:ident ( $path:literal, $handler:ident, $doc:expr ) ),* $(,)? ] ),* $(,)?) => {
+        /// The documentation of the REST routes.
+        pub const REST_ROUTES: &[RouteDoc] = &[
+            $( $( RouteDoc { method: stringify!($method), path: $path, scope: $scope, operation: $doc }, )* )*
+        ];
+
+        impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+            /// Returns the routes of each scope, where a route without a scope checks the scope of each call.
+            fn route_groups() -> Vec<(Option<Scope>, axum::Router<Self>)> {
+                vec![ $( ($scope, axum::Router::new() $( .route($path, $method(Self::$handler)) )* ) ),* ]
+            }
+        }
+    };
+}
+
+rest_routes! {
+    // The admin endpoints, which are always protected with JWT auth.
+    // Note: The bans and trusted peers that are changed through these endpoints are only held in memory,
+    // so they are lost when the node restarts. Use `--peers` and `--validators` to trust peers persistently.
+    Some(Scope::Admin) => [
+        get("/testnet3/node/address", get_node_address, doc("Returns the address of the node.", &[], Schema::Type("string"))),
+        post("/testnet3/admin/peers/connect", admin_peers_connect, doc_with_body("Connects to the given peer.", Schema::Ref("PeerRequest"), Schema::Type("boolean"))),
+        post("/testnet3/admin/peers/disconnect", admin_peers_disconnect, doc_with_body("Disconnects from the given peer.", Schema::Ref("PeerRequest"), Schema::Type("boolean"))),
+        post("/testnet3/admin/peers/ban", admin_peers_ban, doc_with_body("Bans the given IP, and disconnects from its peers, until the node restarts.", Schema::Ref("BanRequest"), Schema::Type("boolean"))),
+        post("/testnet3/admin/peers/unban", admin_peers_unban, doc_with_body("Unbans the given IP.", Schema::Ref("BanRequest"), Schema::Type("boolean"))),
+        get("/testnet3/admin/peers/banned", get_admin_peers_banned, doc("Returns the banned IPs.", &[], Schema::Array("IpAddress"))),
+        get("/testnet3/admin/peers/trusted", get_admin_peers_trusted, doc("Returns the trusted peers and validators.", &[], Schema::Ref("TrustedPeers"))),
+        post("/testnet3/admin/peers/trusted", admin_peers_trust, doc_with_body("Adds the given peer to the trusted peers, until the node restarts.", Schema::Ref("PeerRequest"), Schema::Type("boolean"))),
+        delete("/testnet3/admin/peers/trusted", admin_peers_distrust, doc_with_body("Removes the given peer from the trusted peers, until the node restarts.", Schema::Ref("PeerRequest"), Schema::Type("boolean"))),
+    ],
+
+    // The endpoints that add transmissions to the memory pool.
+    Some(Scope::Broadcast) => [
+        post("/testnet3/transaction/broadcast", transaction_broadcast, doc_with_body("Broadcasts the given transaction, and returns its ID.", Schema::Ref("Transaction"), Schema::Type("string"))),
+        post("/testnet3/solution/broadcast", solution_broadcast, doc_with_body("Broadcasts the given prover solution, and returns its commitment.", Schema::Ref("ProverSolution"), Schema::Type("string"))),
+    ],
+
+    // The endpoints that read from the node.
+    Some(Scope::Read) => [
+        // ----------------- DEPRECATED ROUTES -----------------
+        // The following `GET ../latest/..` routes will be removed before mainnet.
+        // Please refer to the recommended routes for each endpoint, in their summaries.
+        get("/testnet3/latest/height", latest_height, doc("Deprecated: use `/testnet3/block/height/latest` instead.", &[], Schema::Type("integer"))),
+        get("/testnet3/latest/hash", latest_hash, doc("Deprecated: use `/testnet3/block/hash/latest` instead.", &[], Schema::Type("string"))),
+        get("/testnet3/latest/block", latest_block, doc("Deprecated: use `/testnet3/block/latest` instead.", &[], Schema::Ref("Block"))),
+        get("/testnet3/latest/stateRoot", latest_state_root, doc("Deprecated: use `/testnet3/stateRoot/latest` instead.", &[], Schema::Type("string"))),
+        get("/testnet3/latest/committee", latest_committee, doc("Deprecated: use `/testnet3/committee/latest` instead.", &[], Schema::Ref("Committee"))),
+        // ------------------------------------------------------
+
+        // GET ../block/..
+        get("/testnet3/block/height/latest", get_block_height_latest, doc("Returns the latest block height.", &[], Schema::Type("integer"))),
+        get("/testnet3/block/hash/latest", get_block_hash_latest, doc("Returns the latest block hash.", &[], Schema::Type("string"))),
+        get("/testnet3/block/latest", get_block_latest, doc("Returns the latest block.", &[], Schema::Ref("Block"))),
+        get("/testnet3/block/:height_or_hash", get_block, doc("Returns the block for the given height or hash.", &[], Schema::Ref("Block"))),
+        // The path param here is actually only the height, but the name must match the route
+        // above, otherwise there'll be a conflict at runtime.
+        get("/testnet3/block/:height_or_hash/transactions", get_block_transactions, doc("Returns the transactions of the block at the given height.", &[], Schema::Array("ConfirmedTransaction"))),
+
+        // GET and POST ../transaction/..
+        get("/testnet3/transaction/:id", get_transaction, doc("Returns the transaction for the given ID.", &[], Schema::Ref("Transaction"))),
+        get("/testnet3/transaction/confirmed/:id", get_confirmed_transaction, doc("Returns the confirmed transaction for the given ID.", &[], Schema::Ref("ConfirmedTransaction"))),
+        get("/testnet3/transaction/:id/status", get_transaction_status, doc("Returns the status of the transaction for the given ID.", &[], Schema::Ref("TransactionStatus"))),
+        post("/testnet3/transaction/simulate", transaction_simulate, doc_with_body("Simulates the given transaction against the latest state, with at most two simulations at a time.", Schema::Ref("Transaction"), Schema::Ref("Simulation"))),
+        get("/testnet3/transactions", get_transactions, doc("Returns a page of the indexed transactions that match the filters.", &["program", "function", "fee_payer", "from_height", "cursor", "limit"], Schema::Ref("TransactionPage"))),
+
+        // GET ../find/..
+        get("/testnet3/find/blockHash/:tx_id", find_block_hash, doc("Returns the block hash of the given transaction ID.", &[], Schema::Type("string"))),
+        get("/testnet3/find/transactionID/deployment/:program_id", find_transaction_id_from_program_id, doc("Returns the ID of the transaction that deployed the given program.", &[], Schema::Type("string"))),
+        get("/testnet3/find/transactionID/:transition_id", find_transaction_id_from_transition_id, doc("Returns the ID of the transaction that contains the given transition.", &[], Schema::Type("string"))),
+        get("/testnet3/find/transitionID/:input_or_output_id", find_transition_id, doc("Returns the ID of the transition that contains the given input or output.", &[], Schema::Type("string"))),
+
+        // GET ../peers/..
+        get("/testnet3/peers/count", get_peers_count, doc("Returns the number of connected peers.", &[], Schema::Type("integer"))),
+        get("/testnet3/peers/all", get_peers_all, doc("Returns the connected peers.", &[], Schema::Array("SocketAddress"))),
+        get("/testnet3/peers/all/metrics", get_peers_all_metrics, doc("Returns the connected peers and their node types.", &[], Schema::Type("array"))),
+
+        // GET ../program/..
+        get("/testnet3/program/:id", get_program, doc("Returns the source of the given program.", &[], Schema::Ref("Program"))),
+        get("/testnet3/program/:id/mappings", get_mapping_names, doc("Returns the mapping names of the given program.", &[], Schema::Type("array"))),
+        get("/testnet3/program/:id/mapping/:name/:key", get_mapping_value, doc("Returns the value of the given key in the given mapping.", &["metadata"], Schema::Ref("MappingValue"))),
+
+        // GET ../fee/..
+        get("/testnet3/fee/estimate", get_fee_estimate, doc("Returns the finalize cost of the given function, and of the functions it calls. The storage cost is not included, as it depends on the size of the proven execution; simulate the transaction for its full cost.", &["program", "function"], Schema::Ref("FeeEstimate"))),
+
+        // GET ../address/..
+        get("/testnet3/address/:address/balance", get_address_balance, doc("Returns the public balance of the given address.", &[], Schema::Ref("Balance"))),
+        get("/testnet3/address/:address/bonded", get_address_bonded, doc("Returns the bond and unbond states of the given address.", &[], Schema::Ref("Bonded"))),
+        get("/testnet3/address/:address/transitions", get_address_transitions, doc("Returns a page of the indexed `credits.aleo` transitions of the given address.", &["cursor", "limit"], Schema::Ref("AddressTransitionPage"))),
+
+        // GET misc endpoints.
+        get("/testnet3/blocks", get_blocks, doc("Returns the blocks in the given range, or a page of blocks.", &["start", "end", "cursor", "limit"], Schema::Ref("BlockPage"))),
+        get("/testnet3/height/:hash", get_height, doc("Returns the height of the given block hash.", &[], Schema::Type("integer"))),
+        get("/testnet3/memoryPool/transmissions", get_memory_pool_transmissions, doc("Returns the unconfirmed transmissions in the memory pool.", &[], Schema::Type("object"))),
+        get("/testnet3/memoryPool/solutions", get_memory_pool_solutions, doc("Returns the unconfirmed solutions in the memory pool.", &[], Schema::Type("object"))),
+        get("/testnet3/memoryPool/transactions", get_memory_pool_transactions, doc("Returns the unconfirmed transactions in the memory pool.", &[], Schema::Type("object"))),
+        get("/testnet3/memoryPool/queue", get_memory_pool_queue, doc("Returns the state of the unconfirmed solutions and transactions queues, and of the ready queues of the workers.", &[], Schema::Ref("MemoryPoolQueue"))),
+        get("/testnet3/statePath/:commitment", get_state_path_for_commitment, doc("Returns the state path of the given commitment.", &[], Schema::Type("string"))),
+        get("/testnet3/stateRoot/latest", get_state_root_latest, doc("Returns the latest state root.", &[], Schema::Type("string"))),
+        get("/testnet3/committee/latest", get_committee_latest, doc("Returns the latest committee.", &[], Schema::Ref("Committee"))),
+
+        // GET ../subscribe/..
+        get("/testnet3/subscribe/blocks", subscribe_blocks, doc("Streams the blocks, starting from the given height.", &["from_height"], Schema::Events("Block"))),
+        get("/testnet3/subscribe/transactions", subscribe_transactions, doc("Streams the confirmed transactions, starting from the given height.", &["from_height"], Schema::Events("ConfirmedTransaction"))),
+        get("/testnet3/subscribe/memoryPool", subscribe_memory_pool, doc("Streams the transmissions that enter the memory pool.", &[], Schema::Events("Transmission"))),
+
+        // GET ../openapi.json
+        get("/testnet3/openapi.json", get_openapi, doc("Returns this OpenAPI document.", &[], Schema::Type("object"))),
+    ],
+
+    // The JSON-RPC endpoint, which checks the scope of each method it is called with.
+    None => [
+        post("/testnet3/rpc", rpc, doc_with_body("Processes the given JSON-RPC 2.0 request or batch.", Schema::Ref("JsonRpcRequest"), Schema::Ref("JsonRpcResponse"))),
+    ],
+}
+
 async fn log_middleware(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     request: Request<Body>,
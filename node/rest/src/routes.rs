@@ -13,30 +13,172 @@
 // limitations under the License.
 
 use super::*;
+use snarkos_node_consensus::MemoryPoolStatus;
 use snarkos_node_router::messages::UnconfirmedSolution;
 use snarkvm::{
     ledger::coinbase::ProverSolution,
-    prelude::{block::Transaction, Identifier, Plaintext},
+    prelude::{
+        block::Transaction,
+        cost_in_microcredits,
+        deployment_cost,
+        execution_cost,
+        Address,
+        Identifier,
+        Literal,
+        Plaintext,
+        Value,
+        VM,
+    },
+    synthesizer::program::{CallOperator, FinalizeGlobalState, Instruction},
 };
 
+use anyhow::ensure;
 use indexmap::IndexMap;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
 
-/// The `get_blocks` query object.
+/// The `get_blocks` query object, which is either a block range or a page of blocks.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct BlockRange {
     /// The starting block height (inclusive).
-    start: u32,
+    pub(crate) start: Option<u32>,
     /// The ending block height (exclusive).
-    end: u32,
+    pub(crate) end: Option<u32>,
+    /// The block height to start the page from (inclusive).
+    pub(crate) cursor: Option<u32>,
+    /// The maximum number of blocks in the page.
+    pub(crate) limit: Option<u32>,
+}
+
+/// The `get_transactions` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct TransactionQuery {
+    /// The program of a transition in the transaction.
+    pub(crate) program: Option<String>,
+    /// The function of a transition in the transaction, which requires the program.
+    pub(crate) function: Option<String>,
+    /// The payer of the fee.
+    pub(crate) fee_payer: Option<String>,
+    /// The lowest block height of the transaction.
+    pub(crate) from_height: Option<u32>,
+    /// The cursor of the page, as returned by the previous page.
+    pub(crate) cursor: Option<usize>,
+    /// The maximum number of transactions in the page.
+    pub(crate) limit: Option<usize>,
+}
+
+/// The query object of a page, such as the `get_address_transitions` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Page {
+    /// The cursor of the page, as returned by the previous page.
+    pub(crate) cursor: Option<usize>,
+    /// The maximum number of items in the page.
+    pub(crate) limit: Option<usize>,
+}
+
+/// The request object of the admin peer endpoints.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct PeerRequest {
+    /// The listener IP of the peer.
+    pub(crate) ip: SocketAddr,
+    /// Whether the peer is a validator in the memory pool network, instead of a peer in the router.
+    #[serde(default)]
+    pub(crate) gateway: bool,
+}
+
+/// The request object of the admin ban endpoints.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BanRequest {
+    /// The IP to ban or unban.
+    pub(crate) ip: IpAddr,
+}
+
+/// The status of a transaction, from the earliest to the latest stage of its lifecycle.
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case", bound = "")]
+pub(crate) enum TransactionStatus<N: Network> {
+    /// The transaction is not known to this node.
+    Unknown,
+    /// The transaction is in the unconfirmed transactions queue.
+    Queued,
+    /// The transaction is in the ready queue of a worker.
+    Ready { worker_id: u8 },
+    /// The transaction is in the batch proposed by this node.
+    Proposed { round: u64, timestamp: i64 },
+    /// The transaction is in a certificate that is not yet committed.
+    Certified { round: u64, certificate_id: Field<N>, timestamp: i64 },
+    /// The transaction is accepted in a block.
+    Confirmed { height: u32, block_hash: N::BlockHash, index: u32, timestamp: i64 },
+    /// The transaction is rejected in a block, where only its fee is processed.
+    Rejected { height: u32, block_hash: N::BlockHash, index: u32, fee_transaction_id: N::TransactionID, timestamp: i64 },
+    /// The transaction is aborted in a block.
+    Aborted { height: u32, block_hash: N::BlockHash, timestamp: i64 },
+}
+
+/// The `get_transaction_status` response, which is the status of the transaction with its ID.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct TransactionStatusResponse<N: Network> {
+    /// The ID of the transaction.
+    pub(crate) id: N::TransactionID,
+    /// The status of the transaction.
+    #[serde(flatten)]
+    pub(crate) status: TransactionStatus<N>,
+    /// The timestamp at which this node first saw the transaction, if it is known.
+    pub(crate) seen_at: Option<i64>,
+}
+
+/// The blocks of the `get_blocks` response.
+#[derive(Serialize)]
+#[serde(untagged, bound = "")]
+pub(crate) enum Blocks<N: Network> {
+    /// The blocks of the requested range.
+    Range(Vec<Block<N>>),
+    /// The blocks of the requested page, with the cursor of the next page.
+    Page { blocks: Vec<Block<N>>, next_cursor: Option<u32> },
+}
+
+/// The `get_transactions` response, which is a page of the transaction index.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct TransactionPage<N: Network> {
+    /// The transactions of the page.
+    pub(crate) transactions: Vec<TransactionEntry<N>>,
+    /// The cursor of the next page, if there may be more transactions.
+    pub(crate) next_cursor: Option<usize>,
+    /// The height up to which the ledger is indexed, as the index catches up in the background on startup.
+    pub(crate) indexed_height: Option<u32>,
+}
+
+/// The `get_address_transitions` response, which is a page of the address index.
+#[derive(Serialize)]
+#[serde(bound = "")]
+pub(crate) struct AddressTransitionPage<N: Network> {
+    /// The transitions of the page.
+    pub(crate) transitions: Vec<AddressTransition<N>>,
+    /// The cursor of the next page, if there may be more transitions.
+    pub(crate) next_cursor: Option<usize>,
+}
+
+/// The `get_fee_estimate` query object.
+#[derive(Deserialize, Serialize)]
+#[serde(bound = "")]
+pub(crate) struct ProgramCall<N: Network> {
+    /// The program ID.
+    pub(crate) program: ProgramID<N>,
+    /// The name of the function.
+    pub(crate) function: Identifier<N>,
 }
 
 /// The `get_mapping_value` query object.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct Metadata {
-    metadata: bool,
+    pub(crate) metadata: bool,
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
@@ -46,49 +188,49 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
     // Deprecated: Use `get_block_height_latest` instead.
     // GET /testnet3/latest/height
-    pub(crate) async fn latest_height(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_height())
+    pub(crate) async fn latest_height(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_height())
     }
 
     // Deprecated: Use `get_block_hash_latest` instead.
     // GET /testnet3/latest/hash
-    pub(crate) async fn latest_hash(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_hash())
+    pub(crate) async fn latest_hash(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_hash())
     }
 
     // Deprecated: Use `get_block_latest` instead.
     // GET /testnet3/latest/block
-    pub(crate) async fn latest_block(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_block())
+    pub(crate) async fn latest_block(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_block())
     }
 
     // Deprecated: Use `get_state_root_latest` instead.
     // GET /testnet3/latest/stateRoot
-    pub(crate) async fn latest_state_root(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_state_root())
+    pub(crate) async fn latest_state_root(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_state_root())
     }
 
     // Deprecated: Use `get_committee_latest` instead.
     // GET /testnet3/latest/committee
-    pub(crate) async fn latest_committee(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    pub(crate) async fn latest_committee(State(rest): State<Self>) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.latest_committee()?))
     }
 
     // ---------------------------------------------------------
 
     // GET /testnet3/block/height/latest
-    pub(crate) async fn get_block_height_latest(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_height())
+    pub(crate) async fn get_block_height_latest(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_height())
     }
 
     // GET /testnet3/block/hash/latest
-    pub(crate) async fn get_block_hash_latest(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_hash())
+    pub(crate) async fn get_block_hash_latest(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_hash())
     }
 
     // GET /testnet3/block/latest
-    pub(crate) async fn get_block_latest(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_block())
+    pub(crate) async fn get_block_latest(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_block())
     }
 
     // GET /testnet3/block/{height}
@@ -96,40 +238,60 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn get_block(
         State(rest): State<Self>,
         Path(height_or_hash): Path<String>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         // Manually parse the height or the height or the hash, axum doesn't support different types
         // for the same path param.
         let block = if let Ok(height) = height_or_hash.parse::<u32>() {
+            // Ensure the block exists.
+            if height > rest.ledger.latest_height() {
+                return Err(RestError::not_found(format!("Block {height} does not exist")));
+            }
             rest.ledger.get_block(height)?
         } else {
-            let hash = height_or_hash
-                .parse::<N::BlockHash>()
-                .map_err(|_| RestError("invalid input, it is neither a block height nor a block hash".to_string()))?;
+            let hash = height_or_hash.parse::<N::BlockHash>().map_err(|_| {
+                RestError::invalid_input("invalid input, it is neither a block height nor a block hash")
+            })?;
 
             rest.ledger.get_block_by_hash(&hash)?
         };
 
-        Ok(ErasedJson::pretty(block))
+        Ok(RestJson(block))
     }
 
     // GET /testnet3/blocks?start={start_height}&end={end_height}
+    // GET /testnet3/blocks?cursor={height}&limit={limit}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
         Query(block_range): Query<BlockRange>,
-    ) -> Result<ErasedJson, RestError> {
-        let start_height = block_range.start;
-        let end_height = block_range.end;
-
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         const MAX_BLOCK_RANGE: u32 = 50;
 
+        let (start_height, end_height) = match (block_range.start, block_range.end) {
+            (Some(start_height), Some(end_height)) => (start_height, end_height),
+            // If there is no range, return a page of blocks with the cursor of the next page.
+            (None, None) => {
+                let start_height = block_range.cursor.unwrap_or(0);
+                let limit = block_range.limit.unwrap_or(MAX_BLOCK_RANGE).min(MAX_BLOCK_RANGE);
+                let end_height = start_height.saturating_add(limit).min(rest.ledger.latest_height().saturating_add(1));
+
+                let blocks = cfg_into_iter!((start_height..end_height))
+                    .map(|height| rest.ledger.get_block(height))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let next_cursor = (end_height <= rest.ledger.latest_height()).then_some(end_height);
+
+                return Ok(RestJson(Blocks::Page { blocks, next_cursor }));
+            }
+            _ => return Err(RestError::invalid_input("Both the start and end of the block range are required")),
+        };
+
         // Ensure the end height is greater than the start height.
         if start_height > end_height {
-            return Err(RestError("Invalid block range".to_string()));
+            return Err(RestError::invalid_input("Invalid block range"));
         }
 
         // Ensure the block range is bounded.
         if end_height - start_height > MAX_BLOCK_RANGE {
-            return Err(RestError(format!(
+            return Err(RestError::invalid_input(format!(
                 "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
                 end_height - start_height
             )));
@@ -139,64 +301,161 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .map(|height| rest.ledger.get_block(height))
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(ErasedJson::pretty(blocks))
+        Ok(RestJson(Blocks::Range(blocks)))
     }
 
     // GET /testnet3/height/{blockHash}
     pub(crate) async fn get_height(
         State(rest): State<Self>,
         Path(hash): Path<N::BlockHash>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_height(&hash)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_height(&hash)?))
     }
 
     // GET /testnet3/block/{height}/transactions
     pub(crate) async fn get_block_transactions(
         State(rest): State<Self>,
         Path(height): Path<u32>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transactions(height)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_transactions(height)?))
     }
 
     // GET /testnet3/transaction/{transactionID}
     pub(crate) async fn get_transaction(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_transaction(tx_id)?))
     }
 
     // GET /testnet3/transaction/confirmed/{transactionID}
     pub(crate) async fn get_confirmed_transaction(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_confirmed_transaction(tx_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_confirmed_transaction(tx_id)?))
+    }
+
+    // GET /testnet3/transaction/{transactionID}/status
+    pub(crate) async fn get_transaction_status(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // If the transaction is in a block, it is confirmed, rejected or aborted.
+        let status = if let Some(block_hash) = rest.ledger.find_block_hash(&tx_id)? {
+            let height = rest.ledger.get_height(&block_hash)?;
+            let timestamp = rest.ledger.get_header(height)?.metadata().timestamp();
+
+            if rest.ledger.get_aborted_transaction_ids(height)?.contains(&tx_id) {
+                TransactionStatus::Aborted { height, block_hash, timestamp }
+            } else {
+                let transactions = rest.ledger.get_transactions(height)?;
+                let Some(confirmed) = transactions.find_confirmed_transaction_for_unconfirmed_transaction_id(&tx_id)
+                else {
+                    return Err(RestError::internal(format!("Missing transaction '{tx_id}' in block {height}")));
+                };
+                let index = confirmed.index();
+                match confirmed.is_accepted() {
+                    true => TransactionStatus::Confirmed { height, block_hash, index, timestamp },
+                    // Note: A rejected transaction is replaced in the block by its fee transaction.
+                    false => {
+                        let fee_transaction_id = confirmed.transaction().id();
+                        TransactionStatus::Rejected { height, block_hash, index, fee_transaction_id, timestamp }
+                    }
+                }
+            }
+        } else {
+            // Otherwise, determine the stage of the transaction in the memory pool.
+            match rest.consensus.as_ref().and_then(|consensus| consensus.memory_pool_status(&tx_id)) {
+                Some(MemoryPoolStatus::Queued) => TransactionStatus::Queued,
+                Some(MemoryPoolStatus::Ready { worker_id }) => TransactionStatus::Ready { worker_id },
+                Some(MemoryPoolStatus::Proposed { round, timestamp }) => {
+                    TransactionStatus::Proposed { round, timestamp }
+                }
+                Some(MemoryPoolStatus::Certified { round, certificate_id, timestamp }) => {
+                    TransactionStatus::Certified { round, certificate_id, timestamp }
+                }
+                None => TransactionStatus::Unknown,
+            }
+        };
+
+        // Include the timestamp at which this node first saw the transaction, if it is known.
+        let seen_at = rest.consensus.as_ref().and_then(|consensus| consensus.transaction_seen_at(&tx_id));
+
+        Ok(RestJson(TransactionStatusResponse { id: tx_id, status, seen_at }))
+    }
+
+    // GET /testnet3/transactions?program={programID}&function={functionName}&fee_payer={address}&from_height={height}
+    pub(crate) async fn get_transactions(
+        State(rest): State<Self>,
+        Query(query): Query<TransactionQuery>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        const MAX_TRANSACTIONS: usize = 100;
+
+        // Ensure the transaction index is enabled.
+        let Some(transaction_index) = &rest.transaction_index else {
+            return Err(RestError::unavailable("The transaction index is not enabled on this node"));
+        };
+
+        // Parse the filter.
+        let filter = TransactionFilter {
+            program: query.program.as_deref().map(ProgramID::from_str).transpose()?,
+            function: query.function.as_deref().map(Identifier::from_str).transpose()?,
+            fee_payer: query.fee_payer.as_deref().map(Address::from_str).transpose()?,
+            from_height: query.from_height,
+        };
+        // Ensure the function is filtered within a program.
+        if filter.function.is_some() && filter.program.is_none() {
+            return Err(RestError::invalid_input("The function filter requires a program"));
+        }
+
+        let limit = query.limit.unwrap_or(MAX_TRANSACTIONS).min(MAX_TRANSACTIONS);
+        let (transactions, next_cursor) = transaction_index.query(&filter, query.cursor.unwrap_or(0), limit);
+        // Include the height up to which the ledger is indexed, as the index catches up in the background on startup.
+        let indexed_height = transaction_index.next_height().checked_sub(1);
+
+        Ok(RestJson(TransactionPage { transactions, next_cursor, indexed_height }))
     }
 
     // GET /testnet3/memoryPool/transmissions
-    pub(crate) async fn get_memory_pool_transmissions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_transmissions(
+        State(rest): State<Self>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         match rest.consensus {
-            Some(consensus) => {
-                Ok(ErasedJson::pretty(consensus.unconfirmed_transmissions().collect::<IndexMap<_, _>>()))
-            }
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            Some(consensus) => Ok(RestJson(consensus.unconfirmed_transmissions().collect::<IndexMap<_, _>>())),
+            None => Err(RestError::unavailable("Route isn't available for this node type")),
         }
     }
 
     // GET /testnet3/memoryPool/solutions
-    pub(crate) async fn get_memory_pool_solutions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_solutions(
+        State(rest): State<Self>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         match rest.consensus {
-            Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_solutions().collect::<IndexMap<_, _>>())),
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            Some(consensus) => Ok(RestJson(consensus.unconfirmed_solutions().collect::<IndexMap<_, _>>())),
+            None => Err(RestError::unavailable("Route isn't available for this node type")),
         }
     }
 
     // GET /testnet3/memoryPool/transactions
-    pub(crate) async fn get_memory_pool_transactions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_transactions(
+        State(rest): State<Self>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         match rest.consensus {
-            Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_transactions().collect::<IndexMap<_, _>>())),
-            None => Err(RestError("Route isn't available for this node type".to_string())),
+            Some(consensus) => Ok(RestJson(consensus.unconfirmed_transactions().collect::<IndexMap<_, _>>())),
+            None => Err(RestError::unavailable("Route isn't available for this node type")),
+        }
+    }
+
+    // GET /testnet3/memoryPool/queue
+    pub(crate) async fn get_memory_pool_queue(State(rest): State<Self>) -> Result<RestJson<impl Serialize>, RestError> {
+        match rest.consensus {
+            Some(consensus) => Ok(RestJson(json!({
+                "solutions": consensus.solutions_queue_state(),
+                "transactions": consensus.transactions_queue_state(),
+                "ready": consensus.ready_queue_state(),
+            }))),
+            None => Err(RestError::unavailable("Route isn't available for this node type")),
         }
     }
 
@@ -204,16 +463,16 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn get_program(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_program(id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_program(id)?))
     }
 
     // GET /testnet3/program/{programID}/mappings
     pub(crate) async fn get_mapping_names(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.vm().finalize_store().get_mapping_names_confirmed(&id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.vm().finalize_store().get_mapping_names_confirmed(&id)?))
     }
 
     // GET /testnet3/program/{programID}/mapping/{mappingName}/{mappingKey}
@@ -222,97 +481,281 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         State(rest): State<Self>,
         Path((id, name, key)): Path<(ProgramID<N>, Identifier<N>, Plaintext<N>)>,
         metadata: Option<Query<Metadata>>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         // Retrieve the mapping value.
         let mapping_value = rest.ledger.vm().finalize_store().get_value_confirmed(id, name, &key)?;
 
         // Check if metadata is requested and return the value with metadata if so.
         if metadata.map(|q| q.metadata).unwrap_or(false) {
-            return Ok(ErasedJson::pretty(json!({
+            return Ok(RestJson(json!({
                 "data": mapping_value,
                 "height": rest.ledger.latest_height(),
             })));
         }
 
         // Return the value without metadata.
-        Ok(ErasedJson::pretty(mapping_value))
+        Ok(RestJson(json!(mapping_value)))
+    }
+
+    // GET /testnet3/address/{address}/balance
+    pub(crate) async fn get_address_balance(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // Retrieve the public balance, which is zero if the address has no account.
+        let (credits, account) = (ProgramID::from_str("credits.aleo")?, Identifier::from_str("account")?);
+        let key = Plaintext::from(Literal::Address(address));
+        let balance = match rest.ledger.vm().finalize_store().get_value_confirmed(credits, account, &key)? {
+            Some(Value::Plaintext(Plaintext::Literal(Literal::U64(balance), _))) => *balance,
+            Some(value) => {
+                return Err(RestError::internal(format!("Invalid account balance '{value}' for '{address}'")))
+            }
+            None => 0,
+        };
+
+        Ok(RestJson(json!({
+            "address": address,
+            "balance": balance,
+            "height": rest.ledger.latest_height(),
+        })))
+    }
+
+    // GET /testnet3/address/{address}/bonded
+    pub(crate) async fn get_address_bonded(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // Retrieve the bond and unbond states of the address.
+        let credits = ProgramID::from_str("credits.aleo")?;
+        let key = Plaintext::from(Literal::Address(address));
+        let finalize_store = rest.ledger.vm().finalize_store();
+        let bonded = finalize_store.get_value_confirmed(credits, Identifier::from_str("bonded")?, &key)?;
+        let unbonding = finalize_store.get_value_confirmed(credits, Identifier::from_str("unbonding")?, &key)?;
+
+        Ok(RestJson(json!({
+            "address": address,
+            "bonded": bonded,
+            "unbonding": unbonding,
+            "height": rest.ledger.latest_height(),
+        })))
+    }
+
+    // GET /testnet3/address/{address}/transitions?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_transitions(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(page): Query<Page>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        const MAX_TRANSITIONS: usize = 100;
+
+        // Ensure the address index is enabled.
+        let Some(address_index) = &rest.address_index else {
+            return Err(RestError::unavailable("The address index is not enabled on this node"));
+        };
+
+        let limit = page.limit.unwrap_or(MAX_TRANSITIONS).min(MAX_TRANSITIONS);
+        let (transitions, next_cursor) = address_index.query(&address, page.cursor.unwrap_or(0), limit);
+
+        Ok(RestJson(AddressTransitionPage { transitions, next_cursor }))
     }
 
     // GET /testnet3/statePath/{commitment}
     pub(crate) async fn get_state_path_for_commitment(
         State(rest): State<Self>,
         Path(commitment): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_state_path_for_commitment(&commitment)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.get_state_path_for_commitment(&commitment)?))
     }
 
     // GET /testnet3/stateRoot/latest
-    pub(crate) async fn get_state_root_latest(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_state_root())
+    pub(crate) async fn get_state_root_latest(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.ledger.latest_state_root())
     }
 
     // GET /testnet3/committee/latest
-    pub(crate) async fn get_committee_latest(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    pub(crate) async fn get_committee_latest(State(rest): State<Self>) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.latest_committee()?))
     }
 
     // GET /testnet3/peers/count
-    pub(crate) async fn get_peers_count(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().number_of_connected_peers())
+    pub(crate) async fn get_peers_count(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.routing.router().number_of_connected_peers())
     }
 
     // GET /testnet3/peers/all
-    pub(crate) async fn get_peers_all(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().connected_peers())
+    pub(crate) async fn get_peers_all(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.routing.router().connected_peers())
     }
 
     // GET /testnet3/peers/all/metrics
-    pub(crate) async fn get_peers_all_metrics(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().connected_metrics())
+    pub(crate) async fn get_peers_all_metrics(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.routing.router().connected_metrics())
     }
 
     // GET /testnet3/node/address
-    pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().address())
+    pub(crate) async fn get_node_address(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.routing.router().address())
+    }
+
+    // GET /testnet3/admin/peers/trusted
+    pub(crate) async fn get_admin_peers_trusted(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(json!({
+            "peers": rest.routing.router().trusted_peers(),
+            "validators": rest.consensus.as_ref().map(|consensus| consensus.bft().primary().gateway().trusted_validators()),
+        }))
+    }
+
+    // GET /testnet3/admin/peers/banned
+    pub(crate) async fn get_admin_peers_banned(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(rest.routing.router().banned_ips())
+    }
+
+    // POST /testnet3/admin/peers/connect
+    pub(crate) async fn admin_peers_connect(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        match (request.gateway, rest.consensus) {
+            (false, _) => Ok(RestJson(rest.routing.router().connect(request.ip).is_some())),
+            (true, Some(consensus)) => Ok(RestJson(consensus.bft().primary().gateway().connect(request.ip).is_some())),
+            (true, None) => Err(RestError::unavailable("The memory pool network isn't available for this node type")),
+        }
+    }
+
+    // POST /testnet3/admin/peers/disconnect
+    pub(crate) async fn admin_peers_disconnect(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        match (request.gateway, rest.consensus) {
+            (false, _) => {
+                // Retrieve the router.
+                let router = rest.routing.router();
+                // Ensure the peer is connected.
+                if !router.is_connected(&request.ip) {
+                    return Ok(RestJson(false));
+                }
+                // Disconnect from the peer.
+                let is_disconnected =
+                    router.disconnect(request.ip).await.map_err(|error| RestError::internal(error.to_string()))?;
+                Ok(RestJson(is_disconnected))
+            }
+            (true, Some(consensus)) => {
+                // Retrieve the gateway.
+                let gateway = consensus.bft().primary().gateway();
+                // Ensure the validator is connected.
+                if !gateway.is_connected_ip(request.ip) {
+                    return Ok(RestJson(false));
+                }
+                // Disconnect from the validator.
+                gateway.disconnect(request.ip).await.map_err(|error| RestError::internal(error.to_string()))?;
+                Ok(RestJson(true))
+            }
+            (true, None) => Err(RestError::unavailable("The memory pool network isn't available for this node type")),
+        }
+    }
+
+    // POST /testnet3/admin/peers/ban
+    pub(crate) async fn admin_peers_ban(
+        State(rest): State<Self>,
+        Json(request): Json<BanRequest>,
+    ) -> RestJson<impl Serialize> {
+        // Ban the IP in the router, which disconnects any peers with this IP.
+        let is_new = rest.routing.router().ban(request.ip);
+        // Ban the IP in the memory pool network, if it exists.
+        if let Some(consensus) = &rest.consensus {
+            consensus.bft().primary().gateway().ban(request.ip);
+        }
+        info!("Banned '{}' via the admin API", request.ip);
+        RestJson(is_new)
+    }
+
+    // POST /testnet3/admin/peers/unban
+    pub(crate) async fn admin_peers_unban(
+        State(rest): State<Self>,
+        Json(request): Json<BanRequest>,
+    ) -> RestJson<impl Serialize> {
+        // Unban the IP in the router.
+        let is_removed = rest.routing.router().unban(request.ip);
+        // Unban the IP in the memory pool network, if it exists.
+        if let Some(consensus) = &rest.consensus {
+            consensus.bft().primary().gateway().unban(request.ip);
+        }
+        info!("Unbanned '{}' via the admin API", request.ip);
+        RestJson(is_removed)
+    }
+
+    // POST /testnet3/admin/peers/trusted
+    pub(crate) async fn admin_peers_trust(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // Note: The heartbeat connects to the new trusted peer.
+        match (request.gateway, rest.consensus) {
+            (false, _) => Ok(RestJson(rest.routing.router().insert_trusted_peer(request.ip))),
+            (true, Some(consensus)) => {
+                Ok(RestJson(consensus.bft().primary().gateway().insert_trusted_validator(request.ip)))
+            }
+            (true, None) => Err(RestError::unavailable("The memory pool network isn't available for this node type")),
+        }
+    }
+
+    // DELETE /testnet3/admin/peers/trusted
+    pub(crate) async fn admin_peers_distrust(
+        State(rest): State<Self>,
+        Json(request): Json<PeerRequest>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        match (request.gateway, rest.consensus) {
+            (false, _) => Ok(RestJson(rest.routing.router().remove_trusted_peer(request.ip))),
+            (true, Some(consensus)) => {
+                Ok(RestJson(consensus.bft().primary().gateway().remove_trusted_validator(request.ip)))
+            }
+            (true, None) => Err(RestError::unavailable("The memory pool network isn't available for this node type")),
+        }
+    }
+
+    // GET /testnet3/openapi.json
+    pub(crate) async fn get_openapi(State(rest): State<Self>) -> RestJson<impl Serialize> {
+        RestJson(openapi_document::<N>(&rest.protected_scopes))
     }
 
     // GET /testnet3/find/blockHash/{transactionID}
     pub(crate) async fn find_block_hash(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_block_hash(&tx_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.find_block_hash(&tx_id)?))
     }
 
     // GET /testnet3/find/transactionID/deployment/{programID}
     pub(crate) async fn find_transaction_id_from_program_id(
         State(rest): State<Self>,
         Path(program_id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_program_id(&program_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.find_transaction_id_from_program_id(&program_id)?))
     }
 
     // GET /testnet3/find/transactionID/{transitionID}
     pub(crate) async fn find_transaction_id_from_transition_id(
         State(rest): State<Self>,
         Path(transition_id): Path<N::TransitionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?))
     }
 
     // GET /testnet3/find/transitionID/{inputOrOutputID}
     pub(crate) async fn find_transition_id(
         State(rest): State<Self>,
         Path(input_or_output_id): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transition_id(&input_or_output_id)?))
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        Ok(RestJson(rest.ledger.find_transition_id(&input_or_output_id)?))
     }
 
     // POST /testnet3/transaction/broadcast
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
         Json(tx): Json<Transaction<N>>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
         if let Some(consensus) = rest.consensus {
             // Add the unconfirmed transaction to the memory pool.
@@ -329,14 +772,108 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // Broadcast the transaction.
         rest.routing.propagate(message, &[]);
 
-        Ok(ErasedJson::pretty(tx_id))
+        Ok(RestJson(tx_id))
+    }
+
+    // POST /testnet3/transaction/simulate
+    pub(crate) async fn transaction_simulate(
+        State(rest): State<Self>,
+        Json(tx): Json<Transaction<N>>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // Ensure the number of concurrent simulations is bounded, as speculating locks the VM, which stalls block production.
+        let Ok(permit) = rest.simulations.clone().try_acquire_owned() else {
+            return Err(RestError::unavailable("Too many transactions are being simulated, try again later"));
+        };
+
+        // Note: The transaction is verified and speculated on in a blocking task, as both are expensive.
+        let ledger = rest.ledger.clone();
+        let simulation = tokio::task::spawn_blocking(move || {
+            // Hold the permit until the simulation is over, even if the request is dropped in the meantime.
+            let _permit = permit;
+            let tx_id = tx.id();
+
+            // Check the transaction, as it is checked before it is added to the memory pool.
+            if let Err(error) = ledger.check_transaction_basic(&tx, None, &mut rand::thread_rng()) {
+                return Ok(json!({ "id": tx_id, "status": "invalid", "error": error.to_string() }));
+            }
+
+            // Compute the minimum cost of the transaction.
+            let (total_cost, storage_cost, finalize_cost, namespace_cost) = match &tx {
+                Transaction::Deploy(_, _, deployment, _) => {
+                    let (total_cost, (storage_cost, namespace_cost)) = deployment_cost(deployment)?;
+                    (total_cost, storage_cost, 0, namespace_cost)
+                }
+                Transaction::Execute(_, execution, _) => {
+                    let (total_cost, (storage_cost, finalize_cost)) = execution_cost(ledger.vm(), execution)?;
+                    (total_cost, storage_cost, finalize_cost, 0)
+                }
+                Transaction::Fee(..) => (0, 0, 0, 0),
+            };
+            let fee = *tx.fee_amount()?;
+
+            // Speculate on the transaction, as if it were in the next block.
+            let latest_block = ledger.latest_block();
+            let state = FinalizeGlobalState::new::<N>(
+                latest_block.round().saturating_add(1),
+                latest_block.height().saturating_add(1),
+                latest_block.cumulative_weight(),
+                latest_block.cumulative_proof_target(),
+                latest_block.hash(),
+            )?;
+            let (_, transactions, aborted_ids, _) =
+                ledger.vm().speculate(state, None, vec![], None, std::iter::once(&tx))?;
+            let status = match (aborted_ids.contains(&tx_id), transactions.iter().next()) {
+                (false, Some(confirmed)) if confirmed.is_accepted() => "accepted",
+                (false, Some(_)) => "rejected",
+                _ => "aborted",
+            };
+
+            Ok::<_, anyhow::Error>(json!({
+                "id": tx_id,
+                "status": status,
+                "fee": fee,
+                "cost": {
+                    "total": total_cost,
+                    "storage": storage_cost,
+                    "finalize": finalize_cost,
+                    "namespace": namespace_cost,
+                },
+            }))
+        })
+        .await
+        .map_err(|error| RestError::internal(format!("Failed to simulate the transaction - {error}")))??;
+
+        Ok(RestJson(simulation))
+    }
+
+    // GET /testnet3/fee/estimate?program={programID}&function={functionName}
+    pub(crate) async fn get_fee_estimate(
+        State(rest): State<Self>,
+        Query(call): Query<ProgramCall<N>>,
+    ) -> Result<RestJson<impl Serialize>, RestError> {
+        // Retrieve the finalize cost of each transition of the call, in execution order.
+        // Note: The storage cost is not estimated, as it depends on the size of the proven execution,
+        // so the full cost of a transaction is only known once it is simulated.
+        let mut transitions = Vec::new();
+        finalize_costs(rest.ledger.vm(), call.program, call.function, &mut transitions)?;
+        let finalize_cost = transitions.iter().map(|(_, cost)| *cost).sum::<u64>();
+
+        Ok(RestJson(json!({
+            "program": call.program,
+            "function": call.function,
+            "finalize_cost": finalize_cost,
+            "transitions": transitions
+                .into_iter()
+                .map(|(locator, cost)| json!({ "transition": locator, "finalize_cost": cost }))
+                .collect::<Vec<_>>(),
+        })))
     }
 
     // POST /testnet3/solution/broadcast
     pub(crate) async fn solution_broadcast(
         State(rest): State<Self>,
         Json(prover_solution): Json<ProverSolution<N>>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<RestJson<impl Serialize>, RestError> {
         // If the consensus module is enabled, add the unconfirmed solution to the memory pool.
         if let Some(consensus) = rest.consensus {
             // Add the unconfirmed transaction to the memory pool.
@@ -353,6 +890,41 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         // Broadcast the transaction.
         rest.routing.propagate(message, &[]);
 
-        Ok(ErasedJson::pretty(commitment))
+        Ok(RestJson(commitment))
     }
 }
+
+/// Appends the finalize cost of each transition in the call to the given function, in execution order.
+fn finalize_costs<N: Network, C: ConsensusStorage<N>>(
+    vm: &VM<N, C>,
+    program_id: ProgramID<N>,
+    function_name: Identifier<N>,
+    costs: &mut Vec<(String, u64)>,
+) -> Result<()> {
+    // Note: An execution has at most 31 transitions, as one is reserved for the fee.
+    const MAX_TRANSITIONS: usize = 31;
+    ensure!(
+        costs.len() < MAX_TRANSITIONS,
+        "The call to '{program_id}/{function_name}' exceeds {MAX_TRANSITIONS} transitions"
+    );
+
+    // Retrieve the function.
+    let function = vm.process().read().get_program(program_id)?.get_function(&function_name)?;
+
+    // Note: The transitions of the external calls precede the transition of the caller.
+    for instruction in function.instructions() {
+        if let Instruction::Call(call) = instruction {
+            if let CallOperator::Locator(locator) = call.operator() {
+                finalize_costs(vm, *locator.program_id(), *locator.resource(), costs)?;
+            }
+        }
+    }
+
+    // Compute the finalize cost of the function.
+    let cost = match function.finalize_logic() {
+        Some(finalize) => cost_in_microcredits(finalize)?,
+        None => 0,
+    };
+    costs.push((format!("{program_id}/{function_name}"), cost));
+    Ok(())
+}
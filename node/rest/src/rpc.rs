@@ -0,0 +1,471 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::routes::{BlockRange, Metadata, Page, ProgramCall, TransactionQuery};
+use snarkvm::{
+    ledger::coinbase::ProverSolution,
+    prelude::{block::Transaction, Address, Identifier, Plaintext},
+};
+
+use axum::{body::Bytes, http::HeaderMap, response::IntoResponse};
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Map, Value};
+
+/// The maximum number of requests in a JSON-RPC batch.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// The error code for invalid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The error code for a request that is not a valid JSON-RPC request.
+const INVALID_REQUEST: i64 = -32600;
+/// The error code for a method that does not exist.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The error code for invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The error code for an internal error of the server.
+const INTERNAL_ERROR: i64 = -32603;
+/// The error code for a `RestError` returned by the method, if the request failed on the server.
+const SERVER_ERROR: i64 = -32000;
+/// The error code for a `RestError` returned by the method, if the requested item does not exist.
+const NOT_FOUND: i64 = -32001;
+/// The error code for a `RestError` returned by the method, if the method is not available.
+const UNAVAILABLE: i64 = -32002;
+
+/// Defines the JSON-RPC methods, along with the list of their names and parameters.
+///
+/// Each method calls the REST handler of the same operation, with the parameters given by position or by name,
+/// and serializes the value it responds with. Each method requires the scope of the routes of its REST handler,
+/// and weighs as much as its route against the rate limits.
+macro_rules! rpc_methods {
+    (
+        $rest:ident,
+        $( $method:literal ( $( $param:ident : $type:ty ),* ) [$scope:ident, $class:ident] => $call:expr, )*
+    ) => {
+        /// The JSON-RPC methods, along with the names of their parameters.
+        pub const RPC_METHODS: &[(&str, &[&str])] = &[ $( ($method, &[ $( stringify!($param) ),* ]) ),* ];
+
+        /// Returns the scope that is required to call the given JSON-RPC method, if the method exists.
+        fn rpc_method_scope(method: &str) -> Option<Scope> {
+            match method {
+                $( $method => Some(Scope::$scope), )*
+                _ => None,
+            }
+        }
+
+        /// Returns the class of the route of the given JSON-RPC method, which defaults to a light route.
+        fn rpc_method_class(method: &str) -> RouteClass {
+            match method {
+                $( $method => RouteClass::$class, )*
+                _ => RouteClass::Light,
+            }
+        }
+
+        impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+            /// Calls the JSON-RPC method with the given parameters.
+            async fn call_rpc_method(&self, method: &str, params: Params) -> Result<Value, RpcError> {
+                let $rest = self.clone();
+                match method {
+                    $( $method => {
+                        #[allow(unused_mut, unused_variables)]
+                        let mut params = params;
+                        $( let $param: $type = params.take(stringify!($param))?; )*
+                        $call.await.into_rpc_result()
+                    } )*
+                    _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method '{method}' not found"))),
+                }
+            }
+        }
+    };
+}
+
+rpc_methods! {
+    rest,
+    // ../block/..
+    "getLatestHeight"() [Read, Light] => Self::get_block_height_latest(State(rest)),
+    "getLatestHash"() [Read, Light] => Self::get_block_hash_latest(State(rest)),
+    "getLatestBlock"() [Read, Light] => Self::get_block_latest(State(rest)),
+    "getBlock"(height_or_hash: String) [Read, Light] => Self::get_block(State(rest), Path(height_or_hash)),
+    "getBlocks"(start: Option<u32>, end: Option<u32>, cursor: Option<u32>, limit: Option<u32>) [Read, Heavy] => {
+        Self::get_blocks(State(rest), Query(BlockRange { start, end, cursor, limit }))
+    },
+    "getHeight"(hash: N::BlockHash) [Read, Light] => Self::get_height(State(rest), Path(hash)),
+    "getBlockTransactions"(height: u32) [Read, Medium] => Self::get_block_transactions(State(rest), Path(height)),
+    // ../transaction/..
+    "getTransaction"(id: N::TransactionID) [Read, Light] => Self::get_transaction(State(rest), Path(id)),
+    "getConfirmedTransaction"(id: N::TransactionID) [Read, Light] => {
+        Self::get_confirmed_transaction(State(rest), Path(id))
+    },
+    "getTransactionStatus"(id: N::TransactionID) [Read, Light] => Self::get_transaction_status(State(rest), Path(id)),
+    "getTransactions"(
+        program: Option<String>,
+        function: Option<String>,
+        fee_payer: Option<String>,
+        from_height: Option<u32>,
+        cursor: Option<usize>,
+        limit: Option<usize>
+    ) [Read, Heavy] => {
+        let query = TransactionQuery { program, function, fee_payer, from_height, cursor, limit };
+        Self::get_transactions(State(rest), Query(query))
+    },
+    "broadcastTransaction"(transaction: Transaction<N>) [Broadcast, Medium] => {
+        Self::transaction_broadcast(State(rest), Json(transaction))
+    },
+    "simulateTransaction"(transaction: Transaction<N>) [Read, Heavy] => {
+        Self::transaction_simulate(State(rest), Json(transaction))
+    },
+    // ../solution/..
+    "broadcastSolution"(solution: ProverSolution<N>) [Broadcast, Medium] => {
+        Self::solution_broadcast(State(rest), Json(solution))
+    },
+    // ../find/..
+    "findBlockHash"(transaction_id: N::TransactionID) [Read, Light] => {
+        Self::find_block_hash(State(rest), Path(transaction_id))
+    },
+    "findTransactionIdFromProgramId"(program_id: ProgramID<N>) [Read, Light] => {
+        Self::find_transaction_id_from_program_id(State(rest), Path(program_id))
+    },
+    "findTransactionIdFromTransitionId"(transition_id: N::TransitionID) [Read, Light] => {
+        Self::find_transaction_id_from_transition_id(State(rest), Path(transition_id))
+    },
+    "findTransitionId"(input_or_output_id: Field<N>) [Read, Light] => {
+        Self::find_transition_id(State(rest), Path(input_or_output_id))
+    },
+    // ../peers/..
+    "getPeersCount"() [Read, Light] => Self::get_peers_count(State(rest)),
+    "getPeersAll"() [Read, Light] => Self::get_peers_all(State(rest)),
+    "getPeersAllMetrics"() [Read, Light] => Self::get_peers_all_metrics(State(rest)),
+    // ../program/..
+    "getProgram"(id: ProgramID<N>) [Read, Light] => Self::get_program(State(rest), Path(id)),
+    "getMappingNames"(id: ProgramID<N>) [Read, Light] => Self::get_mapping_names(State(rest), Path(id)),
+    "getMappingValue"(
+        id: ProgramID<N>,
+        name: Identifier<N>,
+        key: Plaintext<N>,
+        metadata: Option<bool>
+    ) [Read, Light] => {
+        Self::get_mapping_value(State(rest), Path((id, name, key)), metadata.map(|metadata| Query(Metadata { metadata })))
+    },
+    // ../fee/..
+    "getFeeEstimate"(program: ProgramID<N>, function: Identifier<N>) [Read, Medium] => {
+        Self::get_fee_estimate(State(rest), Query(ProgramCall { program, function }))
+    },
+    // ../address/..
+    "getAddressBalance"(address: Address<N>) [Read, Light] => Self::get_address_balance(State(rest), Path(address)),
+    "getAddressBonded"(address: Address<N>) [Read, Light] => Self::get_address_bonded(State(rest), Path(address)),
+    "getAddressTransitions"(address: Address<N>, cursor: Option<usize>, limit: Option<usize>) [Read, Heavy] => {
+        Self::get_address_transitions(State(rest), Path(address), Query(Page { cursor, limit }))
+    },
+    // ../memoryPool/..
+    "getMemoryPoolTransmissions"() [Read, Light] => Self::get_memory_pool_transmissions(State(rest)),
+    "getMemoryPoolSolutions"() [Read, Light] => Self::get_memory_pool_solutions(State(rest)),
+    "getMemoryPoolTransactions"() [Read, Light] => Self::get_memory_pool_transactions(State(rest)),
+    // misc
+    "getStatePath"(commitment: Field<N>) [Read, Light] => {
+        Self::get_state_path_for_commitment(State(rest), Path(commitment))
+    },
+    "getLatestStateRoot"() [Read, Light] => Self::get_state_root_latest(State(rest)),
+    "getLatestCommittee"() [Read, Light] => Self::get_committee_latest(State(rest)),
+    "getMethods"() [Read, Light] => Self::get_rpc_methods(),
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /testnet3/rpc
+    pub(crate) async fn rpc(State(rest): State<Self>, headers: HeaderMap, body: Bytes) -> Response {
+        // Parse the request body.
+        let request = match serde_json::from_slice::<Value>(&body) {
+            Ok(request) => request,
+            Err(error) => return Json(RpcError::new(PARSE_ERROR, error.to_string()).into_json(None)).into_response(),
+        };
+
+        // Ensure the caller is granted the scope of every method it calls, if the scope is protected.
+        let calls: Vec<&Value> = match &request {
+            Value::Array(requests) => requests.iter().collect(),
+            request => vec![request],
+        };
+        let mut scopes = calls
+            .into_iter()
+            .filter_map(|request| rpc_method_scope(request.get("method")?.as_str()?))
+            .filter(|scope| rest.protected_scopes.contains(scope))
+            .peekable();
+        if scopes.peek().is_some() {
+            let claims = match request_claims(&headers) {
+                Ok(claims) => claims,
+                Err(response) => return response,
+            };
+            for scope in scopes {
+                if let Err(response) = check_scope(claims.as_ref(), scope) {
+                    return response;
+                }
+            }
+        }
+
+        match request {
+            // Process the batch request.
+            Value::Array(requests) => {
+                // Ensure the batch is not empty, and is bounded.
+                if requests.is_empty() || requests.len() > MAX_BATCH_SIZE {
+                    let error =
+                        RpcError::new(INVALID_REQUEST, format!("The batch must have 1 to {MAX_BATCH_SIZE} requests"));
+                    return Json(error.into_json(None)).into_response();
+                }
+                let mut responses = Vec::with_capacity(requests.len());
+                for request in requests {
+                    responses.extend(rest.process_rpc_request(request).await);
+                }
+                // If the batch only contains notifications, there is nothing to respond with.
+                match responses.is_empty() {
+                    true => StatusCode::NO_CONTENT.into_response(),
+                    false => Json(responses).into_response(),
+                }
+            }
+            // Process the single request.
+            request => match rest.process_rpc_request(request).await {
+                Some(response) => Json(response).into_response(),
+                None => StatusCode::NO_CONTENT.into_response(),
+            },
+        }
+    }
+
+    // The `getMethods` JSON-RPC method.
+    async fn get_rpc_methods() -> RestJson<IndexMap<&'static str, &'static [&'static str]>> {
+        RestJson(RPC_METHODS.iter().map(|(method, params)| (*method, *params)).collect())
+    }
+
+    /// Processes the given JSON-RPC request, and returns the response, unless the request is a notification.
+    async fn process_rpc_request(&self, request: Value) -> Option<Value> {
+        // Parse the request.
+        let RpcCall { id, method, params } = match parse_rpc_request(request) {
+            Ok(request) => request,
+            Err((id, error)) => return Some(error.into_json(id)),
+        };
+        // Call the method.
+        let result = self.call_rpc_method(&method, params).await;
+        // Return the response, unless the request is a notification.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(error) => error.into_json(Some(id)),
+        })
+    }
+}
+
+/// Returns the route classes of the methods in the given JSON-RPC request body, which may be a batch.
+///
+/// A body that is not valid JSON, or a request without a method, weighs as much as a light route.
+pub(crate) fn rpc_request_classes(body: &[u8]) -> Vec<RouteClass> {
+    let class =
+        |request: &Value| request.get("method").and_then(Value::as_str).map_or(RouteClass::Light, rpc_method_class);
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(requests)) if !requests.is_empty() => requests.iter().map(class).collect(),
+        Ok(request) => vec![class(&request)],
+        Err(_) => vec![RouteClass::Light],
+    }
+}
+
+/// A parsed JSON-RPC request.
+struct RpcCall {
+    /// The ID of the request, which is `None` for a notification.
+    id: Option<Value>,
+    /// The method to call.
+    method: String,
+    /// The parameters of the method.
+    params: Params,
+}
+
+/// Parses the given JSON-RPC request into its ID (if it is not a notification), method, and parameters.
+///
+/// On failure, this function returns the ID of the request (if it is known), along with the error.
+fn parse_rpc_request(request: Value) -> Result<RpcCall, (Option<Value>, RpcError)> {
+    let Value::Object(mut request) = request else {
+        return Err((Some(Value::Null), RpcError::new(INVALID_REQUEST, "The request must be an object")));
+    };
+    // Retrieve the ID, where a request without an ID is a notification.
+    let id = request.remove("id");
+    let error_id = Some(id.clone().unwrap_or(Value::Null));
+    if !matches!(id, None | Some(Value::Null | Value::Number(_) | Value::String(_))) {
+        return Err((Some(Value::Null), RpcError::new(INVALID_REQUEST, "The ID must be a number or a string")));
+    }
+    // Ensure the version is supported.
+    if request.get("jsonrpc") != Some(&Value::from("2.0")) {
+        return Err((error_id, RpcError::new(INVALID_REQUEST, "The 'jsonrpc' version must be \"2.0\"")));
+    }
+    // Retrieve the method.
+    let Some(Value::String(method)) = request.remove("method") else {
+        return Err((error_id, RpcError::new(INVALID_REQUEST, "The 'method' must be a string")));
+    };
+    // Retrieve the parameters.
+    let params = match request.remove("params") {
+        None => Params::Positional(Vec::new()),
+        Some(Value::Array(params)) => Params::Positional(params),
+        Some(Value::Object(params)) => Params::Named(params),
+        Some(_) => {
+            return Err((error_id, RpcError::new(INVALID_REQUEST, "The 'params' must be an array or an object")))
+        }
+    };
+    Ok(RpcCall { id, method, params })
+}
+
+/// The parameters of a JSON-RPC request.
+enum Params {
+    /// The parameters, in the order of the method parameters.
+    Positional(Vec<Value>),
+    /// The parameters, by the name of the method parameters.
+    Named(Map<String, Value>),
+}
+
+impl Params {
+    /// Takes the next parameter with the given name, where a missing parameter is `null`.
+    fn take<T: DeserializeOwned>(&mut self, name: &str) -> Result<T, RpcError> {
+        let value = match self {
+            Self::Positional(params) if params.is_empty() => Value::Null,
+            Self::Positional(params) => params.remove(0),
+            Self::Named(params) => params.remove(name).unwrap_or(Value::Null),
+        };
+        serde_json::from_value(value)
+            .map_err(|error| RpcError::new(INVALID_PARAMS, format!("Invalid parameter '{name}' - {error}")))
+    }
+}
+
+/// A JSON-RPC error.
+#[derive(Debug, Serialize)]
+struct RpcError {
+    /// The error code.
+    code: i64,
+    /// The error message.
+    message: String,
+}
+
+impl RpcError {
+    /// Initializes a new JSON-RPC error.
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    /// Returns the JSON-RPC response for the error.
+    fn into_json(self, id: Option<Value>) -> Value {
+        json!({ "jsonrpc": "2.0", "error": self, "id": id.unwrap_or(Value::Null) })
+    }
+}
+
+impl From<RestError> for RpcError {
+    fn from(error: RestError) -> Self {
+        let code = match error.kind {
+            RestErrorKind::NotFound => NOT_FOUND,
+            RestErrorKind::InvalidInput => INVALID_PARAMS,
+            RestErrorKind::Unavailable => UNAVAILABLE,
+            RestErrorKind::Internal => SERVER_ERROR,
+        };
+        Self::new(code, error.message)
+    }
+}
+
+/// A trait for the responses of the REST handlers, which may not return an error.
+trait IntoRpcResult {
+    /// Returns the result of the JSON-RPC method.
+    fn into_rpc_result(self) -> Result<Value, RpcError>;
+}
+
+impl<T: Serialize> IntoRpcResult for RestJson<T> {
+    fn into_rpc_result(self) -> Result<Value, RpcError> {
+        serde_json::to_value(self.0).map_err(|error| RpcError::new(INTERNAL_ERROR, error.to_string()))
+    }
+}
+
+impl<T: Serialize> IntoRpcResult for Result<RestJson<T>, RestError> {
+    fn into_rpc_result(self) -> Result<Value, RpcError> {
+        self?.into_rpc_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rpc_request() {
+        // Ensure a request with positional parameters is parsed.
+        let request = json!({ "jsonrpc": "2.0", "method": "getBlock", "params": [5], "id": 1 });
+        let RpcCall { id, method, mut params } = parse_rpc_request(request).unwrap();
+        assert_eq!((id, method.as_str()), (Some(json!(1)), "getBlock"));
+        assert_eq!(params.take::<u32>("height").unwrap(), 5);
+        assert_eq!(params.take::<Option<u32>>("metadata").unwrap(), None);
+
+        // Ensure a notification with named parameters is parsed.
+        let request = json!({ "jsonrpc": "2.0", "method": "getBlocks", "params": { "end": 2, "start": 1 } });
+        let RpcCall { id, mut params, .. } = parse_rpc_request(request).unwrap();
+        assert_eq!(id, None);
+        assert_eq!((params.take::<u32>("start").unwrap(), params.take::<u32>("end").unwrap()), (1, 2));
+        assert_eq!(params.take::<u32>("start").unwrap_err().code, INVALID_PARAMS);
+
+        // Ensure invalid requests are rejected with the ID, if it is known.
+        let (id, error) = parse_rpc_request(json!({ "method": "getBlock", "id": "a" })).err().unwrap();
+        assert_eq!((id, error.code), (Some(json!("a")), INVALID_REQUEST));
+        let (id, error) = parse_rpc_request(json!({ "jsonrpc": "2.0", "method": 1, "id": 2 })).err().unwrap();
+        assert_eq!((id, error.code), (Some(json!(2)), INVALID_REQUEST));
+        let (id, error) = parse_rpc_request(json!([1])).err().unwrap();
+        assert_eq!((id, error.code), (Some(Value::Null), INVALID_REQUEST));
+    }
+
+    #[test]
+    fn test_into_rpc_result() {
+        // Ensure the response of a handler is the result of the method.
+        assert_eq!(RestJson(json!({ "height": 1 })).into_rpc_result().unwrap(), json!({ "height": 1 }));
+        assert_eq!(Ok::<_, RestError>(RestJson(vec![1u32, 2])).into_rpc_result().unwrap(), json!([1, 2]));
+        // Ensure the error of a handler has the code of its kind.
+        let error = Err::<RestJson<u32>, _>(RestError::not_found("Missing block")).into_rpc_result().unwrap_err();
+        assert_eq!((error.code, error.message.as_str()), (NOT_FOUND, "Missing block"));
+        let error = Err::<RestJson<u32>, _>(RestError::invalid_input("Invalid range")).into_rpc_result().unwrap_err();
+        assert_eq!(error.code, INVALID_PARAMS);
+        let error = Err::<RestJson<u32>, _>(RestError::unavailable("Not enabled")).into_rpc_result().unwrap_err();
+        assert_eq!(error.code, UNAVAILABLE);
+        let error = Err::<RestJson<u32>, _>(RestError::from(anyhow::anyhow!("Failed"))).into_rpc_result().unwrap_err();
+        assert_eq!(error.code, SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_rpc_methods() {
+        // Ensure the method names are unique.
+        let methods = RPC_METHODS.iter().map(|(method, _)| *method).collect::<std::collections::HashSet<_>>();
+        assert_eq!(methods.len(), RPC_METHODS.len());
+        // Ensure the parameters are listed in order.
+        let params = RPC_METHODS.iter().find(|(method, _)| *method == "getMappingValue").unwrap().1;
+        assert_eq!(params, ["id", "name", "key", "metadata"]);
+        // Ensure the methods require the scope of their routes.
+        assert_eq!(rpc_method_scope("getBlocks"), Some(Scope::Read));
+        assert_eq!(rpc_method_scope("broadcastTransaction"), Some(Scope::Broadcast));
+        assert_eq!(rpc_method_scope("unknownMethod"), None);
+        // Ensure the methods weigh as much as their routes.
+        assert_eq!(rpc_method_class("getBlocks"), RouteClass::from_path("/testnet3/blocks"));
+        assert_eq!(rpc_method_class("getLatestHeight"), RouteClass::from_path("/testnet3/block/height/latest"));
+    }
+
+    #[test]
+    fn test_rpc_request_classes() {
+        // Ensure each request of a batch is charged with the class of its method.
+        let batch = json!([
+            { "jsonrpc": "2.0", "method": "getBlocks", "params": [0, 10], "id": 1 },
+            { "jsonrpc": "2.0", "method": "broadcastTransaction", "params": [], "id": 2 },
+            { "jsonrpc": "2.0", "method": "getLatestHeight", "id": 3 },
+        ]);
+        let classes = rpc_request_classes(batch.to_string().as_bytes());
+        assert_eq!(classes, [RouteClass::Heavy, RouteClass::Medium, RouteClass::Light]);
+
+        // Ensure a single request, and an invalid body, are charged once.
+        let request = json!({ "jsonrpc": "2.0", "method": "getTransactions", "id": 1 });
+        assert_eq!(rpc_request_classes(request.to_string().as_bytes()), [RouteClass::Heavy]);
+        assert_eq!(rpc_request_classes(b"{"), [RouteClass::Light]);
+    }
+}
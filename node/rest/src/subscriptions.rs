@@ -0,0 +1,199 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::block::Block;
+
+use axum::{
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::{broadcast::error::RecvError, OwnedSemaphorePermit};
+
+/// The name of the header with which a client resumes a subscription after reconnecting.
+const LAST_EVENT_ID: &str = "last-event-id";
+/// The interval at which a block subscription checks the ledger for blocks that were added without a notification.
+const LEDGER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The subscription query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Cursor {
+    /// The block height to start the subscription from (inclusive), which defaults to the next block.
+    from_height: Option<u32>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // GET /testnet3/subscribe/blocks?from_height={height}
+    pub(crate) async fn subscribe_blocks(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Query(cursor): Query<Cursor>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        // Determine the height to start from, where each event ID is the height of its block.
+        let start_height = match last_event_id(&headers) {
+            Some(id) => {
+                id.parse::<u32>().map_err(|_| RestError::invalid_input(format!("Invalid event ID '{id}'")))? + 1
+            }
+            None => rest.start_height(cursor),
+        };
+
+        // Ensure the number of concurrent subscriptions is bounded.
+        let permit = rest.subscription_permit()?;
+
+        // Stream the blocks.
+        let events = rest.block_stream(start_height).map(move |block| {
+            // Hold the permit until the subscription ends.
+            let _permit = &permit;
+            Event::default().event("block").id(block.height().to_string()).json_data(&block)
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+
+    // GET /testnet3/subscribe/transactions?from_height={height}
+    pub(crate) async fn subscribe_transactions(
+        State(rest): State<Self>,
+        headers: HeaderMap,
+        Query(cursor): Query<Cursor>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        // Determine the height to start from, and the transactions to skip in its block,
+        // where each event ID is the height of its block and the index of the transaction.
+        let (start_height, num_skipped) = match last_event_id(&headers) {
+            Some(id) => {
+                let error = || RestError::invalid_input(format!("Invalid event ID '{id}'"));
+                let (height, index) = id.split_once(':').ok_or_else(error)?;
+                (height.parse::<u32>().map_err(|_| error())?, index.parse::<u32>().map_err(|_| error())? + 1)
+            }
+            None => (rest.start_height(cursor), 0),
+        };
+
+        // Ensure the number of concurrent subscriptions is bounded.
+        let permit = rest.subscription_permit()?;
+
+        // Stream the confirmed transactions of the blocks.
+        let events = rest.block_stream(start_height).flat_map(move |block| {
+            // Hold the permit until the subscription ends.
+            let _permit = &permit;
+            let height = block.height();
+            let events = block
+                .transactions()
+                .iter()
+                .filter(|transaction| height != start_height || transaction.index() >= num_skipped)
+                .map(|transaction| {
+                    Event::default()
+                        .event("transaction")
+                        .id(format!("{height}:{}", transaction.index()))
+                        .json_data(transaction)
+                })
+                .collect::<Vec<_>>();
+            stream::iter(events)
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+
+    // GET /testnet3/subscribe/memoryPool
+    pub(crate) async fn subscribe_memory_pool(
+        State(rest): State<Self>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        let receiver = match &rest.consensus {
+            Some(consensus) => consensus.subscribe_memory_pool(),
+            None => return Err(RestError::unavailable("Route isn't available for this node type")),
+        };
+        // Ensure the number of concurrent subscriptions is bounded.
+        let permit = rest.subscription_permit()?;
+
+        // Stream the unconfirmed transmissions, as they are added to the memory pool.
+        // Note: The permit is held in the state of the stream, until the subscription ends.
+        let events = stream::unfold((receiver, permit), |(mut receiver, permit)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(transmission) => {
+                        let event = Event::default().event("transmission").json_data(transmission);
+                        return Some((event, (receiver, permit)));
+                    }
+                    // The memory pool is not resumable, so the missed transmissions are skipped.
+                    Err(RecvError::Lagged(num_skipped)) => {
+                        debug!("A memory pool subscriber skipped {num_skipped} transmissions");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns a permit to open a subscription, if fewer than the maximum number of subscriptions are open.
+    fn subscription_permit(&self) -> Result<OwnedSemaphorePermit, RestError> {
+        self.subscriptions
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| RestError::unavailable("Too many subscriptions are open, try again later"))
+    }
+
+    /// Returns the height to start a subscription from, which defaults to the next block.
+    fn start_height(&self, cursor: Cursor) -> u32 {
+        cursor.from_height.unwrap_or_else(|| self.ledger.latest_height() + 1)
+    }
+
+    /// Returns a stream of the blocks from the given height onwards.
+    ///
+    /// The stream first catches up with the blocks in the ledger, and then follows the blocks as they are
+    /// added to the ledger. If the subscriber falls behind, it catches up from the ledger again.
+    /// As blocks synced from the CDN are not notified, the ledger is also checked periodically.
+    /// If a block fails to be read from the ledger, it is retried periodically, instead of ending the stream.
+    pub(crate) fn block_stream(&self, start_height: u32) -> impl Stream<Item = Block<N>> {
+        // Subscribe before reading the ledger, so that no block is missed in between.
+        let receiver = self.block_sender.subscribe();
+        let ledger = self.ledger.clone();
+
+        stream::unfold((receiver, start_height), move |(mut receiver, next_height)| {
+            let ledger = ledger.clone();
+            async move {
+                loop {
+                    // If the block is in the ledger, catch up from the ledger.
+                    if next_height <= ledger.latest_height() {
+                        // Note: The block is read in a blocking task, as it is read from storage.
+                        let ledger = ledger.clone();
+                        match tokio::task::spawn_blocking(move || ledger.get_block(next_height)).await {
+                            Ok(Ok(block)) => return Some((block, (receiver, next_height + 1))),
+                            // Retry the block after a while, so that a transient failure does not end the stream.
+                            Ok(Err(error)) => warn!("Failed to retrieve block {next_height} from the ledger - {error}"),
+                            Err(error) => warn!("Failed to retrieve block {next_height} from the ledger - {error}"),
+                        }
+                        tokio::time::sleep(LEDGER_POLL_INTERVAL).await;
+                        continue;
+                    }
+                    // Otherwise, wait for the next block to be added to the ledger.
+                    match tokio::time::timeout(LEDGER_POLL_INTERVAL, receiver.recv()).await {
+                        Ok(Ok(block)) if block.height() == next_height => {
+                            return Some((block, (receiver, next_height + 1)));
+                        }
+                        // Skip the blocks that were already sent, and catch up on the blocks that were missed.
+                        Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) | Err(_) => continue,
+                        Ok(Err(RecvError::Closed)) => return None,
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Returns the ID of the last event received by the client, if it is resuming a subscription.
+fn last_event_id(headers: &HeaderMap) -> Option<&str> {
+    headers.get(LAST_EVENT_ID).and_then(|id| id.to_str().ok())
+}
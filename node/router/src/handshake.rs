@@ -262,6 +262,10 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection request from '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip.ip()) {
+            bail!("Dropping connection request from '{peer_ip}' (banned)")
+        }
         // Ensure the peer is not spamming connection attempts.
         if !peer_ip.ip().is_loopback() {
             // Add this connection attempt and retrieve the number of attempts.
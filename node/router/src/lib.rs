@@ -46,7 +46,15 @@ use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 use anyhow::{bail, Result};
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashSet, future::Future, net::SocketAddr, ops::Deref, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
 use tokio::task::JoinHandle;
 
 #[derive(Clone)]
@@ -72,7 +80,7 @@ pub struct InnerRouter<N: Network> {
     /// The resolver.
     resolver: Resolver,
     /// The set of trusted peers.
-    trusted_peers: IndexSet<SocketAddr>,
+    trusted_peers: RwLock<IndexSet<SocketAddr>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<IndexMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -84,6 +92,8 @@ pub struct InnerRouter<N: Network> {
     candidate_peers: RwLock<IndexSet<SocketAddr>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<IndexMap<SocketAddr, Instant>>,
+    /// The set of banned IPs.
+    banned_ips: RwLock<IndexSet<IpAddr>>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -119,11 +129,12 @@ impl<N: Network> Router<N> {
             account,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_peers: trusted_peers.iter().copied().collect(),
+            trusted_peers: RwLock::new(trusted_peers.iter().copied().collect()),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            banned_ips: Default::default(),
             handles: Default::default(),
             is_dev,
         })))
@@ -176,6 +187,10 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(&peer_ip.ip()) {
+            bail!("Dropping connection attempt to '{peer_ip}' (banned)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (already shaking hands as the initiator)")
@@ -288,6 +303,11 @@ impl<N: Network> Router<N> {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the given IP is banned.
+    pub fn is_banned(&self, ip: &IpAddr) -> bool {
+        self.banned_ips.read().contains(ip)
+    }
+
     /// Returns the maximum number of connected peers.
     pub fn max_connected_peers(&self) -> usize {
         self.tcp.config().max_connections as usize
@@ -363,9 +383,14 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of banned IPs.
+    pub fn banned_ips(&self) -> Vec<IpAddr> {
+        self.banned_ips.read().iter().copied().collect()
+    }
+
     /// Returns the list of trusted peers.
-    pub fn trusted_peers(&self) -> &IndexSet<SocketAddr> {
-        &self.trusted_peers
+    pub fn trusted_peers(&self) -> Vec<SocketAddr> {
+        self.trusted_peers.read().iter().copied().collect()
     }
 
     /// Returns the list of bootstrap peers.
@@ -421,7 +446,10 @@ impl<N: Network> Router<N> {
             .iter()
             .filter(|peer_ip| {
                 // Ensure the peer is not itself, is not already connected, and is not restricted.
-                !self.is_local_ip(peer_ip) && !self.is_connected(peer_ip) && !self.is_restricted(peer_ip)
+                !self.is_local_ip(peer_ip)
+                    && !self.is_connected(peer_ip)
+                    && !self.is_restricted(peer_ip)
+                    && !self.is_banned(&peer_ip.ip())
             })
             .take(max_candidate_peers);
 
@@ -441,6 +469,44 @@ impl<N: Network> Router<N> {
         self.update_metrics();
     }
 
+    /// Inserts the given peer IP into the trusted peers.
+    pub fn insert_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        // Remove this peer from the restricted peers, if it exists.
+        self.restricted_peers.write().remove(&peer_ip);
+        // Add the peer to the trusted peers.
+        self.trusted_peers.write().insert(peer_ip)
+    }
+
+    /// Removes the given peer IP from the trusted peers.
+    pub fn remove_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_peers.write().shift_remove(&peer_ip)
+    }
+
+    /// Bans the given IP, and disconnects from any connected peers with this IP.
+    pub fn ban(&self, ip: IpAddr) -> bool {
+        // Add the IP to the banned IPs.
+        let is_new = self.banned_ips.write().insert(ip);
+        // Remove any candidate peers with this IP.
+        self.candidate_peers.write().retain(|peer_ip| peer_ip.ip() != ip);
+        // Disconnect from any connected peers with this IP.
+        for peer_ip in self.connected_peers().into_iter().filter(|peer_ip| peer_ip.ip() == ip) {
+            self.disconnect(peer_ip);
+        }
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+        is_new
+    }
+
+    /// Unbans the given IP, and lifts any restrictions on peers with this IP.
+    pub fn unban(&self, ip: IpAddr) -> bool {
+        // Remove any restricted peers with this IP.
+        self.restricted_peers.write().retain(|peer_ip, _| peer_ip.ip() != ip);
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+        // Remove the IP from the banned IPs.
+        self.banned_ips.write().shift_remove(&ip)
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
@@ -84,6 +84,15 @@ pub async fn client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNe
     .into()
 }
 
+/// Initializes a client router that listens on the given IP, so that it can be told apart from other local routers.
+#[allow(dead_code)]
+pub async fn client_with_ip(ip: IpAddr, listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
+    Router::new(SocketAddr::new(ip, listening_port), NodeType::Client, sample_account(), &[], max_peers, true)
+        .await
+        .expect("couldn't create client router")
+        .into()
+}
+
 /// Initializes a prover router. Setting the `listening_port = 0` will result in a random port being assigned.
 #[allow(dead_code)]
 pub async fn prover(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
@@ -18,6 +18,8 @@ use common::*;
 use snarkos_node_tcp::{protocols::Handshake, P2P};
 
 use core::time::Duration;
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv4Addr};
 
 #[tokio::test]
 async fn test_disconnect_without_handshake() {
@@ -108,3 +110,56 @@ async fn test_disconnect_with_handshake() {
     assert_eq!(node1.tcp().num_connected(), 1); // Router 1 has no way of knowing that Router 0 disconnected.
     assert_eq!(node1.tcp().num_connecting(), 0);
 }
+
+// Note: The banned peer listens on another loopback IP, which is only routed by default on Linux.
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_ban_with_handshake() {
+    // Create 3 routers, where node1 has its own IP, so that banning it does not ban the other local routers.
+    let node0 = validator(0, 2).await;
+    let node1 = client_with_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 0, 1).await;
+    let node2 = client(0, 1).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+    node2.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+    node2.tcp().enable_listener().await.unwrap();
+
+    // Connect node0 to node1 and node2.
+    node0.connect(node1.local_ip());
+    node0.connect(node2.local_ip());
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Check the router level.
+    assert_eq!(node0.number_of_connected_peers(), 2);
+    assert_eq!(node1.number_of_connected_peers(), 1);
+    assert_eq!(node2.number_of_connected_peers(), 1);
+
+    // Ban node1 in node0, which disconnects node1.
+    assert!(node0.ban(node1.local_ip().ip()));
+    assert!(!node0.ban(node1.local_ip().ip()));
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Ensure only node1 is banned and disconnected, while node2 stays connected.
+    assert!(node0.is_banned(&node1.local_ip().ip()));
+    assert!(!node0.is_banned(&node2.local_ip().ip()));
+    assert_eq!(node0.connected_peers(), vec![node2.local_ip()]);
+    assert_eq!(node0.tcp().num_connected(), 1);
+
+    // Ensure node0 refuses to connect to node1.
+    assert!(node0.connect(node1.local_ip()).is_none());
+
+    // Unban node1 in node0.
+    assert!(node0.unban(node1.local_ip().ip()));
+    assert!(!node0.is_banned(&node1.local_ip().ip()));
+
+    // Ensure node0 attempts to connect to node1 again.
+    assert!(node0.connect(node1.local_ip()).is_some());
+}
@@ -17,7 +17,8 @@ mod router;
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_cdn::CdnConfig;
+use snarkos_node_rest::{IndexConfig, RateLimitConfig, Rest, Scope};
 use snarkos_node_router::{
     messages::{Message, NodeType, UnconfirmedSolution},
     Heartbeat,
@@ -41,6 +42,7 @@ use snarkvm::{
     },
 };
 
+use aleo_std::StorageMode;
 use anyhow::Result;
 use core::future::Future;
 use parking_lot::Mutex;
@@ -76,12 +78,14 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub async fn new(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_rate_limits: RateLimitConfig,
+        rest_indexes: IndexConfig,
+        protected_scopes: &[Scope],
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
+        cdn: Option<CdnConfig>,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -90,19 +94,9 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         let signal_node = Self::handle_signals(shutdown.clone());
 
         // Initialize the ledger.
-        let ledger = Ledger::<N, C>::load(genesis.clone(), dev)?;
+        let ledger = crate::load_ledger::<N, C>(genesis.clone(), &storage_mode)?;
         // TODO: Remove me after Phase 3.
-        let ledger = crate::phase_3_reset(ledger, dev)?;
-        // Initialize the CDN.
-        if let Some(base_url) = cdn {
-            // Sync the ledger with the CDN.
-            if let Err((_, error)) =
-                snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), shutdown.clone()).await
-            {
-                crate::log_clean_error(dev);
-                return Err(error);
-            }
-        }
+        let ledger = crate::phase_3_reset(ledger, &storage_mode)?;
 
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone(), shutdown.clone()));
@@ -116,7 +110,7 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             account,
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
-            dev.is_some(),
+            storage_mode.dev().is_some(),
         )
         .await?;
         // Load the coinbase puzzle.
@@ -135,12 +129,26 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?);
+            // Notify the REST subscribers of the blocks that are synced from peers.
+            let block_sender = node.sync.block_sender().clone();
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rest_rate_limits,
+                    rest_indexes,
+                    protected_scopes,
+                    None,
+                    ledger.clone(),
+                    block_sender,
+                    Arc::new(node.clone()),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
-        // Initialize the sync module.
-        node.initialize_sync();
+        // Initialize the sync module, along with the CDN sync.
+        node.initialize_sync(cdn, storage_mode);
         // Initialize the notification message loop.
         node.handles.lock().push(crate::start_notification_message_loop());
         // Pass the node to the signal handler.
@@ -162,7 +170,58 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     /// Initializes the sync pool.
-    fn initialize_sync(&self) {
+    ///
+    /// If a CDN is given, the blocks up to the CDN height are synced from the CDN, while the blocks after it
+    /// are requested from peers in the meantime. If the CDN sync stops early, the peers sync the remaining blocks.
+    fn initialize_sync(&self, cdn: Option<CdnConfig>, storage_mode: StorageMode) {
+        // Start the CDN sync.
+        if let Some(cdn) = cdn {
+            // Hold back the block requests to the peers until the CDN height is known.
+            self.sync.set_cdn_height(u32::MAX);
+            let node = self.clone();
+            self.handles.lock().push(tokio::spawn(async move {
+                // Leave the blocks up to the CDN height to the CDN sync.
+                // Note: The height is fetched once, so that the CDN sync stops at the height that the peers start from.
+                let cdn_height = match snarkos_node_cdn::fetch_cdn_height(&cdn.mirrors).await {
+                    Ok(cdn_height) => cdn_height,
+                    Err(error) => {
+                        warn!("Skipping the CDN sync - {error}");
+                        node.sync.set_cdn_height(0);
+                        return;
+                    }
+                };
+                node.sync.set_cdn_height(cdn_height);
+                // Sync the ledger with the CDN.
+                let ledger = node.ledger.clone();
+                let shutdown = node.shutdown.clone();
+                match snarkos_node_cdn::sync_ledger_with_cdn(
+                    &cdn.mirrors,
+                    &cdn.manifest,
+                    ledger,
+                    Some(cdn_height),
+                    shutdown,
+                )
+                .await
+                {
+                    Ok(height) => info!("Finished the CDN sync at block {height}"),
+                    // Note: The ledger is only corrupted if it fell behind the blocks that the CDN sync added.
+                    Err((_, error)) => {
+                        error!("{error}");
+                        crate::log_clean_error(&storage_mode);
+                        // Shut down the node, as it cannot proceed with a corrupted ledger.
+                        // Note: This is done in a separate task, as shutting down aborts this task.
+                        tokio::spawn(async move {
+                            node.shut_down().await;
+                            std::process::exit(1);
+                        });
+                        return;
+                    }
+                }
+                // Hand the remaining blocks over to the peers.
+                node.sync.set_cdn_height(0);
+            }));
+        }
+
         // Start the sync loop.
         let node = self.clone();
         self.handles.lock().push(tokio::spawn(async move {
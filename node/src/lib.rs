@@ -46,34 +46,50 @@ mod traits;
 pub use traits::*;
 
 /// A helper to log instructions to recover.
-pub fn log_clean_error(dev: Option<u16>) {
-    match dev {
-        Some(id) => error!("Storage corruption detected! Run `snarkos clean --dev {id}` to reset storage"),
-        None => error!("Storage corruption detected! Run `snarkos clean` to reset storage"),
+pub fn log_clean_error(storage_mode: &StorageMode) {
+    match storage_mode {
+        StorageMode::Production => error!("Storage corruption detected! Run `snarkos clean` to reset storage"),
+        StorageMode::Development(id) => {
+            error!("Storage corruption detected! Run `snarkos clean --dev {id}` to reset storage")
+        }
+        StorageMode::Custom(path) => {
+            error!("Storage corruption detected! Run `snarkos clean --storage-dir {}` to reset storage", path.display())
+        }
     }
 }
 
 use snarkvm::{
     ledger::store::ConsensusStorage,
-    prelude::{const_assert, hrp2, AleoID, Field, Ledger, Network},
+    prelude::{block::Block, const_assert, hrp2, AleoID, Field, Ledger, Network},
 };
 
+use aleo_std::StorageMode;
 use anyhow::{bail, Result};
 
+/// Loads the ledger from the given storage mode.
+pub fn load_ledger<N: Network, C: ConsensusStorage<N>>(
+    genesis: Block<N>,
+    storage_mode: &StorageMode,
+) -> Result<Ledger<N, C>> {
+    Ledger::load(genesis, storage_mode.clone())
+}
+
 // TODO: Remove me after Phase 3.
 pub fn phase_3_reset<N: Network, C: ConsensusStorage<N>>(
     ledger: Ledger<N, C>,
-    dev: Option<u16>,
+    storage_mode: &StorageMode,
 ) -> Result<Ledger<N, C>> {
     use core::str::FromStr;
 
     /// Removes the specified ledger from storage.
-    pub(crate) fn remove_ledger(network: u16, dev: Option<u16>) -> Result<String> {
+    pub(crate) fn remove_ledger(network: u16, storage_mode: &StorageMode) -> Result<String> {
         // Construct the path to the ledger in storage.
-        let mut path = aleo_std::aleo_ledger_dir(network, aleo_std::StorageMode::from(dev));
+        let mut path = aleo_std::aleo_ledger_dir(network, storage_mode.clone());
 
-        // Delete the parent folder.
-        path.pop();
+        // Delete the parent folder, unless the ledger is in a custom directory.
+        if !matches!(storage_mode, StorageMode::Custom(_)) {
+            path.pop();
+        }
 
         // Prepare the path string.
         let path_string = format!("(in \"{}\")", path.display());
@@ -98,73 +114,97 @@ pub fn phase_3_reset<N: Network, C: ConsensusStorage<N>>(
         if *block.hash() == *ID::<N>::from_str("ab1fxetqjm0ppruay8vlg6gtt52d5fkeydmrk0talp04ymjm65acg9sh8d0r5")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(28251) {
         if *block.hash() == *ID::<N>::from_str("ab1ngmc9wf3kz73lxg9ylx75vday82a26xqthjykzrwyhngnr25uvqqau9eyh")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(28252) {
         if *block.hash() == *ID::<N>::from_str("ab1k6msq00mzrlmm3e0xzgynks5mqh2zrhd35akqqts24sd9u5x9yxs355qgv")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(115314) {
         if *block.hash() == *ID::<N>::from_str("ab13eckyhvhpv5zdhw8xz2zskrmm0a5hgeq7f5sjaw4errx0678pgpsjhuaqf")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(115315) {
         if *block.hash() == *ID::<N>::from_str("ab1axs5ltm6kjezsjxw35taf3xjpherrhpu6868h3ezhc3ap8pyrggqrrkjcg")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(726845) {
         if *block.hash() == *ID::<N>::from_str("ab1tf3v9qef0uh3ygsc0qqem7dzeyy2m8aqz583a80z60l8t5l22u9s84y38z")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(997810) {
         if *block.hash() == *ID::<N>::from_str("ab1pap9sxh5fcskw7l3msax4fq2mrqd80kxp0epx9dguxua2e8dacys78key5")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     } else if let Ok(block) = ledger.get_block(997810) {
         if *block.hash() == *ID::<N>::from_str("ab1fx4mpz0fdqx75djf3n9grsjkc229xfs8fzmjqsxkajtj8j8sdurqufgvyz")? {
             let genesis = ledger.get_block(0)?;
             drop(ledger);
-            println!("{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n", remove_ledger(N::ID, dev)?);
+            println!(
+                "{}.\n\n\nMIGRATION SUCCEEDED. RESTART THIS SNARKOS NODE AGAIN.\n\n",
+                remove_ledger(N::ID, storage_mode)?
+            );
             // Sleep for 5 seconds to allow the user to read the message.
             std::thread::sleep(std::time::Duration::from_secs(5));
-            return Ledger::<N, C>::load(genesis.clone(), dev);
+            return Ledger::<N, C>::load(genesis.clone(), storage_mode.clone());
         }
     }
     Ok(ledger)
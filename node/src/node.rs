@@ -14,6 +14,9 @@
 
 use crate::{traits::NodeInterface, Client, Prover, Validator};
 use snarkos_account::Account;
+use snarkos_node_bft::{storage_service::StorageBackend, BFTConfig};
+use snarkos_node_cdn::CdnConfig;
+use snarkos_node_rest::{IndexConfig, RateLimitConfig, Scope};
 use snarkos_node_router::messages::NodeType;
 use snarkvm::prelude::{
     block::Block,
@@ -24,6 +27,7 @@ use snarkvm::prelude::{
     ViewKey,
 };
 
+use aleo_std::StorageMode;
 use anyhow::Result;
 use std::{net::SocketAddr, sync::Arc};
 
@@ -42,26 +46,36 @@ impl<N: Network> Node<N> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_rate_limits: RateLimitConfig,
+        rest_indexes: IndexConfig,
+        protected_scopes: &[Scope],
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
+        cdn: Option<CdnConfig>,
+        transmission_ttl: u64,
+        bft_config: BFTConfig,
+        bft_storage: StorageBackend,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
             Validator::new(
                 node_ip,
                 bft_ip,
                 rest_ip,
-                rest_rps,
+                rest_rate_limits,
+                rest_indexes,
+                protected_scopes,
                 account,
                 trusted_peers,
                 trusted_validators,
                 genesis,
                 cdn,
-                dev,
+                transmission_ttl,
+                bft_config,
+                bft_storage,
+                storage_mode,
             )
             .await?,
         )))
@@ -73,24 +87,38 @@ impl<N: Network> Node<N> {
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
-        dev: Option<u16>,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, dev).await?)))
+        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, storage_mode).await?)))
     }
 
     /// Initializes a new client node.
     pub async fn new_client(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_rate_limits: RateLimitConfig,
+        rest_indexes: IndexConfig,
+        protected_scopes: &[Scope],
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
+        cdn: Option<CdnConfig>,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
         Ok(Self::Client(Arc::new(
-            Client::new(node_ip, rest_ip, rest_rps, account, trusted_peers, genesis, cdn, dev).await?,
+            Client::new(
+                node_ip,
+                rest_ip,
+                rest_rate_limits,
+                rest_indexes,
+                protected_scopes,
+                account,
+                trusted_peers,
+                genesis,
+                cdn,
+                storage_mode,
+            )
+            .await?,
         )))
     }
 
@@ -16,9 +16,15 @@ mod router;
 
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
-use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService};
+use snarkos_node_bft::{
+    helpers::init_primary_channels,
+    ledger_service::CoreLedgerService,
+    storage_service::StorageBackend,
+    BFTConfig,
+};
+use snarkos_node_cdn::CdnConfig;
 use snarkos_node_consensus::Consensus;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{IndexConfig, RateLimitConfig, Rest, Scope};
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
@@ -40,6 +46,7 @@ use snarkvm::prelude::{
     Network,
 };
 
+use aleo_std::StorageMode;
 use anyhow::Result;
 use core::future::Future;
 use parking_lot::Mutex;
@@ -75,13 +82,18 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         node_ip: SocketAddr,
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
-        rest_rps: u32,
+        rest_rate_limits: RateLimitConfig,
+        rest_indexes: IndexConfig,
+        protected_scopes: &[Scope],
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
-        cdn: Option<String>,
-        dev: Option<u16>,
+        cdn: Option<CdnConfig>,
+        transmission_ttl: u64,
+        bft_config: BFTConfig,
+        bft_storage: StorageBackend,
+        storage_mode: StorageMode,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -90,16 +102,24 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let signal_node = Self::handle_signals(shutdown.clone());
 
         // Initialize the ledger.
-        let ledger = Ledger::load(genesis, dev)?;
+        let ledger = crate::load_ledger(genesis, &storage_mode)?;
         // TODO: Remove me after Phase 3.
-        let ledger = crate::phase_3_reset(ledger, dev)?;
+        let ledger = crate::phase_3_reset(ledger, &storage_mode)?;
         // Initialize the CDN.
-        if let Some(base_url) = cdn {
+        // Note: Unlike a client, a validator syncs from the CDN before it starts the consensus, rather than
+        // concurrently with its peers, as the BFT commits blocks to the ledger as soon as it starts.
+        if let Some(cdn) = cdn {
             // Sync the ledger with the CDN.
-            if let Err((_, error)) =
-                snarkos_node_cdn::sync_ledger_with_cdn(&base_url, ledger.clone(), shutdown.clone()).await
+            if let Err((_, error)) = snarkos_node_cdn::sync_ledger_with_cdn(
+                &cdn.mirrors,
+                &cdn.manifest,
+                ledger.clone(),
+                None,
+                shutdown.clone(),
+            )
+            .await
             {
-                crate::log_clean_error(dev);
+                crate::log_clean_error(&storage_mode);
                 return Err(error);
             }
         }
@@ -110,7 +130,16 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
         // Initialize the consensus.
-        let mut consensus = Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, dev)?;
+        let mut consensus = Consensus::new(
+            bft_config,
+            account.clone(),
+            ledger_service,
+            bft_ip,
+            trusted_validators,
+            transmission_ttl,
+            bft_storage,
+            storage_mode.clone(),
+        )?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -123,7 +152,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             account,
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
-            dev.is_some(),
+            storage_mode.dev().is_some(),
         )
         .await?;
 
@@ -138,12 +167,25 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             shutdown,
         };
         // Initialize the transaction pool.
-        node.initialize_transaction_pool(dev)?;
+        node.initialize_transaction_pool(storage_mode.dev())?;
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest =
-                Some(Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?);
+            // Notify the REST subscribers of the blocks that are committed by consensus.
+            let block_sender = consensus.block_sender().clone();
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rest_rate_limits,
+                    rest_indexes,
+                    protected_scopes,
+                    Some(consensus),
+                    ledger.clone(),
+                    block_sender,
+                    Arc::new(node.clone()),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -450,6 +492,7 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use snarkos_node_consensus::DEFAULT_TRANSMISSION_TTL_IN_SECS;
     use snarkvm::prelude::{
         store::{helpers::memory::ConsensusMemory, ConsensusStore},
         Testnet3,
@@ -487,13 +530,18 @@ mod tests {
             node,
             None,
             Some(rest),
-            10,
+            RateLimitConfig::default(),
+            IndexConfig::default(),
+            &[],
             account,
             &[],
             &[],
             genesis,
             None,
-            dev,
+            DEFAULT_TRANSMISSION_TTL_IN_SECS,
+            BFTConfig::default(),
+            StorageBackend::Memory,
+            StorageMode::from(dev),
         )
         .await
         .unwrap();
@@ -30,11 +30,12 @@ use std::{
     collections::BTreeMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
         Arc,
     },
     time::Instant,
 };
+use tokio::sync::broadcast;
 
 pub const REDUNDANCY_FACTOR: usize = 3;
 const EXTRA_REDUNDANCY_FACTOR: usize = REDUNDANCY_FACTOR * 2;
@@ -42,7 +43,9 @@ const NUM_SYNC_CANDIDATE_PEERS: usize = REDUNDANCY_FACTOR * 5;
 
 const BLOCK_REQUEST_TIMEOUT_IN_SECS: u64 = 15; // 15 seconds
 const MAX_BLOCK_REQUESTS: usize = 50; // 50 requests
+const MAX_CDN_PREFETCH_BLOCKS: u32 = 1000; // 1000 blocks
 const MAX_BLOCK_REQUEST_TIMEOUTS: usize = 5; // 5 timeouts
+const MAX_BLOCK_NOTIFICATIONS: usize = 1024; // 1024 blocks
 
 /// The maximum number of blocks tolerated before the primary is considered behind its peers.
 pub const MAX_BLOCKS_BEHIND: u32 = 2; // blocks
@@ -106,6 +109,11 @@ pub struct BlockSync<N: Network> {
     request_timeouts: Arc<RwLock<IndexMap<SocketAddr, Vec<Instant>>>>,
     /// The boolean indicator of whether the node is synced up to the latest block (within the given tolerance).
     is_block_synced: Arc<AtomicBool>,
+    /// The (exclusive) end height of the blocks being synced from the CDN.
+    /// Blocks below this height are left to the CDN, and are not requested from peers.
+    cdn_height: Arc<AtomicU32>,
+    /// The sender of the blocks that are added to the ledger by the sync module.
+    block_sender: broadcast::Sender<Block<N>>,
     /// The lock to guarantee advance_with_sync_blocks() is called only once at a time.
     advance_with_sync_blocks_lock: Arc<Mutex<()>>,
 }
@@ -123,6 +131,8 @@ impl<N: Network> BlockSync<N> {
             request_timestamps: Default::default(),
             request_timeouts: Default::default(),
             is_block_synced: Default::default(),
+            cdn_height: Default::default(),
+            block_sender: broadcast::channel(MAX_BLOCK_NOTIFICATIONS).0,
             advance_with_sync_blocks_lock: Default::default(),
         }
     }
@@ -138,6 +148,30 @@ impl<N: Network> BlockSync<N> {
     pub fn is_block_synced(&self) -> bool {
         self.is_block_synced.load(Ordering::SeqCst)
     }
+
+    /// Returns the (exclusive) end height of the blocks being synced from the CDN.
+    #[inline]
+    pub fn cdn_height(&self) -> u32 {
+        self.cdn_height.load(Ordering::SeqCst)
+    }
+
+    /// Sets the (exclusive) end height of the blocks being synced from the CDN.
+    ///
+    /// While the CDN sync is running, block requests to peers start at this height, so that the CDN
+    /// and the peers sync disjoint ranges. The peers keep prefetching up to `MAX_CDN_PREFETCH_BLOCKS` blocks
+    /// past this height, which are added to the ledger once the CDN sync reaches it.
+    /// Setting the height to `u32::MAX` holds back the block requests until the CDN height is known,
+    /// and setting the height to `0` hands the entire range back to the peers.
+    #[inline]
+    pub fn set_cdn_height(&self, height: u32) {
+        self.cdn_height.store(height, Ordering::SeqCst);
+    }
+
+    /// Returns the sender of the blocks that are added to the ledger by the sync module, which subscribers can subscribe to.
+    #[inline]
+    pub const fn block_sender(&self) -> &broadcast::Sender<Block<N>> {
+        &self.block_sender
+    }
 }
 
 #[allow(dead_code)]
@@ -317,6 +351,10 @@ impl<N: Network> BlockSync<N> {
                 warn!("{error}");
                 break;
             }
+            // Notify the subscribers of the new block.
+            if self.block_sender.receiver_count() > 0 {
+                let _ = self.block_sender.send(block);
+            }
             // Update the latest height.
             current_height = self.canon.latest_block_height();
         }
@@ -765,14 +803,23 @@ impl<N: Network> BlockSync<N> {
             return vec![];
         }
 
-        // Compute the start height for the block request.
-        let start_height = latest_canon_height + 1;
-        // Compute the end height for the block request.
-        let end_height = (min_common_ancestor + 1).min(start_height + MAX_BLOCK_REQUESTS as u32);
+        // Compute the start height for the block request, skipping the blocks that are synced from the CDN.
+        let start_height = (latest_canon_height + 1).max(self.cdn_height());
+        // Compute the end height for the block request. While the CDN sync is running, the blocks after
+        // the CDN height are prefetched, as they cannot be added to the ledger until the CDN sync reaches them.
+        let max_num_blocks = match start_height > latest_canon_height + 1 {
+            true => MAX_CDN_PREFETCH_BLOCKS,
+            false => MAX_BLOCK_REQUESTS as u32,
+        };
+        let end_height = (min_common_ancestor + 1).min(start_height.saturating_add(max_num_blocks));
 
-        let mut requests = Vec::with_capacity((start_height..end_height).len());
+        let mut requests = Vec::with_capacity((start_height..end_height).len().min(MAX_BLOCK_REQUESTS));
 
         for height in start_height..end_height {
+            // Ensure the number of requests is within the limit.
+            if requests.len() >= MAX_BLOCK_REQUESTS {
+                break;
+            }
             // Ensure the current height is not canonized or already requested.
             if self.check_block_request(height).is_err() {
                 continue;
@@ -1102,6 +1149,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prepare_block_requests_with_cdn_height() {
+        let sync = sample_sync_at_height(0);
+
+        // Add the peers.
+        for peer_id in 1..=REDUNDANCY_FACTOR as u16 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(200)).unwrap();
+        }
+
+        // When the CDN syncs up to block 100, the sync pool should request the blocks after it.
+        sync.set_cdn_height(100);
+        let requests = sync.prepare_block_requests();
+        assert_eq!(requests.len(), MAX_BLOCK_REQUESTS);
+        for (idx, (height, _)) in requests.into_iter().enumerate() {
+            assert_eq!(height, 100 + idx as u32);
+        }
+
+        // When the CDN syncs beyond the peers, the sync pool should not request any blocks.
+        sync.set_cdn_height(300);
+        assert!(sync.prepare_block_requests().is_empty());
+
+        // While the CDN height is unknown, the sync pool should not request any blocks.
+        sync.set_cdn_height(u32::MAX);
+        assert!(sync.prepare_block_requests().is_empty());
+
+        // When the CDN hands the range back, the sync pool should request the blocks after the canon height.
+        sync.set_cdn_height(0);
+        let requests = sync.prepare_block_requests();
+        assert_eq!(requests.len(), MAX_BLOCK_REQUESTS);
+        for (idx, (height, _)) in requests.into_iter().enumerate() {
+            assert_eq!(height, 1 + idx as u32);
+        }
+    }
+
+    #[test]
+    fn test_prepare_block_requests_while_cdn_syncs() {
+        let ledger = Arc::new(sample_ledger_service(0));
+        let sync = BlockSync::<CurrentNetwork>::new(BlockSyncMode::Router, ledger.clone());
+
+        // Add the peers.
+        for peer_id in 1..=REDUNDANCY_FACTOR as u16 {
+            sync.update_peer_locators(sample_peer_ip(peer_id), sample_block_locators(2000)).unwrap();
+        }
+
+        // Leave the blocks up to block 100 to the CDN.
+        sync.set_cdn_height(100);
+        let mut next_height = 100;
+        for cdn_progress in [0, 20, 40, 60, 80] {
+            // Advance the ledger, as the CDN sync adds blocks to it.
+            ledger.advance_to_height(cdn_progress);
+            // Ensure the peers keep syncing the blocks after the CDN height in the meantime.
+            let requests = sync.prepare_block_requests();
+            assert_eq!(requests.len(), MAX_BLOCK_REQUESTS);
+            for (idx, (height, request)) in requests.into_iter().enumerate() {
+                assert_eq!(height, next_height + idx as u32);
+                sync.insert_block_request(height, request).unwrap();
+            }
+            next_height += MAX_BLOCK_REQUESTS as u32;
+        }
+        assert_eq!(ledger.latest_block_height(), 80);
+        assert_eq!(sync.requests.read().len(), 5 * MAX_BLOCK_REQUESTS);
+
+        // Ensure the peers stop prefetching once the prefetched blocks reach the limit.
+        for _ in 0..(MAX_CDN_PREFETCH_BLOCKS as usize / MAX_BLOCK_REQUESTS) {
+            for (height, request) in sync.prepare_block_requests() {
+                sync.insert_block_request(height, request).unwrap();
+            }
+        }
+        assert_eq!(sync.requests.read().len(), MAX_CDN_PREFETCH_BLOCKS as usize);
+        assert!(sync.prepare_block_requests().is_empty());
+    }
+
     #[test]
     fn test_prepare_block_requests_with_trailing_fork_at_9() {
         let sync = sample_sync_at_height(0);
@@ -14,21 +14,31 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{
+    bft::{storage_service::StorageBackend, BFTConfig},
+    consensus::DEFAULT_TRANSMISSION_TTL_IN_SECS,
+    rest::{IndexConfig, RateLimitConfig},
+    Client,
+    Prover,
+    Validator,
+};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, Testnet3 as CurrentNetwork};
 
+use aleo_std::StorageMode;
 use std::str::FromStr;
 
 pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Client::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
-        10,
+        RateLimitConfig::default(),
+        IndexConfig::default(),
+        &[],
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
         None, // No CDN.
-        None,
+        StorageMode::Production,
     )
     .await
     .expect("couldn't create client instance")
@@ -40,7 +50,7 @@ pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
-        None,
+        StorageMode::Production,
     )
     .await
     .expect("couldn't create prover instance")
@@ -51,13 +61,18 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
         "127.0.0.1:0".parse().unwrap(),
         None,
         None,
-        10,
+        RateLimitConfig::default(),
+        IndexConfig::default(),
+        &[],
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         &[],
         sample_genesis_block(), // Should load the current network's genesis block.
         None,                   // No CDN.
-        None,
+        DEFAULT_TRANSMISSION_TTL_IN_SECS,
+        BFTConfig::default(),   // Use the default BFT parameters.
+        StorageBackend::Memory, // Use the in-memory BFT storage.
+        StorageMode::Production,
     )
     .await
     .expect("couldn't create validator instance")
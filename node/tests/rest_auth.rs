@@ -0,0 +1,86 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[allow(dead_code)]
+mod common;
+use common::{sample_account, sample_genesis_block};
+
+use snarkos_node::{
+    rest::{Claims, IndexConfig, RateLimitConfig, Scope},
+    Client,
+    NodeInterface,
+};
+use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, Testnet3 as CurrentNetwork};
+
+use aleo_std::StorageMode;
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Sends the given JSON-RPC request to the REST server, and returns the status code of the response.
+async fn rpc_status(rest_ip: SocketAddr, body: &str, token: Option<&str>) -> u16 {
+    let auth = token.map(|token| format!("Authorization: Bearer {token}\r\n")).unwrap_or_default();
+    let request = format!(
+        "POST /testnet3/rpc HTTP/1.1\r\nHost: {rest_ip}\r\nContent-Type: application/json\r\n{auth}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    let mut stream = TcpStream::connect(rest_ip).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    // Parse the status code from the status line, e.g. `HTTP/1.1 200 OK`.
+    response.split_whitespace().nth(1).unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn test_rpc_requires_the_scope_of_each_method() {
+    // Reserve a port for the REST server.
+    let rest_ip = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+    // Start a client that only protects the read scope, as with `--rest-auth read`.
+    let client = Client::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new(
+        "127.0.0.1:0".parse().unwrap(),
+        Some(rest_ip),
+        RateLimitConfig::default(),
+        IndexConfig::default(),
+        &[Scope::Read],
+        sample_account(),
+        &[],
+        sample_genesis_block(),
+        None, // No CDN.
+        StorageMode::Production,
+    )
+    .await
+    .unwrap();
+
+    let get_blocks = r#"{ "jsonrpc": "2.0", "method": "getBlocks", "params": { "start": 0, "end": 1 }, "id": 1 }"#;
+    // Ensure a read method is unauthorized without a token, even though the broadcast scope is not protected.
+    assert_eq!(rpc_status(rest_ip, get_blocks, None).await, 401);
+    // Ensure the same holds when the read method is hidden in a batch.
+    assert_eq!(rpc_status(rest_ip, &format!("[{get_blocks}]"), None).await, 401);
+
+    // Ensure a token without the read scope is forbidden.
+    let token = Claims::new_with_scopes("broadcaster".to_string(), vec![Scope::Broadcast], 60).to_jwt_string().unwrap();
+    assert_eq!(rpc_status(rest_ip, get_blocks, Some(&token)).await, 403);
+
+    // Ensure a token with the read scope is authorized.
+    let token = Claims::new_with_scopes("reader".to_string(), vec![Scope::Read], 60).to_jwt_string().unwrap();
+    assert_eq!(rpc_status(rest_ip, get_blocks, Some(&token)).await, 200);
+
+    client.shut_down().await;
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[allow(dead_code)]
+mod common;
+use common::{sample_account, sample_genesis_block};
+
+use snarkos_node::{
+    bft::{storage_service::StorageBackend, BFTConfig},
+    consensus::DEFAULT_TRANSMISSION_TTL_IN_SECS,
+    rest::{IndexConfig, RateLimitConfig},
+    Client,
+    NodeInterface,
+    Validator,
+};
+use snarkvm::prelude::{store::helpers::rocksdb::ConsensusDB, Testnet3 as CurrentNetwork};
+
+use aleo_std::StorageMode;
+
+#[tokio::test]
+async fn test_start_with_custom_storage_dir() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let storage_dir = temp_dir.path().join("storage");
+    let storage_mode = StorageMode::Custom(storage_dir.clone());
+
+    // Start a client with the ledger in the custom directory.
+    let client = Client::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::new(
+        "127.0.0.1:0".parse().unwrap(),
+        None,
+        RateLimitConfig::default(),
+        IndexConfig::default(),
+        &[],
+        sample_account(),
+        &[],
+        sample_genesis_block(),
+        None, // No CDN.
+        storage_mode.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(client.ledger().latest_height(), 0);
+    assert!(storage_dir.exists());
+    client.shut_down().await;
+    drop(client);
+
+    // Restart the node as a validator on the same directory, with the BFT storage next to the ledger.
+    let validator = Validator::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::new(
+        "127.0.0.1:0".parse().unwrap(),
+        None,
+        None,
+        RateLimitConfig::default(),
+        IndexConfig::default(),
+        &[],
+        sample_account(),
+        &[],
+        &[],
+        sample_genesis_block(),
+        None, // No CDN.
+        DEFAULT_TRANSMISSION_TTL_IN_SECS,
+        BFTConfig::default(),
+        StorageBackend::File,
+        storage_mode,
+    )
+    .await
+    .unwrap();
+    assert_eq!(validator.ledger().latest_height(), 0);
+    assert!(storage_dir.join("bft").exists());
+    validator.shut_down().await;
+}